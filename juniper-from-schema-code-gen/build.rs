@@ -0,0 +1,46 @@
+//! Fingerprints the source of the code generator, so cached macro output is invalidated when the
+//! code generator changes, even if its version doesn't.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    let src = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("src");
+    println!("cargo:rerun-if-changed={}", src.display());
+
+    let mut files = Vec::new();
+    collect_files(&src, &mut files);
+    files.sort();
+
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for file in files {
+        let path = file
+            .strip_prefix(&src)
+            .unwrap()
+            .to_string_lossy()
+            .into_owned();
+        let contents = fs::read(&file).unwrap();
+        for byte in path.bytes().chain(contents) {
+            hash = (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3);
+        }
+        println!("cargo:rerun-if-changed={}", file.display());
+    }
+
+    println!(
+        "cargo:rustc-env=JUNIPER_FROM_SCHEMA_CODE_GEN_FINGERPRINT={:016x}",
+        hash
+    );
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
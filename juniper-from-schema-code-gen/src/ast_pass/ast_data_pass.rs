@@ -1,5 +1,5 @@
 use crate::ast_pass::{
-    directive_parsing::{DateTimeScalarType, ParseDirective},
+    directive_parsing::{DateTimeScalarType, FieldDefaults, ParseDirective},
     error::{Error, ErrorKind},
     schema_visitor::SchemaVisitor,
     type_name, EmitError,
@@ -19,9 +19,14 @@ pub struct AstData<'doc> {
     errors: BTreeSet<Error<'doc>>,
     raw_schema: &'doc str,
     include_time_zone_on_date_time_scalar: bool,
+    field_defaults: FieldDefaults,
 }
 
 impl<'doc> SchemaVisitor<'doc> for AstData<'doc> {
+    fn visit_schema_definition(&mut self, schema_def: &'doc SchemaDefinition) {
+        self.field_defaults = FieldDefaults::of_schema(schema_def);
+    }
+
     fn visit_object_type(&mut self, obj: &'doc ObjectType) {
         for interface in &obj.implements_interfaces {
             self.interface_implementors
@@ -86,9 +91,15 @@ impl<'doc> AstData<'doc> {
             errors: Default::default(),
             raw_schema,
             include_time_zone_on_date_time_scalar: true,
+            field_defaults: FieldDefaults::default(),
         }
     }
 
+    /// The defaults for field options set with `@juniper` on `schema { }`.
+    pub fn field_defaults(&self) -> FieldDefaults {
+        self.field_defaults
+    }
+
     pub fn get_implementors_of_interface(&self, name: &str) -> Option<&Vec<&str>> {
         self.interface_implementors.get(name)
     }
@@ -97,10 +108,6 @@ impl<'doc> AstData<'doc> {
         self.is_scalar(crate::DATE_SCALAR_NAME)
     }
 
-    pub fn date_time_scalar_defined(&self) -> bool {
-        self.is_scalar(crate::DATE_TIME_SCALAR_NAME)
-    }
-
     pub fn date_time_scalar_definition(&self) -> Option<DateTimeScalarDefinition> {
         if self.is_scalar(crate::DATE_TIME_SCALAR_NAME) {
             if self.include_time_zone_on_date_time_scalar {
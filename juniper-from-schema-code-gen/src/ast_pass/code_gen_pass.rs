@@ -7,10 +7,14 @@ use super::{
 use crate::{
     ast_pass::{
         ast_data_pass::{AstData, DateTimeScalarDefinition},
-        directive_parsing::{Deprecation, FieldArguments, Ownership, ParseDirective},
+        directive_parsing::{
+            Deprecation, FieldDefaults, FieldWithDefaults, Ownership, ParseDirective,
+        },
         schema_visitor::SchemaVisitor,
     },
+    naming::Naming,
     nullable_type::NullableType,
+    type_attributes::{AttributesConfig, GeneratedType, TypeAttributes},
 };
 use graphql_parser::{
     query::{Name, Type},
@@ -32,6 +36,8 @@ pub struct CodeGenPass<'doc> {
     tokens: TokenStream,
     error_type: syn::Type,
     context_type: syn::Type,
+    naming: Naming,
+    attributes: AttributesConfig,
     errors: BTreeSet<Error<'doc>>,
     ast_data: AstData<'doc>,
     raw_schema: &'doc str,
@@ -61,9 +67,10 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
             }
         };
 
+        let schema_name = self.naming.schema_name();
         self.extend(quote! {
             /// The GraphQL schema type generated by `juniper-from-schema`.
-            pub type Schema = juniper::RootNode<'static, #query, #mutation>;
+            pub type #schema_name = juniper::RootNode<'static, #query, #mutation>;
         });
     }
 
@@ -84,7 +91,14 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                 || name == crate::URL_SCALAR_NAME
                 || name == crate::UUID_SCALAR_NAME =>
             {
-                self.parse_directives(scalar_type);
+                let attributes = self.parse_directives(scalar_type);
+
+                if !attributes.derives.is_empty() || !attributes.attributes.is_empty() {
+                    self.emit_non_fatal_error(
+                        scalar_type.position,
+                        ErrorKind::SpecialCaseScalarWithAttributes,
+                    );
+                }
 
                 if scalar_type.description.is_some() {
                     self.emit_non_fatal_error(
@@ -94,7 +108,7 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                 }
             }
             name => {
-                self.parse_directives(scalar_type);
+                let attributes = self.parse_directives(scalar_type);
 
                 let name = ident(name);
                 let description = &scalar_type
@@ -103,24 +117,34 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                     .map(|desc| quote! { description: #desc })
                     .unwrap_or(quote! {});
 
-                self.gen_scalar_type_with_data(&name, &description);
+                self.gen_scalar_type_with_data(&name, &description, attributes);
             }
         };
     }
 
     fn visit_object_type(&mut self, obj_type: &'doc ObjectType) {
-        self.parse_directives(obj_type);
+        let field_defaults = self
+            .parse_directives(obj_type)
+            .or(self.ast_data.field_defaults());
 
         let struct_name = ident(&obj_type.name);
 
-        let trait_name = trait_map_for_struct_name(&struct_name);
+        let trait_name = self.naming.fields_trait(&obj_type.name);
 
         let field_tokens = obj_type
             .fields
             .iter()
-            .map(|field| self.collect_data_for_field_gen(field))
+            .map(|field| self.collect_data_for_field_gen(&obj_type.name, field, field_defaults))
             .collect::<Vec<_>>();
 
+        // Only objects get arguments structs. Interface fields call the field methods of their
+        // implementors, which take the implementors' structs.
+        for (field, tokens) in obj_type.fields.iter().zip(&field_tokens) {
+            if tokens.arguments_struct {
+                self.gen_arguments_struct(&obj_type.name, field);
+            }
+        }
+
         let trait_methods = field_tokens
             .iter()
             .map(|field| {
@@ -128,19 +152,41 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                 let args = &field.trait_args;
                 let context_type = &self.context_type;
                 let return_type = self.field_return_type_tokens(&field);
+                let executor = if field.executor {
+                    quote! { executor: &juniper::Executor<'a, #context_type> }
+                } else {
+                    quote! { ctx: &'a #context_type }
+                };
 
-                match field.type_kind {
-                    TypeKind::Scalar => {
+                match (field.type_kind, field.trail) {
+                    (TypeKind::Scalar, _) | (TypeKind::Type, false) => {
                         quote! {
                             /// Field method generated by `juniper-from-schema`.
                             fn #field_name<'a>(
                                 &self,
-                                executor: &juniper::Executor<'a, #context_type>,
+                                #executor,
                                 #(#args),*
                             ) -> #return_type;
                         }
                     }
-                    TypeKind::Type => {
+                    (TypeKind::Type, true) if field.borrows_from_context => {
+                        // The trail borrows a look ahead that only lives as long as the field is
+                        // being resolved, so it can't share a lifetime with the returned reference.
+                        let query_trail_type = ident(&field.inner_type);
+                        let trail = quote! {
+                            &QueryTrail<'r, #query_trail_type, juniper_from_schema::Walked>
+                        };
+
+                        quote! {
+                            /// Field method generated by `juniper-from-schema`.
+                            fn #field_name<'a, 'r>(
+                                &self,
+                                #executor,
+                                trail: #trail, #(#args),*
+                            ) -> #return_type;
+                        }
+                    }
+                    (TypeKind::Type, true) => {
                         let query_trail_type = ident(&field.inner_type);
                         let trail = quote! {
                             &QueryTrail<'a, #query_trail_type, juniper_from_schema::Walked>
@@ -150,7 +196,7 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                             /// Field method generated by `juniper-from-schema`.
                             fn #field_name<'a>(
                                 &self,
-                                executor: &juniper::Executor<'a, #context_type>,
+                                #executor,
                                 trail: #trail, #(#args),*
                             ) -> #return_type;
                         }
@@ -200,7 +246,11 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
     }
 
     fn visit_interface_type(&mut self, interface: &'doc InterfaceType) {
-        self.parse_directives(interface);
+        let (attributes, field_defaults) = self.parse_directives(interface);
+        let attributes = self
+            .attributes
+            .tokens_for(GeneratedType::Interface, attributes);
+        let field_defaults = field_defaults.or(self.ast_data.field_defaults());
 
         let interface_name = ident(&interface.name);
 
@@ -225,6 +275,7 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
             quote! { #name(#name) }
         });
         self.extend(quote! {
+            #attributes
             pub enum #interface_name {
                 #(#variants),*
             }
@@ -251,7 +302,7 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
         let field_tokens: Vec<FieldTokens> = interface
             .fields
             .iter()
-            .map(|field| self.collect_data_for_field_gen(field))
+            .map(|field| self.collect_data_for_field_gen(&interface.name, field, field_defaults))
             .collect::<Vec<_>>();
 
         let field_token_streams = field_tokens
@@ -263,10 +314,16 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                 let description = doc_tokens(&field.description);
 
                 let arms = implementors.iter().map(|variant| {
-                    let trait_name = trait_map_for_struct_name(&variant);
+                    let trait_name = self.naming.fields_trait(&variant.to_string());
                     let struct_name = variant;
 
-                    let body = gen_field_body(&field, &quote! {inner}, &struct_name, &trait_name);
+                    let body = gen_field_body(
+                        &field,
+                        &quote! {inner},
+                        &struct_name,
+                        &trait_name,
+                        &self.naming,
+                    );
 
                     quote! {
                         #interface_name::#struct_name(ref inner) => {
@@ -277,7 +334,7 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
 
                 let all_args = to_field_args_list(&args);
                 let deprecation = &field.deprecation;
-                let return_type = self.field_return_type_tokens(&field);
+                let return_type = self.resolved_return_type_tokens(&field);
 
                 quote! {
                     #description
@@ -308,7 +365,8 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
     }
 
     fn visit_union_type(&mut self, union: &'doc UnionType) {
-        self.parse_directives(union);
+        let attributes = self.parse_directives(union);
+        let attributes = self.attributes.tokens_for(GeneratedType::Union, attributes);
 
         let union_name = ident(&union.name);
         let implementors = union.types.iter().map(ident).collect::<Vec<_>>();
@@ -318,6 +376,7 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
             quote! { #name(#name) }
         });
         self.extend(quote! {
+            #attributes
             pub enum #union_name {
                 #(#variants),*
             }
@@ -362,9 +421,11 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
     }
 
     fn visit_enum_type(&mut self, enum_type: &'doc EnumType) {
-        self.parse_directives(enum_type);
+        let attributes = self.parse_directives(enum_type);
+        let attributes = self.attributes.tokens_for(GeneratedType::Enum, attributes);
 
         let name = to_enum_name(&enum_type.name);
+        let enum_name = &enum_type.name;
 
         let values = enum_type
             .values
@@ -376,39 +437,35 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
         let string_to_enum_value_mappings = enum_type.values.iter().map(|enum_value| {
             let graphql_name = &enum_value.name;
             let variant = to_enum_name(&graphql_name);
-            quote! { &#graphql_name => #name::#variant, }
+            quote! { &#graphql_name => Ok(#name::#variant), }
         });
 
         let code = quote! {
             #description
             #[derive(juniper::GraphQLEnum, Debug, Eq, PartialEq, Copy, Clone, Hash)]
+            #attributes
             pub enum #name {
                 #(#values)*
             }
 
-            impl<'a, 'b> query_trails::FromLookAheadValue<#name>
+            impl<'a, 'b> juniper_from_schema::FromLookAheadValue<#name>
                 for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
             {
-                fn from(self) -> #name {
+                fn from(self) -> Result<#name, juniper_from_schema::ConversionError> {
                     match self {
                         juniper::LookAheadValue::Enum(name) => {
                             match name {
                                 #(#string_to_enum_value_mappings)*
-                                other => panic!("Invalid enum name: {}", other),
+                                other => Err(juniper_from_schema::ConversionError::new(
+                                    #enum_name,
+                                    format!("got unknown value `{}`", other),
+                                )),
                             }
                         },
-                        juniper::LookAheadValue::Null => panic!(
-                            "Failed converting look ahead value. Expected enum type got `null`",
-                        ),
-                        juniper::LookAheadValue::List(_) => panic!(
-                            "Failed converting look ahead value. Expected enum type got `list`",
-                        ),
-                        juniper::LookAheadValue::Object(_) => panic!(
-                            "Failed converting look ahead value. Expected enum type got `object`",
-                        ),
-                        juniper::LookAheadValue::Scalar(_) => panic!(
-                            "Failed converting look ahead value. Expected enum type got `scalar`",
-                        ),
+                        other => Err(juniper_from_schema::ConversionError::unexpected_value(
+                            #enum_name,
+                            other,
+                        )),
                     }
                 }
             }
@@ -417,9 +474,13 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
     }
 
     fn visit_input_object_type(&mut self, input_object: &'doc InputObjectType) {
-        self.parse_directives(input_object);
+        let attributes = self.parse_directives(input_object);
+        let attributes = self
+            .attributes
+            .tokens_for(GeneratedType::InputObject, attributes);
 
         let name = ident(&input_object.name);
+        let input_name = &input_object.name;
 
         let fields = input_object
             .fields
@@ -433,6 +494,15 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                 }
 
                 let arg = self.argument_to_name_and_rust_type(&field);
+                if arg.borrow.is_some() {
+                    self.emit_non_fatal_error(
+                        field.position,
+                        ErrorKind::OwnershipNotSupportedForType {
+                            ownership: Ownership::Borrowed.name(),
+                            supported: "arguments, not input object fields",
+                        },
+                    );
+                }
                 let name = ident(arg.name);
                 let rust_type = arg.macro_type;
 
@@ -464,8 +534,23 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                 let arg = self.argument_to_name_and_rust_type(&field);
                 let name = ident(&arg.name);
                 let temp_name = ident(&format!("{}_temp", arg.name));
-                quote! {
-                    #name: #temp_name.unwrap_or_else(|| panic!("Field `{}` was not set", stringify!(#name))),
+                let graphql_name = &field.name;
+
+                match field.value_type {
+                    schema::Type::NonNullType(_) => quote! {
+                        #name: match #temp_name {
+                            Some(value) => value,
+                            None => {
+                                return Err(juniper_from_schema::ConversionError::new(
+                                    #input_name,
+                                    format!("missing field `{}`", #graphql_name),
+                                ))
+                            }
+                        },
+                    },
+                    _ => quote! {
+                        #name: #temp_name.unwrap_or(None),
+                    },
                 }
             })
             .collect::<Vec<_>>();
@@ -481,9 +566,9 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                 quote! {
                     #name => {
                         #temp_name = Some(
-                            query_trails::FromLookAheadValue::<#rust_type>::from(
+                            juniper_from_schema::FromLookAheadValue::<#rust_type>::from(
                                 look_ahead_value
-                            )
+                            ).map_err(|err| err.in_path(*look_ahead_key))?
                         );
                     },
                 }
@@ -492,15 +577,16 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
 
         let code = quote! {
             #[derive(juniper::GraphQLInputObject, Debug, Clone)]
+            #attributes
             #description
             pub struct #name {
                 #(#fields),*
             }
 
-            impl<'a, 'b> query_trails::FromLookAheadValue<#name>
+            impl<'a, 'b> juniper_from_schema::FromLookAheadValue<#name>
                 for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
             {
-                fn from(self) -> #name {
+                fn from(self) -> Result<#name, juniper_from_schema::ConversionError> {
                     match self {
                         juniper::LookAheadValue::Object(pairs) => {
                             #(
@@ -509,25 +595,22 @@ impl<'doc> SchemaVisitor<'doc> for CodeGenPass<'doc> {
                             for (look_ahead_key, look_ahead_value) in pairs {
                                 match *look_ahead_key {
                                     #(#temp_field_setters)*
-                                    other => panic!("Invalid input object key: {}", other),
+                                    other => {
+                                        return Err(juniper_from_schema::ConversionError::new(
+                                            #input_name,
+                                            format!("got unknown field `{}`", other),
+                                        ))
+                                    }
                                 }
                             }
-                            #name {
+                            Ok(#name {
                                 #(#field_setters)*
-                            }
+                            })
                         },
-                        juniper::LookAheadValue::Enum(_) => panic!(
-                            "Failed converting look ahead value. Expected object type got `enum`",
-                        ),
-                        juniper::LookAheadValue::Null => panic!(
-                            "Failed converting look ahead value. Expected object type got `null`",
-                        ),
-                        juniper::LookAheadValue::List(_) => panic!(
-                            "Failed converting look ahead value. Expected object type got `list`",
-                        ),
-                        juniper::LookAheadValue::Scalar(_) => panic!(
-                            "Failed converting look ahead value. Expected object type got `scalar`",
-                        ),
+                        other => Err(juniper_from_schema::ConversionError::unexpected_value(
+                            #input_name,
+                            other,
+                        )),
                     }
                 }
             }
@@ -571,12 +654,16 @@ impl<'doc> CodeGenPass<'doc> {
         raw_schema: &'doc str,
         error_type: syn::Type,
         context_type: syn::Type,
+        naming: Naming,
+        attributes: AttributesConfig,
         ast_data: AstData<'doc>,
     ) -> Self {
         CodeGenPass {
             tokens: quote! {},
             error_type,
             context_type,
+            naming,
+            attributes,
             ast_data,
             errors: BTreeSet::new(),
             raw_schema,
@@ -615,35 +702,39 @@ impl<'doc> CodeGenPass<'doc> {
         Err(())
     }
 
-    fn gen_scalar_type_with_data(&mut self, name: &Ident, description: &TokenStream) {
+    fn gen_scalar_type_with_data(
+        &mut self,
+        name: &Ident,
+        description: &TokenStream,
+        attributes: TypeAttributes,
+    ) {
         // We explicity don't implement `From<T> where T: Into<String>` because then users wouldn't
         // be able to add their own `From` implementations, since `From<T>` overlaps with other
         // implementations.
+        let graphql_name = name.to_string();
+        let attributes = self
+            .attributes
+            .tokens_for(GeneratedType::Scalar, attributes);
+
         self.extend(quote! {
             /// Custom scalar type generated by `juniper-from-schema`.
             #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Hash)]
+            #attributes
             pub struct #name(pub String);
 
             juniper::graphql_scalar!(#name {
                 #description
 
                 resolve(&self) -> juniper::Value {
-                    juniper::Value::scalar(juniper::DefaultScalarValue::from(self.0.as_ref()))
+                    juniper_from_schema::resolve_custom_scalar(&self.0)
                 }
 
                 from_input_value(v: &InputValue) -> Option<#name> {
-                    let scalar = v.as_scalar_value();
-                    match scalar {
-                        Some(juniper::DefaultScalarValue::String(s)) => {
-                            Some(#name::new(s.to_owned()))
-                        }
-                        Some(_) => None,
-                        None => None,
-                    }
+                    juniper_from_schema::custom_scalar_from_input_value(v).map(#name)
                 }
 
                 from_str<'a>(value: ScalarToken<'a>) -> juniper::ParseScalarResult<'a> {
-                    <String as juniper::ParseScalarValue>::from_str(value)
+                    juniper_from_schema::parse_custom_scalar(value)
                 }
             });
 
@@ -667,19 +758,19 @@ impl<'doc> CodeGenPass<'doc> {
                 }
             }
 
-            impl<'a, 'b> query_trails::FromLookAheadValue<#name>
+            impl<'a, 'b> juniper_from_schema::FromLookAheadValue<#name>
                 for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
             {
-                fn from(self) -> #name {
-                    let s = query_trails::FromLookAheadValue::<String>::from(self);
-                    #name(s)
+                fn from(self) -> Result<#name, juniper_from_schema::ConversionError> {
+                    juniper_from_schema::custom_scalar_from_look_ahead_value(self, #graphql_name)
+                        .map(#name)
                 }
             }
         })
     }
 
     fn argument_to_name_and_rust_type(&mut self, arg: &'doc InputValue) -> FieldArgument<'doc> {
-        self.parse_directives(arg);
+        let ownership = self.parse_directives(arg);
 
         let default_value_tokens = arg
             .default_value
@@ -688,33 +779,88 @@ impl<'doc> CodeGenPass<'doc> {
 
         let arg_name = arg.name.to_snake_case();
 
-        let (macro_type, _) = self.gen_field_type(
-            &arg.value_type,
-            &FieldTypeDestination::Argument,
-            false,
-            arg.position,
-        );
+        let (macro_type, _) = self.gen_field_type(&arg.value_type, false, arg.position);
 
         let (trait_type, _) = self.gen_field_type(
             &arg.value_type,
-            &FieldTypeDestination::Argument,
             default_value_tokens.is_some(),
             arg.position,
         );
 
+        let (trait_type, borrow) = match ownership {
+            Ownership::Borrowed => {
+                let (trait_type, borrow) = self.gen_borrowed_argument_type(
+                    &arg.value_type,
+                    default_value_tokens.is_some(),
+                    arg.position,
+                );
+                (trait_type, Some(borrow))
+            }
+            _ => (trait_type, None),
+        };
+
         FieldArgument {
             name: arg_name,
             macro_type,
             trait_type,
             default_value: default_value_tokens,
+            borrow,
             description: &arg.description,
+            position: arg.position,
+        }
+    }
+
+    /// The type of an argument with `@juniper(ownership: "borrowed")`, along with how to borrow
+    /// the owned value.
+    fn gen_borrowed_argument_type(
+        &mut self,
+        arg_type: &Type,
+        has_default_value: bool,
+        pos: Pos,
+    ) -> (TokenStream, ArgumentBorrow) {
+        let arg_type = NullableType::from_schema_type(arg_type);
+        let arg_type = if has_default_value {
+            arg_type.remove_one_layer_of_nullability()
+        } else {
+            arg_type
+        };
+
+        match arg_type {
+            NullableType::NullableType(inner) => {
+                let (tokens, derefs) = self.gen_borrowed_type(*inner, pos);
+                let borrow = if derefs {
+                    ArgumentBorrow::AsDeref
+                } else {
+                    ArgumentBorrow::AsRef
+                };
+                (quote! { Option<#tokens> }, borrow)
+            }
+            other => {
+                let (tokens, _) = self.gen_borrowed_type(other, pos);
+                (tokens, ArgumentBorrow::Ref)
+            }
+        }
+    }
+
+    /// Borrow a non-null type. Also returns whether the borrowed type is the `Deref` target of
+    /// the owned type, rather than a reference to it.
+    fn gen_borrowed_type(&mut self, ty: NullableType, pos: Pos) -> (TokenStream, bool) {
+        match ty {
+            NullableType::NamedType(name) if name == "String" => (quote! { &str }, true),
+            NullableType::ListType(item) => {
+                let (item, _) = self.gen_nullable_field_type(*item, false, pos);
+                (quote! { &[#item] }, true)
+            }
+            other => {
+                let (owned, _) = self.gen_nullable_field_type(other, false, pos);
+                (quote! { &#owned }, false)
+            }
         }
     }
 
     fn gen_field_type(
         &mut self,
         field_type: &Type,
-        destination: &FieldTypeDestination,
         has_default_value: bool,
         pos: Pos,
     ) -> (TokenStream, TypeKind) {
@@ -730,28 +876,115 @@ impl<'doc> CodeGenPass<'doc> {
             field_type
         };
 
-        let as_ref = match destination {
-            FieldTypeDestination::Return(attrs) => match attrs.ownership {
-                Ownership::AsRef => true,
-                Ownership::Borrowed => false,
-                Ownership::Owned => false,
-            },
-            FieldTypeDestination::Argument => false,
-        };
+        self.gen_nullable_field_type(field_type, false, pos)
+    }
+
+    /// The type returned by a field method, with the given ownership.
+    fn gen_return_type(
+        &mut self,
+        field_type: &Type,
+        ownership: Ownership,
+        pos: Pos,
+    ) -> FieldReturnType {
+        let field_type = NullableType::from_schema_type(field_type);
+        let (owned, type_kind) = self.gen_nullable_field_type(field_type.clone(), false, pos);
 
-        let (tokens, ty) = self.gen_nullable_field_type(field_type, as_ref, pos);
+        let mut unsupported = |supported| {
+            self.emit_non_fatal_error(
+                pos,
+                ErrorKind::OwnershipNotSupportedForType {
+                    ownership: ownership.name(),
+                    supported,
+                },
+            );
+            FieldReturnType::same(owned.clone(), type_kind)
+        };
 
-        match (destination, ty) {
-            (FieldTypeDestination::Return(attrs), ref ty) => match attrs.ownership {
-                Ownership::Owned | Ownership::AsRef => (tokens, *ty),
-                Ownership::Borrowed => (quote! { &#tokens }, *ty),
+        // The list type, if the field is a list or a nullable list.
+        let list_item = match &field_type {
+            NullableType::ListType(item) => Some((false, &**item)),
+            NullableType::NullableType(inner) => match &**inner {
+                NullableType::ListType(item) => Some((true, &**item)),
+                _ => None,
             },
+            NullableType::NamedType(_) => None,
+        };
 
-            (FieldTypeDestination::Argument, ty @ TypeKind::Scalar) => (tokens, ty),
-            (FieldTypeDestination::Argument, ty @ TypeKind::Type) => (tokens, ty),
+        match ownership {
+            Ownership::Owned => FieldReturnType::same(owned, type_kind),
+            Ownership::Borrowed => FieldReturnType::same(quote! { &#owned }, type_kind),
+            // `'a` is the lifetime of the executor, and therefore of `executor.context()`.
+            Ownership::BorrowedFromContext => FieldReturnType {
+                trait_type: quote! { &'a #owned },
+                resolved_type: quote! { &#owned },
+                conversion: None,
+                type_kind,
+            },
+            Ownership::AsRef => {
+                if let NullableType::NamedType(_) = field_type {
+                    self.emit_non_fatal_error(pos, ErrorKind::AsRefOwnershipForNamedType);
+                    return FieldReturnType::same(owned, type_kind);
+                }
+                let (tokens, _) = self.gen_nullable_field_type(field_type, true, pos);
+                FieldReturnType::same(tokens, type_kind)
+            }
+            Ownership::Str => {
+                let is_string = |ty: &NullableType| match ty {
+                    NullableType::NamedType(name) => *name == "String",
+                    _ => false,
+                };
+                match &field_type {
+                    ty if is_string(ty) => FieldReturnType::same(quote! { &str }, type_kind),
+                    NullableType::NullableType(inner) if is_string(inner) => {
+                        FieldReturnType::same(quote! { Option<&str> }, type_kind)
+                    }
+                    _ => unsupported("`String` fields"),
+                }
+            }
+            Ownership::Slice => match list_item {
+                Some((nullable, item)) => {
+                    let (item, _) = self.gen_nullable_field_type(item.clone(), false, pos);
+                    let tokens = if nullable {
+                        quote! { Option<&[#item]> }
+                    } else {
+                        quote! { &[#item] }
+                    };
+                    FieldReturnType::same(tokens, type_kind)
+                }
+                None => unsupported("list fields"),
+            },
+            Ownership::Arc => FieldReturnType::same(quote! { std::sync::Arc<#owned> }, type_kind),
+            Ownership::Cow => FieldReturnType {
+                trait_type: quote! { std::borrow::Cow<'_, #owned> },
+                resolved_type: quote! { juniper_from_schema::CowField<'_, #owned> },
+                conversion: Some(quote! { juniper_from_schema::CowField(value) }),
+                type_kind,
+            },
+            Ownership::Iter => match list_item {
+                Some((nullable, item)) => {
+                    let (item, _) = self.gen_nullable_field_type(item.clone(), false, pos);
+                    let iter = quote! { impl std::iter::Iterator<Item = #item> };
+                    let (trait_type, conversion) = if nullable {
+                        (
+                            quote! { Option<#iter> },
+                            quote! { value.map(|value| value.collect::<Vec<_>>()) },
+                        )
+                    } else {
+                        (iter, quote! { value.collect::<Vec<_>>() })
+                    };
+                    FieldReturnType {
+                        trait_type,
+                        resolved_type: owned,
+                        conversion: Some(conversion),
+                        type_kind,
+                    }
+                }
+                None => unsupported("list fields"),
+            },
         }
     }
 
+    /// `as_ref` borrows the innermost type, such as `Option<Vec<Option<&T>>>`.
     fn gen_nullable_field_type(
         &mut self,
         field_type: NullableType,
@@ -762,53 +995,46 @@ impl<'doc> CodeGenPass<'doc> {
 
         match field_type {
             NamedType(name) => {
+                let (tokens, ty) = self.graphql_scalar_type_to_rust_type(&name, pos);
                 if as_ref {
-                    self.emit_non_fatal_error(pos, ErrorKind::AsRefOwnershipForNamedType);
+                    (quote! { &#tokens }, ty)
+                } else {
+                    (tokens, ty)
                 }
-
-                self.graphql_scalar_type_to_rust_type(&name, pos)
             }
             ListType(item_type) => {
-                let (item_type, ty) = self.gen_nullable_field_type(*item_type, false, pos);
-                let tokens = if as_ref {
-                    quote! { Vec<&#item_type> }
-                } else {
-                    quote! { Vec<#item_type> }
-                };
-                (tokens, ty)
+                let (item_type, ty) = self.gen_nullable_field_type(*item_type, as_ref, pos);
+                (quote! { Vec<#item_type> }, ty)
             }
             NullableType(item_type) => {
-                let (item_type, ty) = self.gen_nullable_field_type(*item_type, false, pos);
-                let tokens = if as_ref {
-                    quote! { Option<&#item_type> }
-                } else {
-                    quote! { Option<#item_type> }
-                };
-                (tokens, ty)
+                let (item_type, ty) = self.gen_nullable_field_type(*item_type, as_ref, pos);
+                (quote! { Option<#item_type> }, ty)
             }
         }
     }
 
-    fn collect_data_for_field_gen(&mut self, field: &'doc Field) -> FieldTokens<'doc> {
+    fn collect_data_for_field_gen(
+        &mut self,
+        parent_type: &str,
+        field: &'doc Field,
+        defaults: FieldDefaults,
+    ) -> FieldTokens<'doc> {
         let name = ident(&field.name);
 
         let inner_type = type_name(&field.field_type).to_camel_case();
 
-        let attributes = self.parse_directives(field);
+        let attributes = self.parse_directives(FieldWithDefaults(field, defaults));
         let deprecation = attributes
             .deprecated
             .as_ref()
             .map(quote_deprecation)
             .unwrap_or_else(empty_token_stream);
 
-        let (field_type, type_kind) = self.gen_field_type(
-            &field.field_type,
-            &FieldTypeDestination::Return(&attributes),
-            false,
-            field.position,
-        );
+        let return_type =
+            self.gen_return_type(&field.field_type, attributes.ownership, field.position);
+        let borrows_from_context = matches!(attributes.ownership, Ownership::BorrowedFromContext);
 
-        let field_method = ident(format!("field_{}", name.to_string().to_snake_case()));
+        let field_method = self.naming.field_method(&name.to_string());
 
         let args_data = field
             .arguments
@@ -829,25 +1055,54 @@ impl<'doc> CodeGenPass<'doc> {
             })
             .collect::<Vec<_>>();
 
-        let trait_args = args_data
+        let arguments_struct = attributes.arguments_struct.value && !args_data.is_empty();
+
+        if arguments_struct {
+            for arg in args_data.iter().filter(|arg| arg.borrow.is_some()) {
+                self.emit_non_fatal_error(
+                    arg.position,
+                    ErrorKind::OwnershipNotSupportedForType {
+                        ownership: Ownership::Borrowed.name(),
+                        supported: "arguments of fields without `arguments_struct: true`",
+                    },
+                );
+            }
+        }
+
+        let trait_args = if arguments_struct {
+            let arguments_type = self.naming.arguments_type(parent_type, &field.name);
+            vec![quote! { args: #arguments_type }]
+        } else {
+            args_data
+                .iter()
+                .map(|arg| {
+                    let name = ident(&arg.name);
+                    let arg_type = &arg.trait_type;
+                    quote! { #name: #arg_type }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let param_names = args_data
             .iter()
-            .map(|arg| {
-                let name = ident(&arg.name);
-                let arg_type = &arg.trait_type;
-                quote! { #name: #arg_type }
-            })
+            .map(|arg| ident(&arg.name))
             .collect::<Vec<_>>();
 
         let params = args_data
             .iter()
             .map(|arg| {
                 let name = ident(&arg.name);
-                if let Some(default_value) = &arg.default_value {
+                let value = if let Some(default_value) = &arg.default_value {
                     quote! {
                         #name.unwrap_or_else(|| #default_value)
                     }
                 } else {
                     quote! { #name }
+                };
+                // The owned value is a temporary that lives until the field method returns.
+                match arg.borrow {
+                    Some(borrow) => borrow.apply(value),
+                    None => value,
                 }
             })
             .collect::<Vec<_>>();
@@ -856,17 +1111,79 @@ impl<'doc> CodeGenPass<'doc> {
             name,
             macro_args,
             trait_args,
-            field_type,
+            field_type: return_type.trait_type,
+            resolved_type: return_type.resolved_type,
+            conversion: return_type.conversion,
             field_method,
             params,
+            param_names,
+            arguments_struct,
+            graphql_name: &field.name,
             description: &field.description,
-            type_kind,
+            type_kind: return_type.type_kind,
             inner_type,
             deprecation,
             infallible: attributes.infallible.value,
+            trail: attributes.trail.value,
+            borrows_from_context,
+            executor: attributes.executor.value,
         }
     }
 
+    /// Generate the struct passed to the field method of a field using
+    /// `@juniper(arguments_struct: true)`, along with a conversion from the arguments in a query
+    /// trail.
+    fn gen_arguments_struct(&mut self, type_name: &str, field: &'doc Field) {
+        let arguments_type = self.naming.arguments_type(type_name, &field.name);
+        let args_type = self.naming.args_type(type_name, &field.name);
+        let doc = format!(
+            "The arguments of `{}.{}`, with default values applied.",
+            type_name, field.name
+        );
+
+        let args_data = field
+            .arguments
+            .iter()
+            .map(|input_value| self.argument_to_name_and_rust_type(&input_value))
+            .collect::<Vec<_>>();
+
+        let fields = args_data.iter().map(|arg| {
+            let name = ident(&arg.name);
+            let arg_type = &arg.trait_type;
+            let description = doc_tokens(&arg.description);
+            quote! {
+                #description
+                pub #name: #arg_type
+            }
+        });
+
+        let names = args_data
+            .iter()
+            .map(|arg| ident(&arg.name))
+            .collect::<Vec<_>>();
+
+        self.extend(quote! {
+            #[doc = #doc]
+            ///
+            /// Generated by `juniper-from-schema`.
+            #[derive(Debug, Clone)]
+            pub struct #arguments_type {
+                #(#fields),*
+            }
+
+            impl<'a> #args_type<'a> {
+                /// Convert all the arguments at once.
+                pub fn to_arguments(
+                    &self,
+                ) -> Result<#arguments_type, juniper_from_schema::ConversionError> {
+                    Ok(#arguments_type {
+                        #(#names: self.#names()?),*
+                    })
+                }
+            }
+        });
+    }
+
     fn gen_enum_value(&mut self, enum_value: &'doc EnumValue) -> TokenStream {
         let graphql_name = &enum_value.name;
         let name = to_enum_name(&graphql_name);
@@ -1056,9 +1373,16 @@ impl<'doc> CodeGenPass<'doc> {
     }
 
     fn field_return_type_tokens(&self, field: &FieldTokens) -> TokenStream {
-        let field_type = &field.field_type;
+        self.result_type_tokens(&field.field_type, field.infallible)
+    }
 
-        if field.infallible {
+    /// The return type of the field in `graphql_object!` and `graphql_interface!`.
+    fn resolved_return_type_tokens(&self, field: &FieldTokens) -> TokenStream {
+        self.result_type_tokens(&field.resolved_type, field.infallible)
+    }
+
+    fn result_type_tokens(&self, field_type: &TokenStream, infallible: bool) -> TokenStream {
+        if infallible {
             quote! { #field_type }
         } else {
             let error_type = &self.error_type;
@@ -1075,7 +1399,13 @@ impl<'doc> CodeGenPass<'doc> {
         let field_name = &field.name;
         let args = &field.macro_args;
 
-        let body = gen_field_body(&field, &quote! { &self }, struct_name, trait_name);
+        let body = gen_field_body(
+            &field,
+            &quote! { &self },
+            struct_name,
+            trait_name,
+            &self.naming,
+        );
 
         let description = field
             .description
@@ -1085,7 +1415,7 @@ impl<'doc> CodeGenPass<'doc> {
 
         let all_args = to_field_args_list(args);
         let deprecation = &field.deprecation;
-        let return_type = self.field_return_type_tokens(&field);
+        let return_type = self.resolved_return_type_tokens(&field);
 
         quote! {
             #[doc = #description]
@@ -1104,7 +1434,15 @@ impl<'doc> CodeGenPass<'doc> {
 
         for location in directive.locations.iter() {
             match location {
-                DirectiveLocation::FieldDefinition => {
+                DirectiveLocation::FieldDefinition
+                | DirectiveLocation::ArgumentDefinition
+                | DirectiveLocation::Schema
+                | DirectiveLocation::Object
+                | DirectiveLocation::Scalar
+                | DirectiveLocation::Interface
+                | DirectiveLocation::Union
+                | DirectiveLocation::Enum
+                | DirectiveLocation::InputObject => {
                     // valid
                 }
                 other => self.emit_non_fatal_error(
@@ -1114,7 +1452,7 @@ impl<'doc> CodeGenPass<'doc> {
                             "Invalid location for @juniper directive: `{}`",
                             other.as_str()
                         ),
-                        Some("Location must be `FIELD_DEFINITION`".to_string()),
+                        Some("Location must be `FIELD_DEFINITION`, `ARGUMENT_DEFINITION`, `SCHEMA`, `OBJECT`, `SCALAR`, `INTERFACE`, `UNION`, `ENUM`, or `INPUT_OBJECT`".to_string()),
                     ),
                 ),
             }
@@ -1177,6 +1515,9 @@ impl<'doc> CodeGenPass<'doc> {
         let mut ownership_present = false;
         let mut infallible_present = false;
         let mut with_time_zone_present = false;
+        let mut trail_present = false;
+        let mut executor_present = false;
+        let mut arguments_struct_present = false;
 
         for arg in directive.arguments.iter() {
             match arg.name.as_str() {
@@ -1198,12 +1539,38 @@ impl<'doc> CodeGenPass<'doc> {
                     no_directives(self, arg, name);
                     default_value(self, arg, Value::Boolean(true), name);
                 }
+                name @ "trail" => {
+                    trail_present = true;
+                    of_type(self, arg, Type::NamedType("Boolean".to_string()), name);
+                    no_directives(self, arg, name);
+                    default_value(self, arg, Value::Boolean(true), name);
+                }
+                name @ "executor" => {
+                    executor_present = true;
+                    of_type(self, arg, Type::NamedType("Boolean".to_string()), name);
+                    no_directives(self, arg, name);
+                    default_value(self, arg, Value::Boolean(true), name);
+                }
+                name @ "arguments_struct" => {
+                    arguments_struct_present = true;
+                    of_type(self, arg, Type::NamedType("Boolean".to_string()), name);
+                    no_directives(self, arg, name);
+                    default_value(self, arg, Value::Boolean(false), name);
+                }
+                // `derive` and `attributes` are optional since they're only used on types.
+                name @ "derive" | name @ "attributes" => {
+                    let string_list = Type::ListType(Box::new(Type::NonNullType(Box::new(
+                        Type::NamedType("String".to_string()),
+                    ))));
+                    of_type(self, arg, string_list, name);
+                    no_directives(self, arg, name);
+                }
                 name => {
                     self.emit_non_fatal_error(
                         arg.position,
                         ErrorKind::InvalidJuniperDirective(
                             format!("Invalid argument for @juniper directive: `{}`", name),
-                            Some("Supported arguments are `ownership`, `infallible`, and `with_time_zone`".to_string()),
+                            Some("Supported arguments are `ownership`, `infallible`, `with_time_zone`, `trail`, `executor`, `arguments_struct`, `derive`, and `attributes`".to_string()),
                         ),
                     )
                 }
@@ -1233,6 +1600,30 @@ impl<'doc> CodeGenPass<'doc> {
                 ),
             )
         }
+
+        if !trail_present {
+            self.emit_non_fatal_error(
+                directive.position,
+                ErrorKind::InvalidJuniperDirective(format!("Missing argument `trail`"), None),
+            )
+        }
+
+        if !executor_present {
+            self.emit_non_fatal_error(
+                directive.position,
+                ErrorKind::InvalidJuniperDirective(format!("Missing argument `executor`"), None),
+            )
+        }
+
+        if !arguments_struct_present {
+            self.emit_non_fatal_error(
+                directive.position,
+                ErrorKind::InvalidJuniperDirective(
+                    format!("Missing argument `arguments_struct`"),
+                    None,
+                ),
+            )
+        }
     }
 }
 
@@ -1264,36 +1655,66 @@ fn to_enum_name(name: &str) -> Ident {
     ident(name.to_camel_case())
 }
 
-fn trait_map_for_struct_name(struct_name: &Ident) -> Ident {
-    ident(format!("{}Fields", struct_name))
-}
-
 fn gen_field_body(
     field: &FieldTokens,
     self_tokens: &TokenStream,
     struct_name: &Ident,
     trait_name: &Ident,
+    naming: &Naming,
 ) -> TokenStream {
     let field_method = &field.field_method;
-    let params = &field.params;
+    let params = if field.arguments_struct {
+        let arguments_type = naming.arguments_type(&struct_name.to_string(), field.graphql_name);
+        let names = &field.param_names;
+        let values = &field.params;
+        vec![quote! { #arguments_type { #(#names: #values),* } }]
+    } else {
+        field.params.clone()
+    };
+    let executor = if field.executor {
+        quote! { &executor }
+    } else {
+        quote! { executor.context() }
+    };
 
-    match field.type_kind {
-        TypeKind::Scalar => {
+    let (setup, call) = match (field.type_kind, field.trail) {
+        (TypeKind::Scalar, _) | (TypeKind::Type, false) => (
+            quote! {},
             quote! {
-                <#struct_name as self::#trait_name>::#field_method(#self_tokens, &executor, #(#params),*)
-            }
-        }
-        TypeKind::Type => {
+                <#struct_name as self::#trait_name>::#field_method(#self_tokens, #executor, #(#params),*)
+            },
+        ),
+        (TypeKind::Type, true) => {
             let query_trail_type = ident(&field.inner_type);
-            quote! {
-                let look_ahead = executor.look_ahead();
-                let trail = juniper_from_schema::QueryTrail::<
-                    #query_trail_type,
-                    juniper_from_schema::Walked,
-                >::new(&look_ahead);
-                <#struct_name as self::#trait_name>::#field_method(#self_tokens, &executor, &trail, #(#params),*)
-            }
+            (
+                quote! {
+                    let look_ahead = executor.look_ahead();
+                    let trail = juniper_from_schema::QueryTrail::<
+                        #query_trail_type,
+                        juniper_from_schema::Walked,
+                    >::new(&look_ahead);
+                },
+                quote! {
+                    <#struct_name as self::#trait_name>::#field_method(#self_tokens, #executor, &trail, #(#params),*)
+                },
+            )
         }
+    };
+
+    let call = match &field.conversion {
+        None => call,
+        Some(conversion) if field.infallible => quote! {
+            {
+                let value = #call;
+                #conversion
+            }
+        },
+        Some(conversion) => quote! { #call.map(|value| #conversion) },
+    };
+
+    quote! {
+        #setup
+        #call
     }
 }
 
@@ -1315,13 +1736,21 @@ struct FieldTokens<'a> {
     macro_args: Vec<TokenStream>,
     trait_args: Vec<TokenStream>,
     field_type: TokenStream,
+    resolved_type: TokenStream,
+    conversion: Option<TokenStream>,
     field_method: Ident,
     params: Vec<TokenStream>,
+    param_names: Vec<Ident>,
+    arguments_struct: bool,
+    graphql_name: &'a str,
     description: &'a Option<String>,
     type_kind: TypeKind,
     inner_type: Name,
     deprecation: TokenStream,
     infallible: bool,
+    trail: bool,
+    borrows_from_context: bool,
+    executor: bool,
 }
 
 struct FieldArgument<'a> {
@@ -1329,7 +1758,30 @@ struct FieldArgument<'a> {
     macro_type: TokenStream,
     trait_type: TokenStream,
     default_value: Option<TokenStream>,
+    borrow: Option<ArgumentBorrow>,
     description: &'a Option<String>,
+    position: Pos,
+}
+
+/// How the owned value of an argument with `@juniper(ownership: "borrowed")` is borrowed.
+#[derive(Debug, Clone, Copy)]
+enum ArgumentBorrow {
+    /// `&value`, relying on deref coercion for `&str` and `&[T]`.
+    Ref,
+    /// `value.as_ref()`, for nullable arguments.
+    AsRef,
+    /// `value.as_deref()`, for nullable strings and lists.
+    AsDeref,
+}
+
+impl ArgumentBorrow {
+    fn apply(self, value: TokenStream) -> TokenStream {
+        match self {
+            ArgumentBorrow::Ref => quote! { &#value },
+            ArgumentBorrow::AsRef => quote! { #value.as_ref() },
+            ArgumentBorrow::AsDeref => quote! { #value.as_deref() },
+        }
+    }
 }
 
 // This can also be with TryInto, but that requires 1.34
@@ -1342,9 +1794,25 @@ fn i32_from_i64(i: i64) -> Option<i32> {
     }
 }
 
-enum FieldTypeDestination<'a> {
-    Argument,
-    Return(&'a FieldArguments),
+/// The type returned by a field method, and how juniper resolves it.
+struct FieldReturnType {
+    trait_type: TokenStream,
+    /// The type returned to juniper, for types juniper can't resolve directly.
+    resolved_type: TokenStream,
+    /// An expression that converts `value` from `trait_type` to `resolved_type`.
+    conversion: Option<TokenStream>,
+    type_kind: TypeKind,
+}
+
+impl FieldReturnType {
+    fn same(tokens: TokenStream, type_kind: TypeKind) -> Self {
+        FieldReturnType {
+            trait_type: tokens.clone(),
+            resolved_type: tokens,
+            conversion: None,
+            type_kind,
+        }
+    }
 }
 
 fn doc_tokens(doc: &Option<String>) -> TokenStream {
@@ -1446,4 +1914,30 @@ mod test {
         assert!(!is_snake_case("fooBar"));
         assert!(!is_snake_case("FooBar"));
     }
+
+    #[test]
+    fn test_arguments_structs_are_only_generated_for_objects() {
+        let schema = r#"
+            type Query {
+              node: Node! @juniper(ownership: "owned")
+            }
+
+            interface Node {
+              name(uppercase: Boolean = false): String! @juniper(ownership: "owned")
+            }
+
+            type User implements Node {
+              name(uppercase: Boolean = false): String! @juniper(ownership: "owned")
+            }
+
+            schema @juniper(arguments_struct: true) {
+              query: Query
+            }
+        "#;
+
+        let tokens = crate::graphql_schema(schema.parse().unwrap()).to_string();
+
+        assert!(tokens.contains("pub struct UserNameArguments"));
+        assert!(!tokens.contains("NodeNameArguments"));
+    }
 }
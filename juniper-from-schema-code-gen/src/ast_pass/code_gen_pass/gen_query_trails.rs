@@ -1,11 +1,19 @@
-use super::{ident, type_name, CodeGenPass, EmitError, FieldTypeDestination, TypeKind};
-use crate::ast_pass::{error::ErrorKind, schema_visitor::SchemaVisitor};
-use graphql_parser::schema::*;
+use super::{ident, type_name, CodeGenPass, EmitError, TypeKind};
+use crate::{
+    ast_pass::{
+        directive_parsing::{Cost, SqlField, SqlTable},
+        error::{self, ErrorKind, UnsupportedDirectiveKind},
+        schema_visitor::SchemaVisitor,
+    },
+    introspection::type_definition_name,
+    naming::Naming,
+};
+use graphql_parser::{schema::*, Pos};
 use heck::{CamelCase, MixedCase, SnakeCase};
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     hash::{Hash, Hasher},
 };
 use syn::Ident;
@@ -13,6 +21,9 @@ use syn::Ident;
 struct QueryTrailCodeGenPass<'pass, 'doc> {
     pass: &'pass mut CodeGenPass<'doc>,
     fields_map: HashMap<&'doc String, Vec<&'doc Field>>,
+    query_trail_types: HashSet<&'doc str>,
+    type_names: HashSet<&'doc str>,
+    sql_tables: HashMap<&'doc str, String>,
 }
 
 impl<'doc> CodeGenPass<'doc> {
@@ -20,28 +31,32 @@ impl<'doc> CodeGenPass<'doc> {
         let original_tokens = std::mem::replace(&mut self.tokens, quote! {});
 
         let fields_map = build_fields_map(doc);
+        let query_trail_types = build_query_trail_types(doc);
+        let type_names = build_type_names(doc);
+        let sql_tables = build_sql_tables(doc);
 
         let mut query_trail_pass = QueryTrailCodeGenPass {
             pass: self,
             fields_map,
+            query_trail_types,
+            type_names,
+            sql_tables,
         };
         query_trail_pass.gen_query_trail();
-        query_trail_pass.gen_from_default_scalar_value();
-        query_trail_pass.gen_from_look_ahead_value();
         query_trail_pass.visit_document(doc);
 
         let query_trail_tokens = &self.tokens;
+        let module_name = self.naming.query_trails_module();
 
         self.tokens = quote! {
-            pub use juniper_from_schema::{Walked, NotWalked, QueryTrail};
-            pub use self::query_trails::*;
+            pub use self::#module_name::*;
 
             #original_tokens
 
             /// `QueryTrail` extension traits specific to the GraphQL schema
             ///
             /// Generated by `juniper-from-schema`.
-            pub mod query_trails {
+            pub mod #module_name {
                 #![allow(unused_imports, dead_code, missing_docs)]
 
                 use super::*;
@@ -55,333 +70,504 @@ impl<'doc> CodeGenPass<'doc> {
 impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
     fn gen_query_trail(&mut self) {
         self.pass.extend(quote! {
-            use juniper_from_schema::{Walked, NotWalked, QueryTrail};
-
-            /// Convert from one type of `QueryTrail` to another. Used for converting interface and
-            /// union trails into concrete subtypes.
+            // Re-exported through the glob import in the parent module. Unlike an explicit
+            // import there, this still works if several schemas are generated in one module.
+            pub use juniper_from_schema::{
+                Walked, NotWalked, QueryTrail, OwnedQueryTrail, DowncastQueryTrail,
+                DowncastOwnedQueryTrail,
+            };
+            use juniper_from_schema::FromLookAheadValue;
+
+            /// The complexity of the fields selected by a `QueryTrail`, as declared with `@cost`
+            /// directives in the schema.
             ///
-            /// This trait cannot live in juniper-from-schema itself because then we wouldn't be
-            /// able to implement it for `QueryTrail` in the user's code. That would result in
-            /// orphan instances.
+            /// Generated by `juniper-from-schema`.
+            pub trait QueryTrailComplexity {
+                /// Compute the complexity of the selection.
+                ///
+                /// Generated by juniper-from-schema.
+                fn complexity(&self) -> u64;
+            }
+
+            /// The columns and joins needed to resolve the fields selected by a `QueryTrail`, as
+            /// declared with `@sql` directives in the schema.
             ///
             /// Generated by `juniper-from-schema`.
-            pub trait DowncastQueryTrail<'a, T> {
-                /// Perform the downcast.
+            pub trait QueryTrailSql {
+                /// Compute the columns to select and the tables to join.
                 ///
                 /// Generated by juniper-from-schema.
-                fn downcast(self) -> QueryTrail<'a, T, Walked>;
+                fn sql_projection(&self) -> juniper_from_schema::SqlProjection;
+
+                #[doc(hidden)]
+                fn add_to_sql_projection(
+                    &self,
+                    projection: &mut juniper_from_schema::SqlProjection,
+                    alias: &str,
+                );
             }
         })
     }
 
-    fn gen_from_default_scalar_value(&mut self) {
-        self.pass.extend(quote! {
-            /// Convert a `juniper::DefaultScalarValue` into a concrete value.
-            ///
-            /// This is used for `QueryTrail`.
-            ///
-            /// Generated by `juniper-from-schema`.
-            pub(super) trait FromDefaultScalarValue<T> {
-                /// Perform the conversion.
-                fn from(self) -> T;
+    fn gen_field_walk_methods(&mut self, obj: InternalQueryTrailNode<'doc>) {
+        let name = ident(&obj.name());
+        let naming = &self.pass.naming;
+        let trait_name = naming.query_trail_extensions(obj.name());
+        let args_trait_name = naming.query_trail_extensions(&format!("{}Arguments", obj.name()));
+        let field_enum_name = naming.field_enum(obj.name());
+        let fields = obj.fields();
+
+        // The enum is generated in the query trails module, where it would shadow a schema type
+        // with the same name that's glob imported from the parent module.
+        let field_enum_string = field_enum_name.to_string();
+        if self.type_names.contains(field_enum_string.as_str()) {
+            self.pass.emit_non_fatal_error(
+                obj.position(),
+                ErrorKind::FieldEnumNameClash(field_enum_string),
+            );
+        }
+
+        let mut method_signatures = vec![];
+        let mut method_implementations = vec![];
+
+        let mut owned_method_signatures = vec![];
+        let mut owned_method_implementations = vec![];
+
+        let mut argument_signatures = vec![];
+        let mut argument_implementations = vec![];
+        let mut argument_types = vec![];
+
+        let mut owned_argument_signatures = vec![];
+        let mut owned_argument_implementations = vec![];
+
+        let mut field_variants = vec![];
+        let mut field_names = vec![];
+        let mut field_descriptors = vec![];
+        let mut descriptor_names = HashSet::new();
+
+        for field in fields {
+            let FieldWalkMethod {
+                method_signature,
+                method_implementation,
+                owned_method_signature,
+                owned_method_implementation,
+                argument_signature,
+                argument_implementation,
+                owned_argument_signature,
+                owned_argument_implementation,
+                argument_type,
+                field_descriptor,
+            } = self.gen_field_walk_method(field, &obj);
+
+            field_variants.push(ident(field.name.to_camel_case()));
+            field_names.push(field.name.to_mixed_case());
+            field_descriptors.push(field_descriptor);
+            descriptor_names.insert(field.name.as_str());
+
+            method_signatures.push(method_signature);
+            method_implementations.push(method_implementation);
+
+            owned_method_signatures.push(owned_method_signature);
+            owned_method_implementations.push(owned_method_implementation);
+
+            argument_signatures.push(argument_signature);
+            argument_implementations.push(argument_implementation);
+            argument_types.push(argument_type);
+
+            owned_argument_signatures.push(owned_argument_signature);
+            owned_argument_implementations.push(owned_argument_implementation);
+        }
+
+        // Fields only found on implementors can be selected through fragments. They have to be
+        // described as well so they can be copied by `QueryTrail::to_owned`.
+        if let InternalQueryTrailNode::Interface(interface) = &obj {
+            let implementors = self
+                .pass
+                .ast_data
+                .get_implementors_of_interface(&interface.name)
+                .cloned()
+                .unwrap_or_default();
+
+            let implementor_fields = fields_by_name(
+                implementors
+                    .iter()
+                    .map(|implementor| implementor.to_string()),
+                &self.fields_map,
+            );
+            for (name, fields) in implementor_fields {
+                if !descriptor_names.insert(name) {
+                    continue;
+                }
+
+                // Implementors can give a field of the same name different types, in which case
+                // the descriptor has to cover the children of all of them.
+                if field_types_agree(&fields) {
+                    field_descriptors.push(self.gen_field_descriptor(fields[0]));
+                } else {
+                    field_descriptors.push(self.gen_merged_field_descriptor(&fields));
+                }
             }
-        });
+        }
 
-        let gen_impl = |to: &str, variant: &str| {
-            let to = ident(to);
-            let variant = ident(variant);
-            quote! {
-                impl<'a, 'b> FromDefaultScalarValue<#to> for &'a &'b juniper::DefaultScalarValue {
-                    fn from(self) -> #to {
-                        match self {
-                            juniper::DefaultScalarValue::#variant(x) => x.to_owned(),
-                            other => {
-                                match other {
-                                    juniper::DefaultScalarValue::Int(_) => panic!(
-                                        "Failed converting scalar value. Expected `{}` got `Int`",
-                                        stringify!(#to),
-                                    ),
-                                    juniper::DefaultScalarValue::String(_) => panic!(
-                                        "Failed converting scalar value. Expected `{}` got `String`",
-                                        stringify!(#to),
-                                    ),
-                                    juniper::DefaultScalarValue::Float(_) => panic!(
-                                        "Failed converting scalar value. Expected `{}` got `Float`",
-                                        stringify!(#to),
-                                    ),
-                                    juniper::DefaultScalarValue::Boolean(_) => panic!(
-                                        "Failed converting scalar value. Expected `{}` got `Boolean`",
-                                        stringify!(#to),
-                                    ),
-                                }
-                            }
-                        }
-                    }
+        // Fields whose type differs between the members of a union don't get methods, but they can
+        // still be selected through fragments so they have to be described as well.
+        if let InternalQueryTrailNode::Union(union, _) = &obj {
+            for fields in union_fields_by_name(union, &self.fields_map).values() {
+                if !field_types_agree(fields) {
+                    field_descriptors.push(self.gen_merged_field_descriptor(fields));
                 }
             }
-        };
+        }
 
-        self.pass.extend(gen_impl("i32", "Int"));
-        self.pass.extend(gen_impl("String", "String"));
-        self.pass.extend(gen_impl("f64", "Float"));
-        self.pass.extend(gen_impl("bool", "Boolean"));
+        let naming = &self.pass.naming;
+        let owned_trait_name = naming.owned_query_trail_extensions(obj.name());
+        let owned_args_trait_name =
+            naming.owned_query_trail_extensions(&format!("{}Arguments", obj.name()));
 
-        self.pass.extend(quote! {
-            impl<'a, 'b, T> FromDefaultScalarValue<Option<T>> for &'a &'b juniper::DefaultScalarValue
-            where
-                &'a &'b juniper::DefaultScalarValue: FromDefaultScalarValue<T>,
-            {
-                fn from(self) -> Option<T> {
-                    Some(self.from())
+        let enum_doc = format!("The fields of the GraphQL type `{}`.", obj.name());
+
+        let possible_types = self.possible_types(&obj);
+        let possible_types_method = if possible_types.is_empty() {
+            quote! {}
+        } else {
+            quote! {
+                fn query_trail_possible_types() -> &'static [&'static str] {
+                    &[#(#possible_types),*]
                 }
             }
-        });
-    }
+        };
 
-    fn gen_from_look_ahead_value(&mut self) {
         self.pass.extend(quote! {
-            /// Convert a `juniper::LookAheadValue` into a concrete value.
+            #[doc = #enum_doc]
             ///
-            /// This is used for `QueryTrail`.
+            /// Returned by `QueryTrail::selected_fields`.
             ///
             /// Generated by `juniper-from-schema`.
-            pub(super) trait FromLookAheadValue<T> {
-                /// Perform the conversion.
-                fn from(self) -> T;
+            #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+            pub enum #field_enum_name {
+                #(
+                    #[allow(missing_docs)]
+                    #field_variants,
+                )*
             }
-        });
 
-        let gen_scalar_impl = |to: &str| {
-            let to = ident(to);
-            quote! {
-                impl<'a, 'b> FromLookAheadValue<#to>
-                    for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
-                {
-                    fn from(self) -> #to {
-                        match self {
-                            juniper::LookAheadValue::Scalar(scalar) => {
-                                FromDefaultScalarValue::from(scalar)
-                            },
-                            juniper::LookAheadValue::Null => panic!(
-                                "Failed converting look ahead value. Expected scalar type got `null`",
-                            ),
-                            juniper::LookAheadValue::Enum(_) => panic!(
-                                "Failed converting look ahead value. Expected scalar type got `enum`",
-                            ),
-                            juniper::LookAheadValue::List(_) => panic!(
-                                "Failed converting look ahead value. Expected scalar type got `list`",
-                            ),
-                            juniper::LookAheadValue::Object(_) => panic!(
-                                "Failed converting look ahead value. Expected scalar type got `object`",
-                            ),
-                        }
+            impl #field_enum_name {
+                /// The name of the field in the schema.
+                pub fn graphql_name(&self) -> &'static str {
+                    match *self {
+                        #( #field_enum_name::#field_variants => #field_names, )*
                     }
                 }
             }
-        };
-
-        self.pass.extend(gen_scalar_impl("i32"));
-        self.pass.extend(gen_scalar_impl("String"));
-        self.pass.extend(gen_scalar_impl("f64"));
-        self.pass.extend(gen_scalar_impl("bool"));
 
-        self.pass.extend(quote! {
-            impl<'a, 'b, T> FromLookAheadValue<Option<T>>
-                for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
-            where
-                &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>: FromLookAheadValue<T>,
-            {
-                fn from(self) -> Option<T> {
-                    match self {
-                        juniper::LookAheadValue::Null => None,
-                        other => Some(other.from()),
-                    }
+            impl juniper_from_schema::QueryTrailFields for #name {
+                fn query_trail_fields() -> &'static [juniper_from_schema::QueryTrailField] {
+                    const FIELDS: &[juniper_from_schema::QueryTrailField] = &[
+                        #(#field_descriptors,)*
+                    ];
+                    FIELDS
                 }
+
+                #possible_types_method
             }
 
-            impl<'a, 'b, T> FromLookAheadValue<Vec<T>>
-                for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
-            where
-                &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>: FromLookAheadValue<T>,
-            {
-                fn from(self) -> Vec<T> {
-                    match self {
-                        juniper::LookAheadValue::List(values) => {
-                            values.iter().map(|value| value.from()).collect::<Vec<_>>()
-                        },
-                        juniper::LookAheadValue::Scalar(_) => panic!(
-                            "Failed converting look ahead value. Expected list type got `scalar`",
-                        ),
-                        juniper::LookAheadValue::Null => panic!(
-                            "Failed converting look ahead value. Expected list type got `null`",
-                        ),
-                        juniper::LookAheadValue::Enum(_) => panic!(
-                            "Failed converting look ahead value. Expected list type got `enum`",
-                        ),
-                        juniper::LookAheadValue::Object(_) => panic!(
-                            "Failed converting look ahead value. Expected list type got `object`",
-                        ),
-                    }
-                }
+            /// Extension trait for `QueryTrail` to inspect incoming queries.
+            pub trait #trait_name<'a, K> {
+                /// The fields selected in the query, in the order they're defined in the schema.
+                ///
+                /// Generated by `juniper-from-schema`.
+                fn selected_fields(&self) -> std::vec::IntoIter<#field_enum_name>;
+
+                #(#method_signatures)*
             }
 
-            impl<'a, 'b> FromLookAheadValue<juniper::ID>
-                for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
-            {
-                fn from(self) -> juniper::ID {
-                    let s = FromLookAheadValue::<String>::from(self);
-                    juniper::ID::new(s)
+            impl<'a, K> #trait_name<'a, K> for QueryTrail<'a, #name, K> {
+                fn selected_fields(&self) -> std::vec::IntoIter<#field_enum_name> {
+                    let mut fields = Vec::new();
+                    #(
+                        if self.child_selected(#field_names) {
+                            fields.push(#field_enum_name::#field_variants);
+                        }
+                    )*
+                    fields.into_iter()
                 }
+
+                #(#method_implementations)*
             }
-        });
 
-        if self.pass.ast_data.url_scalar_defined() {
-            self.pass.extend(quote! {
-                impl<'a, 'b> FromLookAheadValue<url::Url>
-                    for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
-                {
-                    fn from(self) -> url::Url {
-                        let s = FromLookAheadValue::<String>::from(self);
-                        match url::Url::parse(&s) {
-                            Ok(url) => url,
-                            Err(e) => panic!("Error parsing URL: {}", e),
+            /// Extension trait for `QueryTrail` to inspect incoming query arguments.
+            pub trait #args_trait_name<'a> {
+                #(#argument_signatures)*
+            }
+
+            impl<'a> #args_trait_name<'a> for QueryTrail<'a, #name, juniper_from_schema::Walked> {
+                #(#argument_implementations)*
+            }
+
+            /// Extension trait for `OwnedQueryTrail` to inspect incoming queries.
+            pub trait #owned_trait_name<K> {
+                /// The fields selected in the query, in the order they're defined in the schema.
+                ///
+                /// Generated by `juniper-from-schema`.
+                fn selected_fields(&self) -> std::vec::IntoIter<#field_enum_name>;
+
+                #(#owned_method_signatures)*
+            }
+
+            impl<K> #owned_trait_name<K> for OwnedQueryTrail<#name, K> {
+                fn selected_fields(&self) -> std::vec::IntoIter<#field_enum_name> {
+                    let mut fields = Vec::new();
+                    #(
+                        if self.child_selected(#field_names) {
+                            fields.push(#field_enum_name::#field_variants);
                         }
-                    }
+                    )*
+                    fields.into_iter()
                 }
-            });
+
+                #(#owned_method_implementations)*
+            }
+
+            /// Extension trait for `OwnedQueryTrail` to inspect incoming query arguments.
+            pub trait #owned_args_trait_name {
+                #(#owned_argument_signatures)*
+            }
+
+            impl #owned_args_trait_name for OwnedQueryTrail<#name, juniper_from_schema::Walked> {
+                #(#owned_argument_implementations)*
+            }
+
+            #(#argument_types)*
+        });
+
+        self.gen_complexity(&obj);
+        if let InternalQueryTrailNode::Object(obj) = &obj {
+            self.gen_sql_projection(obj);
         }
+        self.gen_conversion_methods(name, obj);
+    }
 
-        if self.pass.ast_data.uuid_scalar_defined() {
-            self.pass.extend(quote! {
-                impl<'a, 'b> FromLookAheadValue<uuid::Uuid>
-                    for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
-                {
-                    fn from(self) -> uuid::Uuid {
-                        let s = FromLookAheadValue::<String>::from(self);
-                        match uuid::Uuid::parse_str(&s) {
-                            Ok(url) => url,
-                            Err(e) => panic!("Error parsing UUID: {}", e),
-                        }
-                    }
-                }
-            });
+    /// The types implementing an interface or the members of a union.
+    fn possible_types(&self, obj: &InternalQueryTrailNode<'_>) -> Vec<String> {
+        match obj {
+            InternalQueryTrailNode::Object(_) => vec![],
+            InternalQueryTrailNode::Interface(interface) => self
+                .pass
+                .ast_data
+                .get_implementors_of_interface(&interface.name)
+                .map(|implementors| implementors.iter().map(|name| name.to_string()).collect())
+                .unwrap_or_default(),
+            InternalQueryTrailNode::Union(union, _) => union.types.clone(),
         }
+    }
 
-        if self.pass.ast_data.date_scalar_defined() {
-            self.pass.extend(quote! {
-                impl<'a, 'b> FromLookAheadValue<chrono::NaiveDate>
-                    for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
-                {
-                    fn from(self) -> chrono::NaiveDate {
-                        let s = FromLookAheadValue::<String>::from(self);
-                        match chrono::NaiveDate::parse_from_str(&s, "%Y-%m-%d") {
-                            Ok(date) => date,
-                            Err(e) => {
-                                panic!(
-                                    "Error parsing NaiveDate. Format used is `%Y-%m-%d`\n{}",
-                                    e,
-                                )
-                            },
-                        }
-                    }
+    fn gen_complexity(&mut self, obj: &InternalQueryTrailNode<'_>) {
+        let name = ident(obj.name());
+
+        let possible_types = self
+            .possible_types(obj)
+            .iter()
+            .map(ident)
+            .collect::<Vec<_>>();
+
+        // Interfaces and unions cost as much as their most expensive possible type.
+        let body = if possible_types.is_empty() {
+            let fields = obj
+                .fields()
+                .into_iter()
+                .map(|field| self.gen_field_complexity(field));
+
+            quote! {
+                use juniper::LookAheadMethods;
+
+                let look_ahead = match self.look_ahead {
+                    Some(look_ahead) => look_ahead,
+                    None => return 0,
+                };
+
+                let mut complexity: u64 = 0;
+                #(
+                    complexity = complexity.saturating_add(#fields);
+                )*
+                complexity
+            }
+        } else {
+            quote! {
+                let look_ahead = match self.look_ahead {
+                    Some(look_ahead) => look_ahead,
+                    None => return 0,
+                };
+
+                let mut complexity: u64 = 0;
+                #(
+                    complexity = complexity.max(QueryTrailComplexity::complexity(
+                        &QueryTrail::<#possible_types, Walked>::new(look_ahead),
+                    ));
+                )*
+                complexity
+            }
+        };
+
+        self.pass.extend(quote! {
+            impl<'a, K> QueryTrailComplexity for QueryTrail<'a, #name, K> {
+                fn complexity(&self) -> u64 {
+                    #body
                 }
+            }
+        });
+    }
+
+    fn gen_sql_projection(&mut self, obj: &'doc ObjectType) {
+        let table = match self.sql_tables.get(obj.name.as_str()) {
+            Some(table) => table.clone(),
+            None => return,
+        };
+        let name = ident(&obj.name);
+
+        let mut fields = vec![];
+        for field in &obj.fields {
+            let sql = match SqlField::of_field(field) {
+                Some(sql) => sql,
+                None => continue,
+            };
+            let field_name = field.name.to_mixed_case();
+
+            let column = sql.column.map(|column| {
+                quote! { projection.add_column(alias, #column); }
             });
-        }
 
-        if self.pass.ast_data.date_time_scalar_defined() {
-            self.pass.extend(quote! {
-                impl<'a, 'b> FromLookAheadValue<chrono::DateTime<chrono::Utc>>
-                    for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
-                {
-                    fn from(self) -> chrono::DateTime<chrono::Utc> {
-                        let s = FromLookAheadValue::<String>::from(self);
-                        let parsed = chrono::DateTime::parse_from_rfc3339(&s);
-                        match parsed {
-                            Ok(date_time) => date_time.into(),
-                            Err(e) => {
-                                panic!(
-                                    "Error parsing DateTime. Format used is RFC 3339 (aka ISO 8601)\n{}",
-                                    e,
-                                )
-                            },
-                        }
-                    }
+            let join = match sql.join {
+                Some(on) if !on.contains("{alias}") || !on.contains("{parent}") => {
+                    self.pass.emit_non_fatal_error(
+                        field.position,
+                        ErrorKind::UnsupportedDirective(UnsupportedDirectiveKind::Sql(
+                            error::Sql::JoinWithoutAliases,
+                        )),
+                    );
+                    None
                 }
-
-                impl<'a, 'b> FromLookAheadValue<chrono::NaiveDateTime>
-                    for &'a juniper::LookAheadValue<'b, juniper::DefaultScalarValue>
-                {
-                    fn from(self) -> chrono::NaiveDateTime {
-                        let s = FromLookAheadValue::<String>::from(self);
-                        let parsed = chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S");
-                        match parsed {
-                            Ok(date_time) => date_time.into(),
-                            Err(e) => {
-                                panic!(
-                                    "Error parsing NaiveDateTime. Format used is `%Y-%m-%d %H:%M:%S`\n{}",
-                                    e,
-                                )
-                            },
+                Some(on) => {
+                    let field_type = type_name(&field.field_type);
+                    match self.sql_tables.get(field_type.as_str()) {
+                        Some(join_table) => {
+                            let field_type = ident(field_type);
+                            let join_field = field.name.to_snake_case();
+                            Some(quote! {
+                                let child_alias =
+                                    projection.add_join(alias, #join_field, #join_table, #on);
+                                QueryTrailSql::add_to_sql_projection(
+                                    &QueryTrail::<#field_type, Walked>::new(child),
+                                    projection,
+                                    &child_alias,
+                                );
+                            })
+                        }
+                        None => {
+                            self.pass.emit_non_fatal_error(
+                                field.position,
+                                ErrorKind::UnsupportedDirective(UnsupportedDirectiveKind::Sql(
+                                    error::Sql::JoinWithoutTable(field_type),
+                                )),
+                            );
+                            None
                         }
                     }
                 }
+                None => None,
+            };
+
+            fields.push(match join {
+                Some(join) => quote! {
+                    if let Some(child) = look_ahead.select_child(#field_name) {
+                        #column
+                        #join
+                    }
+                },
+                None => quote! {
+                    if look_ahead.select_child(#field_name).is_some() {
+                        #column
+                    }
+                },
             });
         }
-    }
 
-    fn gen_field_walk_methods(&mut self, obj: InternalQueryTrailNode) {
-        let name = ident(&obj.name());
-        let trait_name = ident(&format!("QueryTrail{}Extensions", obj.name()));
-        let args_trait_name = ident(&format!("QueryTrail{}ArgumentsExtensions", obj.name()));
-        let fields = obj.fields();
+        self.pass.extend(quote! {
+            impl<'a, K> QueryTrailSql for QueryTrail<'a, #name, K> {
+                fn sql_projection(&self) -> juniper_from_schema::SqlProjection {
+                    let mut projection = juniper_from_schema::SqlProjection::new(#table);
+                    self.add_to_sql_projection(&mut projection, #table);
+                    projection
+                }
 
-        let mut method_signatures = vec![];
-        let mut method_implementations = vec![];
+                fn add_to_sql_projection(
+                    &self,
+                    projection: &mut juniper_from_schema::SqlProjection,
+                    alias: &str,
+                ) {
+                    use juniper::LookAheadMethods;
 
-        let mut argument_signatures = vec![];
-        let mut argument_implementations = vec![];
-        let mut argument_types = vec![];
+                    let look_ahead = match self.look_ahead {
+                        Some(look_ahead) => look_ahead,
+                        None => return,
+                    };
 
-        for field in fields {
-            let FieldWalkMethod {
-                method_signature,
-                method_implementation,
-                argument_signature,
-                argument_implementation,
-                argument_type,
-            } = self.gen_field_walk_method(field, &obj);
+                    #(#fields)*
+                }
+            }
+        });
+    }
 
-            method_signatures.push(method_signature);
-            method_implementations.push(method_implementation);
+    fn gen_field_complexity(&self, field: &Field) -> TokenStream {
+        let name = field.name.to_mixed_case();
+        let selected_field_complexity = self.gen_selected_field_complexity(field);
 
-            argument_signatures.push(argument_signature);
-            argument_implementations.push(argument_implementation);
-            argument_types.push(argument_type);
+        quote! {
+            match look_ahead.select_child(#name) {
+                Some(child) => #selected_field_complexity,
+                None => 0,
+            }
         }
+    }
 
-        self.pass.extend(quote! {
-            /// Extension trait for `QueryTrail` to inspect incoming queries.
-            pub trait #trait_name<'a, K> {
-                #(#method_signatures)*
-            }
+    /// The complexity of `field`, given its selection as `child`.
+    fn gen_selected_field_complexity(&self, field: &Field) -> TokenStream {
+        let cost = Cost::of_field(field).unwrap_or_default();
+        let value = cost.value;
 
-            impl<'a, K> #trait_name<'a, K> for QueryTrail<'a, #name, K> {
-                #(#method_implementations)*
-            }
+        let multipliers = cost.multipliers.iter().map(|multiplier| {
+            let default = field
+                .arguments
+                .iter()
+                .find(|arg| &arg.name == multiplier)
+                .and_then(|arg| match &arg.default_value {
+                    Some(Value::Int(default)) => default.as_i64(),
+                    _ => None,
+                })
+                .map(|default| default.max(0) as u64)
+                .unwrap_or(1);
 
-            /// Extension trait for `QueryTrail` to inspect incoming query arguments.
-            pub trait #args_trait_name<'a> {
-                #(#argument_signatures)*
+            quote! {
+                juniper_from_schema::cost_multiplier(child, #multiplier, #default)
             }
+        });
 
-            impl<'a> #args_trait_name<'a> for QueryTrail<'a, #name, juniper_from_schema::Walked> {
-                #(#argument_implementations)*
+        let field_type = type_name(&field.field_type);
+        let children = if self.query_trail_types.contains(field_type.as_str()) {
+            let field_type = ident(field_type.to_camel_case());
+            quote! {
+                QueryTrailComplexity::complexity(
+                    &QueryTrail::<#field_type, Walked>::new(child),
+                )
             }
+        } else {
+            quote! { 0 }
+        };
 
-            #(#argument_types)*
-        });
-
-        self.gen_conversion_methods(name, obj);
+        quote! {
+            #value
+                .saturating_add(#children)
+                #( .saturating_mul(#multipliers) )*
+        }
     }
 
     fn gen_conversion_methods(
@@ -389,70 +575,110 @@ impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
         original_type_name: Ident,
         obj: InternalQueryTrailNode<'_>,
     ) {
-        let mut destination_types = vec![];
+        let mut destination_types: Vec<String> = vec![];
+        let mut own_fields = HashSet::new();
 
         match obj {
             InternalQueryTrailNode::Object(_) => {}
             InternalQueryTrailNode::Interface(i) => {
-                if let Some(i) = &self.pass.ast_data.get_implementors_of_interface(&i.name) {
-                    for interface_implementor_name in *i {
-                        let ident = ident(interface_implementor_name);
-                        destination_types.push(ident);
-                    }
+                if let Some(implementors) =
+                    self.pass.ast_data.get_implementors_of_interface(&i.name)
+                {
+                    destination_types.extend(implementors.iter().map(|name| name.to_string()));
                 }
+                own_fields.extend(i.fields.iter().map(|field| field.name.as_str()));
             }
             InternalQueryTrailNode::Union(u, _) => {
-                for type_ in &u.types {
-                    let ident = ident(type_);
-                    destination_types.push(ident);
-                }
+                destination_types.extend(u.types.iter().cloned());
+            }
+        }
+
+        if destination_types.is_empty() {
+            return;
+        }
+
+        let trait_name = self
+            .pass
+            .naming
+            .query_trail_extensions(&format!("{}Downcast", original_type_name));
+        // Selections from named fragments apply to every type, so a field only tells which type
+        // the fragment is on if no other possible type has it.
+        let mut possible_types_with_field = HashMap::<&str, usize>::new();
+        for type_name in &destination_types {
+            for field in self.fields_map.get(type_name).into_iter().flatten() {
+                *possible_types_with_field
+                    .entry(field.name.as_str())
+                    .or_default() += 1;
             }
         }
 
-        for type_ in destination_types {
+        let mut method_signatures = vec![];
+        let mut method_implementations = vec![];
+
+        for type_name in &destination_types {
+            let type_ = ident(type_name);
+            let method_name = ident(format!("as_{}", type_name.to_snake_case()));
+            let type_fields = self
+                .fields_map
+                .get(type_name)
+                .into_iter()
+                .flatten()
+                .map(|field| field.name.to_mixed_case())
+                .collect::<Vec<_>>();
+            let type_specific_fields = self
+                .fields_map
+                .get(type_name)
+                .into_iter()
+                .flatten()
+                .filter(|field| !own_fields.contains(field.name.as_str()))
+                .filter(|field| possible_types_with_field[field.name.as_str()] == 1)
+                .map(|field| field.name.to_mixed_case())
+                .collect::<Vec<_>>();
+
+            method_signatures.push(quote! {
+                /// Downcast the trail if the query has a fragment on this type.
+                ///
+                /// Generated by `juniper-from-schema`.
+                fn #method_name(&self) -> Option<QueryTrail<'a, #type_, Walked>>;
+            });
+
+            method_implementations.push(quote! {
+                fn #method_name(&self) -> Option<QueryTrail<'a, #type_, Walked>> {
+                    self.downcast_if_selected(
+                        #type_name,
+                        &[#(#type_fields),*],
+                        &[#(#type_specific_fields),*],
+                    )
+                }
+            });
+
+            let type_ = &type_;
             self.pass.extend(quote! {
                 impl<'a> DowncastQueryTrail<'a, #type_> for &QueryTrail<'a, #original_type_name, Walked> {
                     fn downcast(self) -> QueryTrail<'a, #type_, Walked> {
-                        QueryTrail {
-                            look_ahead: self.look_ahead,
-                            node_type: std::marker::PhantomData,
-                            walked: juniper_from_schema::Walked,
-                        }
+                        self.unchecked_downcast()
+                    }
+                }
+
+                impl DowncastOwnedQueryTrail<#type_> for &OwnedQueryTrail<#original_type_name, Walked> {
+                    fn downcast(self) -> OwnedQueryTrail<#type_, Walked> {
+                        self.cast()
                     }
                 }
             });
         }
-    }
-
-    fn error_msg_if_field_types_dont_overlap(&mut self, union: &'doc UnionType) {
-        let fields_map = &self.fields_map;
-        let mut prev: HashMap<&'doc str, (&'doc str, &'doc str)> = HashMap::new();
-
-        for type_b in &union.types {
-            if let Some(fields) = fields_map.get(type_b) {
-                for field in fields {
-                    let field_type_b = type_name(&field.field_type);
 
-                    if let Some((type_a, field_type_a)) = prev.get(&field.name.as_ref()) {
-                        if field_type_b != field_type_a {
-                            self.pass.emit_non_fatal_error(
-                                union.position,
-                                ErrorKind::UnionFieldTypeMismatch {
-                                    union_name: &union.name,
-                                    field_name: &field.name,
-                                    type_a: &type_a,
-                                    type_b: &type_b,
-                                    field_type_a: &field_type_a,
-                                    field_type_b: &field_type_b,
-                                },
-                            );
-                        }
-                    }
+        self.pass.extend(quote! {
+            /// Extension trait for `QueryTrail` to downcast to the types the query has fragments
+            /// on.
+            pub trait #trait_name<'a> {
+                #(#method_signatures)*
+            }
 
-                    prev.insert(&field.name, (type_b, field_type_b));
-                }
+            impl<'a> #trait_name<'a> for QueryTrail<'a, #original_type_name, Walked> {
+                #(#method_implementations)*
             }
-        }
+        });
     }
 
     fn gen_field_walk_method(
@@ -480,23 +706,34 @@ impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
 
                 let method_implementation = quote! {
                     fn #name(&self) -> bool {
-                        use juniper::LookAheadMethods;
+                        self.child_selected(#string_name)
+                    }
+                };
 
-                        self.look_ahead
-                            .and_then(|la| la.select_child(#string_name))
-                            .is_some()
+                let owned_method_implementation = quote! {
+                    fn #name(&self) -> bool {
+                        self.child_selected(#string_name)
                     }
                 };
 
                 let (argument_signature, argument_implementation, argument_type) =
                     self.gen_args_query_trail(field, &name, obj);
+                let (owned_argument_signature, owned_argument_implementation) =
+                    gen_owned_args_query_trail(field, &name, obj, &self.pass.naming);
+
+                let field_descriptor = self.gen_field_descriptor(field);
 
                 FieldWalkMethod {
+                    owned_method_signature: method_signature.clone(),
                     method_signature,
                     method_implementation,
+                    owned_method_implementation,
                     argument_signature,
                     argument_implementation,
+                    owned_argument_signature,
+                    owned_argument_implementation,
                     argument_type,
+                    field_descriptor,
                 }
             }
             TypeKind::Type => {
@@ -512,32 +749,109 @@ impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
 
                 let method_implementation = quote! {
                     fn #name(&self) -> QueryTrail<'a, #field_type, juniper_from_schema::NotWalked> {
-                        use juniper::LookAheadMethods;
+                        self.child_trail(#string_name)
+                    }
+                };
 
-                        let child = self.look_ahead.and_then(|la| la.select_child(#string_name));
+                let owned_method_signature = quote! {
+                    /// Walk the trail into a field.
+                    ///
+                    /// Generated by `juniper-from-schema`.
+                    fn #name(&self) -> OwnedQueryTrail<#field_type, juniper_from_schema::NotWalked>;
+                };
 
-                        QueryTrail {
-                            look_ahead: child,
-                            node_type: std::marker::PhantomData,
-                            walked: juniper_from_schema::NotWalked,
-                        }
+                let owned_method_implementation = quote! {
+                    fn #name(&self) -> OwnedQueryTrail<#field_type, juniper_from_schema::NotWalked> {
+                        self.child(#string_name)
                     }
                 };
 
                 let (argument_signature, argument_implementation, argument_type) =
                     self.gen_args_query_trail(field, &name, obj);
+                let (owned_argument_signature, owned_argument_implementation) =
+                    gen_owned_args_query_trail(field, &name, obj, &self.pass.naming);
+
+                let field_descriptor = self.gen_field_descriptor(field);
 
                 FieldWalkMethod {
                     method_signature,
                     method_implementation,
+                    owned_method_signature,
+                    owned_method_implementation,
                     argument_signature,
                     argument_implementation,
+                    owned_argument_signature,
+                    owned_argument_implementation,
                     argument_type,
+                    field_descriptor,
                 }
             }
         }
     }
 
+    fn gen_field_descriptor(&self, field: &Field) -> TokenStream {
+        let name = field.name.to_mixed_case();
+        let field_type = type_name(&field.field_type);
+
+        let (children, possible_types) = if self.query_trail_types.contains(field_type.as_str()) {
+            let field_type = ident(field_type.to_camel_case());
+            (
+                quote! { <#field_type as juniper_from_schema::QueryTrailFields>::query_trail_fields },
+                quote! {
+                    <#field_type as juniper_from_schema::QueryTrailFields>::query_trail_possible_types
+                },
+            )
+        } else {
+            (
+                quote! { juniper_from_schema::no_query_trail_fields },
+                quote! { juniper_from_schema::no_query_trail_possible_types },
+            )
+        };
+
+        quote! {
+            juniper_from_schema::QueryTrailField {
+                name: #name,
+                children: #children,
+                possible_types: #possible_types,
+            }
+        }
+    }
+
+    /// Describe a field that has different types on different members of a union, by merging the
+    /// fields of the types.
+    fn gen_merged_field_descriptor(&self, fields: &[&Field]) -> TokenStream {
+        let name = fields[0].name.to_mixed_case();
+
+        let mut child_names = HashSet::new();
+        let children = fields
+            .iter()
+            .flat_map(|field| {
+                self.fields_map
+                    .get(type_name(&field.field_type))
+                    .into_iter()
+                    .flatten()
+            })
+            .filter(|child| child_names.insert(child.name.as_str()))
+            .map(|child| self.gen_field_descriptor(child))
+            .collect::<Vec<_>>();
+
+        quote! {
+            juniper_from_schema::QueryTrailField {
+                name: #name,
+                children: {
+                    fn children() -> &'static [juniper_from_schema::QueryTrailField] {
+                        const FIELDS: &[juniper_from_schema::QueryTrailField] = &[
+                            #(#children,)*
+                        ];
+                        FIELDS
+                    }
+                    children
+                },
+                possible_types: juniper_from_schema::no_query_trail_possible_types,
+            }
+        }
+    }
+
     fn gen_args_query_trail(
         &mut self,
         field: &Field,
@@ -548,8 +862,6 @@ impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
         let mut argument_implementation = quote! {};
         let mut argument_type = quote! {};
 
-        let obj_type = ident(&obj.name());
-
         let args_method_name = ident(&format!("{}_args", name));
 
         if field.arguments.is_empty() {
@@ -568,36 +880,34 @@ impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
                 }
             });
         } else {
-            let args_type_name = ident(&format!(
-                "{}{}Args",
-                obj.name(),
-                name.to_string().to_camel_case()
-            ));
+            let args_type_name = self.pass.naming.args_type(obj.name(), &name.to_string());
+
+            let field_name = &field.name;
 
             argument_signature.extend(quote! {
                 /// Inspect argument in incoming query.
-                fn #args_method_name(&'a self) -> #args_type_name<'a>;
+                ///
+                /// Returns `None` if the field isn't part of the query.
+                fn #args_method_name(&self) -> Option<#args_type_name<'a>>;
             });
 
             argument_implementation.extend(quote! {
                 #[allow(missing_docs)]
-                fn #args_method_name(&'a self) -> #args_type_name<'a> {
-                    #args_type_name(self)
+                fn #args_method_name(&self) -> Option<#args_type_name<'a>> {
+                    self.child_arguments(#field_name).map(#args_type_name)
                 }
             });
 
             let arguments_methods = field
                 .arguments
                 .iter()
-                .map(|input_value| self.gen_argument_look_ahead_methods(input_value, &field.name));
+                .map(|input_value| self.gen_argument_look_ahead_methods(input_value));
 
             argument_type.extend(quote! {
                 /// This is used for inspecting arguments to a field.
                 ///
                 /// Generated by `juniper-from-schema`.
-                pub struct #args_type_name<'a>(
-                    &'a QueryTrail<'a, #obj_type, juniper_from_schema::Walked>
-                );
+                pub struct #args_type_name<'a>(juniper_from_schema::SelectionRef<'a>);
 
                 impl<'a> #args_type_name<'a> {
                     #(#arguments_methods)*
@@ -608,11 +918,7 @@ impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
         (argument_signature, argument_implementation, argument_type)
     }
 
-    fn gen_argument_look_ahead_methods(
-        &mut self,
-        input_value: &InputValue,
-        field_name: &str,
-    ) -> TokenStream {
+    fn gen_argument_look_ahead_methods(&mut self, input_value: &InputValue) -> TokenStream {
         let default_value = input_value.default_value.as_ref().map(|value| {
             self.pass.quote_value(
                 &value,
@@ -623,7 +929,6 @@ impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
 
         let (field_type, _) = self.pass.gen_field_type(
             &input_value.value_type,
-            &FieldTypeDestination::Argument,
             default_value.is_some(),
             input_value.position,
         );
@@ -631,51 +936,27 @@ impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
         let name = &input_value.name;
         let ident = ident(name.to_snake_case());
 
-        if let Some(default_value) = default_value {
-            quote! {
-                #[allow(missing_docs)]
-                pub fn #ident(&self) -> #field_type {
-                    use juniper::LookAheadMethods;
-
-                    // these `expect`s are fine since these methods you can only obtain
-                    // arguments from walked query trails
-                    let lh = &self
-                        .0
-                        .look_ahead
-                        .expect("look_ahead")
-                        .select_child(#field_name)
-                        .expect("select child");
-
-                    let arg = lh.arguments().iter().find(|arg| {
-                        arg.name() == #name
-                    });
-
-                    if let Some(arg) = arg {
-                        let value = arg.value();
-                        FromLookAheadValue::<#field_type>::from(value)
-                    } else {
-                        #default_value
-                    }
+        let missing_value = match (default_value, &input_value.value_type) {
+            (Some(default_value), _) => quote! { Ok(#default_value) },
+            (None, Type::NonNullType(_)) => {
+                let expected = input_value.value_type.to_string();
+                quote! {
+                    Err(
+                        juniper_from_schema::ConversionError::new(#expected, "argument is missing")
+                            .in_path(#name)
+                    )
                 }
             }
-        } else {
-            quote! {
-                #[allow(missing_docs)]
-                pub fn #ident(&self) -> #field_type {
-                    use juniper::LookAheadMethods;
+            (None, _) => quote! { Ok(None) },
+        };
 
-                    // these `expect`s are fine since these methods you can only obtain
-                    // arguments from walked query trails
-                    let lh = &self
-                        .0
-                        .look_ahead
-                        .expect("look_ahead")
-                        .select_child(#field_name)
-                        .expect("select child");
-
-                    let arg = lh.arguments().iter().find(|arg| { arg.name() == #name }).expect("no argument with name");
-                    let value = arg.value();
-                    FromLookAheadValue::<#field_type>::from(value)
+        quote! {
+            #[allow(missing_docs)]
+            pub fn #ident(&self) -> Result<#field_type, juniper_from_schema::ConversionError> {
+                match self.0.argument(#name) {
+                    Some(value) => FromLookAheadValue::<#field_type>::from(&value)
+                        .map_err(|err| err.in_path(#name)),
+                    None => #missing_value,
                 }
             }
         }
@@ -683,6 +964,36 @@ impl<'pass, 'doc> QueryTrailCodeGenPass<'pass, 'doc> {
 }
 
 impl<'pass, 'doc> SchemaVisitor<'doc> for QueryTrailCodeGenPass<'pass, 'doc> {
+    fn visit_schema_definition(&mut self, schema_def: &'doc SchemaDefinition) {
+        if let Some(query) = &schema_def.query {
+            let field_enum_name = self.pass.naming.field_enum(query);
+            let fields = self.fields_map.get(query).cloned().unwrap_or_default();
+
+            let arms = fields.iter().map(|field| {
+                let variant = ident(field.name.to_camel_case());
+                let complexity = self.gen_selected_field_complexity(field);
+                quote! { #field_enum_name::#variant => #complexity, }
+            });
+
+            self.pass.extend(quote! {
+                /// Compute the complexity of a field of the query type from the `@cost` directives
+                /// in the schema, including the cost of the field itself. `look_ahead` is the
+                /// selection of the field, as returned by `executor.look_ahead()` in its resolver.
+                ///
+                /// Generated by `juniper-from-schema`.
+                pub fn complexity(
+                    field: #field_enum_name,
+                    look_ahead: &juniper::LookAheadSelection<'_, juniper::DefaultScalarValue>,
+                ) -> u64 {
+                    let child = look_ahead;
+                    match field {
+                        #(#arms)*
+                    }
+                }
+            });
+        }
+    }
+
     fn visit_object_type(&mut self, obj: &'doc ObjectType) {
         self.gen_field_walk_methods(InternalQueryTrailNode::Object(obj));
     }
@@ -692,8 +1003,6 @@ impl<'pass, 'doc> SchemaVisitor<'doc> for QueryTrailCodeGenPass<'pass, 'doc> {
     }
 
     fn visit_union_type(&mut self, union: &'doc UnionType) {
-        self.error_msg_if_field_types_dont_overlap(union);
-
         self.gen_field_walk_methods(InternalQueryTrailNode::Union(
             union,
             build_union_fields_set(union, &self.fields_map),
@@ -704,9 +1013,14 @@ impl<'pass, 'doc> SchemaVisitor<'doc> for QueryTrailCodeGenPass<'pass, 'doc> {
 struct FieldWalkMethod {
     method_signature: TokenStream,
     method_implementation: TokenStream,
+    owned_method_signature: TokenStream,
+    owned_method_implementation: TokenStream,
     argument_signature: TokenStream,
     argument_implementation: TokenStream,
+    owned_argument_signature: TokenStream,
+    owned_argument_implementation: TokenStream,
     argument_type: TokenStream,
+    field_descriptor: TokenStream,
 }
 
 #[derive(Clone, Debug)]
@@ -742,33 +1056,74 @@ impl<'a> InternalQueryTrailNode<'a> {
         }
     }
 
+    fn position(&self) -> Pos {
+        match self {
+            InternalQueryTrailNode::Object(inner) => inner.position,
+            InternalQueryTrailNode::Interface(inner) => inner.position,
+            InternalQueryTrailNode::Union(inner, _fields) => inner.position,
+        }
+    }
+
     fn fields(&self) -> Vec<&'a Field> {
         match self {
             InternalQueryTrailNode::Object(inner) => inner.fields.iter().collect(),
             InternalQueryTrailNode::Interface(inner) => inner.fields.iter().collect(),
-            InternalQueryTrailNode::Union(_inner, fields) => fields
-                .iter()
-                .map(|hashable_field| hashable_field.0)
-                .collect(),
+            InternalQueryTrailNode::Union(_inner, fields) => {
+                let mut fields = fields
+                    .iter()
+                    .map(|hashable_field| hashable_field.0)
+                    .collect::<Vec<_>>();
+                fields.sort_by(|a, b| a.name.cmp(&b.name));
+                fields
+            }
         }
     }
 }
 
+/// The fields of the members of a union where every member that has the field agrees on its type.
+///
+/// Fields with conflicting types are left out, they can only be reached by downcasting.
 fn build_union_fields_set<'d>(
     union: &UnionType,
     fields_map: &HashMap<&'d String, Vec<&'d Field>>,
 ) -> HashSet<HashFieldByName<'d>> {
-    let mut union_fields_set = HashSet::new();
+    union_fields_by_name(union, fields_map)
+        .values()
+        .filter(|fields| field_types_agree(fields))
+        .map(|fields| HashFieldByName(fields[0]))
+        .collect()
+}
 
-    for type_ in &union.types {
-        if let Some(fields) = fields_map.get(type_) {
-            for field in fields {
-                union_fields_set.insert(HashFieldByName(&field));
-            }
+fn union_fields_by_name<'d>(
+    union: &UnionType,
+    fields_map: &HashMap<&'d String, Vec<&'d Field>>,
+) -> BTreeMap<&'d str, Vec<&'d Field>> {
+    fields_by_name(union.types.iter().cloned(), fields_map)
+}
+
+/// The fields of the given object types, grouped by field name.
+fn fields_by_name<'d>(
+    types: impl IntoIterator<Item = String>,
+    fields_map: &HashMap<&'d String, Vec<&'d Field>>,
+) -> BTreeMap<&'d str, Vec<&'d Field>> {
+    let mut fields_by_name = BTreeMap::new();
+
+    for type_ in types {
+        for field in fields_map.get(&type_).into_iter().flatten() {
+            fields_by_name
+                .entry(field.name.as_str())
+                .or_insert_with(Vec::new)
+                .push(*field);
         }
     }
 
-    union_fields_set
+    fields_by_name
+}
+
+fn field_types_agree(fields: &[&Field]) -> bool {
+    fields
+        .windows(2)
+        .all(|pair| type_name(&pair[0].field_type) == type_name(&pair[1].field_type))
 }
 
 fn build_fields_map(doc: &Document) -> HashMap<&String, Vec<&Field>> {
@@ -788,21 +1143,126 @@ fn build_fields_map(doc: &Document) -> HashMap<&String, Vec<&Field>> {
     map
 }
 
+fn gen_owned_args_query_trail(
+    field: &Field,
+    name: &Ident,
+    obj: &InternalQueryTrailNode,
+    naming: &Naming,
+) -> (TokenStream, TokenStream) {
+    let args_method_name = ident(&format!("{}_args", name));
+
+    if field.arguments.is_empty() {
+        let signature = quote! {
+            /// Inspect argument in incoming query.
+            ///
+            /// This field takes no arguments, so therefore it returns `()`.
+            fn #args_method_name(&self) -> ();
+        };
+
+        let implementation = quote! {
+            #[allow(missing_docs)]
+            #[inline]
+            fn #args_method_name(&self) -> () {
+                ()
+            }
+        };
+
+        (signature, implementation)
+    } else {
+        let args_type_name = naming.args_type(obj.name(), &name.to_string());
+        let field_name = &field.name;
+
+        let signature = quote! {
+            /// Inspect argument in incoming query.
+            ///
+            /// Returns `None` if the field isn't part of the query.
+            fn #args_method_name(&self) -> Option<#args_type_name<'_>>;
+        };
+
+        let implementation = quote! {
+            #[allow(missing_docs)]
+            fn #args_method_name(&self) -> Option<#args_type_name<'_>> {
+                self.child_arguments(#field_name).map(#args_type_name)
+            }
+        };
+
+        (signature, implementation)
+    }
+}
+
+fn build_sql_tables(doc: &Document) -> HashMap<&str, String> {
+    doc.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(obj)) => {
+                SqlTable::of_object(obj).map(|sql| (obj.name.as_str(), sql.table))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn build_query_trail_types(doc: &Document) -> HashSet<&str> {
+    doc.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(TypeDefinition::Object(obj)) => Some(obj.name.as_str()),
+            Definition::TypeDefinition(TypeDefinition::Interface(interface)) => {
+                Some(interface.name.as_str())
+            }
+            Definition::TypeDefinition(TypeDefinition::Union(union)) => Some(union.name.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn build_type_names(doc: &Document) -> HashSet<&str> {
+    doc.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::TypeDefinition(type_def) => Some(type_definition_name(type_def)),
+            _ => None,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::ast_pass::ast_data_pass::AstData;
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    fn code_gen_pass<'doc>(
+        schema: &'doc str,
+        doc: &'doc Document,
+        naming: Naming,
+    ) -> CodeGenPass<'doc> {
+        let ast_data = AstData::new_from_schema_and_doc(&schema, &doc).unwrap();
+        CodeGenPass {
+            tokens: quote! {},
+            error_type: crate::parse_input::default_error_type(),
+            context_type: crate::parse_input::default_context_type(),
+            naming,
+            attributes: Default::default(),
+            ast_data,
+            errors: std::collections::BTreeSet::new(),
+            raw_schema: schema,
+        }
+    }
 
     #[test]
-    fn test_fails_to_generate_query_trail_for_unions_where_fields_dont_overlap() {
+    fn test_skips_union_fields_whose_types_dont_overlap() {
         let schema = r#"
             union Entity = User | Company
 
             type User {
+              name: String!
               country: Country!
             }
 
             type Company {
+              name: String!
               country: OtherCountry!
             }
 
@@ -816,18 +1276,61 @@ mod test {
         "#;
 
         let doc = graphql_parser::parse_schema(&schema).unwrap();
-        let ast_data = AstData::new_from_schema_and_doc(&schema, &doc).unwrap();
-        let mut out = CodeGenPass {
-            tokens: quote! {},
-            error_type: crate::parse_input::default_error_type(),
-            context_type: crate::parse_input::default_context_type(),
-            ast_data,
-            errors: std::collections::BTreeSet::new(),
-            raw_schema: schema,
-        };
+        let mut out = code_gen_pass(schema, &doc, Naming::default());
+
+        out.gen_query_trails(&doc);
+
+        assert!(out.errors.is_empty());
+
+        let tokens = out.tokens.to_string();
+        assert!(tokens.contains("pub enum EntityField { # [allow (missing_docs)] Name , }"));
+        assert!(tokens.contains("fn as_company (& self)"));
+    }
+
+    #[test]
+    fn test_field_enum_clashing_with_schema_type() {
+        let schema = r#"
+            type User {
+              name: String!
+              role: UserField!
+            }
+
+            enum UserField {
+              ADMIN
+              MEMBER
+            }
+        "#;
+
+        let doc = graphql_parser::parse_schema(&schema).unwrap();
+        let mut out = code_gen_pass(schema, &doc, Naming::default());
+
+        out.gen_query_trails(&doc);
+
+        let errors = out
+            .errors
+            .into_iter()
+            .map(|error| error.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![ErrorKind::FieldEnumNameClash("UserField".to_string())]
+        );
+
+        let mut naming = Naming::default();
+        naming
+            .set(
+                "field_enum",
+                &LitStr::new("{type}SelectedField", Span::call_site()),
+            )
+            .unwrap();
+        let mut out = code_gen_pass(schema, &doc, naming);
 
         out.gen_query_trails(&doc);
 
-        assert_eq!(1, out.errors.len());
+        assert!(out.errors.is_empty());
+        assert!(out
+            .tokens
+            .to_string()
+            .contains("pub enum UserSelectedField"));
     }
 }
@@ -1,7 +1,10 @@
-use crate::ast_pass::{
-    code_gen_pass::CodeGenPass,
-    error::{self, ErrorKind, Juniper, UnsupportedDirectiveKind, ValueType},
-    EmitError,
+use crate::{
+    ast_pass::{
+        code_gen_pass::CodeGenPass,
+        error::{self, ErrorKind, Juniper, UnsupportedDirectiveKind, ValueType},
+        EmitError,
+    },
+    type_attributes::TypeAttributes,
 };
 use graphql_parser::{query::Value, schema::*};
 use std::convert::identity;
@@ -16,6 +19,15 @@ pub trait FromDirectiveArguments: Sized + Default {
     fn from_directive_args(args: &(String, Value)) -> Option<Result<Self, ErrorKind>>;
 }
 
+/// Arguments that fall back to a default set elsewhere when they're not given.
+impl<T: FromDirectiveArguments> FromDirectiveArguments for Option<T> {
+    const KEY: &'static str = T::KEY;
+
+    fn from_directive_args(args: &(String, Value)) -> Option<Result<Self, ErrorKind>> {
+        T::from_directive_args(args).map(|arg| arg.map(Some))
+    }
+}
+
 #[derive(Debug)]
 pub enum Deprecation {
     NoDeprecation,
@@ -71,6 +83,198 @@ impl FromDirective for Deprecation {
     }
 }
 
+/// `@cost(value: Int, multipliers: [String])`, used for query complexity analysis.
+#[derive(Debug)]
+pub struct Cost {
+    pub value: u64,
+    pub multipliers: Vec<String>,
+}
+
+impl Default for Cost {
+    fn default() -> Self {
+        Cost {
+            value: 1,
+            multipliers: Vec::new(),
+        }
+    }
+}
+
+impl FromDirective for Cost {
+    fn from_directive(dir: &Directive) -> Result<Self, ErrorKind> {
+        let name = &dir.name;
+        if name != "cost" {
+            return Err(ErrorKind::UnsupportedDirective(
+                UnsupportedDirectiveKind::Cost(error::Cost::InvalidName(name)),
+            ));
+        }
+
+        let mut cost = Cost::default();
+
+        for (key, value) in &dir.arguments {
+            match key.as_str() {
+                "value" => {
+                    let value = value_as_int(value)?;
+                    if value < 0 {
+                        return Err(ErrorKind::UnsupportedDirective(
+                            UnsupportedDirectiveKind::Cost(error::Cost::NegativeValue(value)),
+                        ));
+                    }
+                    cost.value = value as u64;
+                }
+                "multipliers" => {
+                    cost.multipliers = match value {
+                        Value::List(values) => values
+                            .iter()
+                            .map(|value| value_as_string(value).map(|name| name.to_string()))
+                            .collect::<Result<_, _>>()?,
+                        other => {
+                            return Err(ErrorKind::UnsupportedDirective(
+                                UnsupportedDirectiveKind::InvalidType {
+                                    expected: ValueType::List,
+                                    actual: ValueType::from(other),
+                                },
+                            ));
+                        }
+                    };
+                }
+                key => {
+                    return Err(ErrorKind::UnsupportedDirective(
+                        UnsupportedDirectiveKind::Cost(error::Cost::InvalidKey(key)),
+                    ));
+                }
+            }
+        }
+
+        Ok(cost)
+    }
+}
+
+impl Cost {
+    /// Find the `@cost` directive of a field, if it has one and it's valid.
+    pub fn of_field(field: &Field) -> Option<Cost> {
+        field
+            .directives
+            .iter()
+            .find(|dir| dir.name == "cost")
+            .and_then(|dir| Cost::from_directive(dir).ok())
+    }
+
+    fn validate_multipliers<'doc>(&self, field: &'doc Field) -> Result<(), ErrorKind<'doc>> {
+        for multiplier in &self.multipliers {
+            let is_int_argument = field.arguments.iter().any(|arg| {
+                &arg.name == multiplier
+                    && match &arg.value_type {
+                        Type::NamedType(name) => name == "Int",
+                        Type::NonNullType(inner) => **inner == Type::NamedType("Int".to_string()),
+                        Type::ListType(_) => false,
+                    }
+            });
+
+            if !is_int_argument {
+                return Err(ErrorKind::UnsupportedDirective(
+                    UnsupportedDirectiveKind::Cost(error::Cost::UnknownMultiplier(
+                        multiplier.to_string(),
+                    )),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `@sql(table: String)` on object types.
+#[derive(Debug)]
+pub struct SqlTable {
+    pub table: String,
+}
+
+impl FromDirective for SqlTable {
+    fn from_directive(dir: &Directive) -> Result<Self, ErrorKind> {
+        let name = &dir.name;
+        if name != "sql" {
+            return Err(ErrorKind::UnsupportedDirective(
+                UnsupportedDirectiveKind::Sql(error::Sql::InvalidName(name)),
+            ));
+        }
+
+        let mut table = None;
+
+        for (key, value) in &dir.arguments {
+            match key.as_str() {
+                "table" => table = Some(value_as_string(value)?.to_string()),
+                key => {
+                    return Err(ErrorKind::UnsupportedDirective(
+                        UnsupportedDirectiveKind::Sql(error::Sql::InvalidTypeKey(key)),
+                    ));
+                }
+            }
+        }
+
+        match table {
+            Some(table) => Ok(SqlTable { table }),
+            None => Err(ErrorKind::UnsupportedDirective(
+                UnsupportedDirectiveKind::Sql(error::Sql::MissingTable),
+            )),
+        }
+    }
+}
+
+impl SqlTable {
+    /// Find the `@sql` directive of an object type, if it has one and it's valid.
+    pub fn of_object(obj: &ObjectType) -> Option<SqlTable> {
+        obj.directives
+            .iter()
+            .find(|dir| dir.name == "sql")
+            .and_then(|dir| SqlTable::from_directive(dir).ok())
+    }
+}
+
+/// `@sql(column: String, join: String)` on fields.
+#[derive(Debug, Default)]
+pub struct SqlField {
+    pub column: Option<String>,
+    pub join: Option<String>,
+}
+
+impl FromDirective for SqlField {
+    fn from_directive(dir: &Directive) -> Result<Self, ErrorKind> {
+        let name = &dir.name;
+        if name != "sql" {
+            return Err(ErrorKind::UnsupportedDirective(
+                UnsupportedDirectiveKind::Sql(error::Sql::InvalidName(name)),
+            ));
+        }
+
+        let mut sql = SqlField::default();
+
+        for (key, value) in &dir.arguments {
+            match key.as_str() {
+                "column" => sql.column = Some(value_as_string(value)?.to_string()),
+                "join" => sql.join = Some(value_as_string(value)?.to_string()),
+                key => {
+                    return Err(ErrorKind::UnsupportedDirective(
+                        UnsupportedDirectiveKind::Sql(error::Sql::InvalidFieldKey(key)),
+                    ));
+                }
+            }
+        }
+
+        Ok(sql)
+    }
+}
+
+impl SqlField {
+    /// Find the `@sql` directive of a field, if it has one and it's valid.
+    pub fn of_field(field: &Field) -> Option<SqlField> {
+        field
+            .directives
+            .iter()
+            .find(|dir| dir.name == "sql")
+            .and_then(|dir| SqlField::from_directive(dir).ok())
+    }
+}
+
 #[derive(Debug)]
 pub struct JuniperDirective<T> {
     pub name: String,
@@ -143,19 +347,105 @@ macro_rules! impl_from_directive_for {
 
 impl_from_directive_for! { (A) }
 impl_from_directive_for! { (A, B) }
+impl_from_directive_for! { (A, B, C) }
+impl_from_directive_for! { (A, B, C, D) }
+impl_from_directive_for! { (A, B, C, D, E) }
+impl_from_directive_for! { (A, B, C, D, E, F, G) }
 
 #[derive(Debug)]
 pub struct FieldArguments {
     pub ownership: Ownership,
     pub deprecated: Option<Deprecation>,
     pub infallible: Infallible,
+    pub trail: Trail,
+    pub executor: Executor,
+    pub arguments_struct: ArgumentsStruct,
 }
 
-#[derive(Debug)]
+/// Defaults for the `@juniper` arguments of fields, set with `@juniper` on `schema { }` or on
+/// object and interface types.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FieldDefaults {
+    pub ownership: Option<Ownership>,
+    pub infallible: Option<Infallible>,
+    pub trail: Option<Trail>,
+    pub executor: Option<Executor>,
+    pub arguments_struct: Option<ArgumentsStruct>,
+}
+
+type FieldDefaultsArgs = (
+    Option<Ownership>,
+    Option<Infallible>,
+    Option<Trail>,
+    Option<Executor>,
+    Option<ArgumentsStruct>,
+);
+
+impl FieldDefaults {
+    fn from_args(
+        (ownership, infallible, trail, executor, arguments_struct): FieldDefaultsArgs,
+    ) -> Self {
+        FieldDefaults {
+            ownership,
+            infallible,
+            trail,
+            executor,
+            arguments_struct,
+        }
+    }
+
+    /// Use the defaults from `self`, falling back to `parent` for the ones that aren't set.
+    pub fn or(self, parent: FieldDefaults) -> FieldDefaults {
+        FieldDefaults {
+            ownership: self.ownership.or(parent.ownership),
+            infallible: self.infallible.or(parent.infallible),
+            trail: self.trail.or(parent.trail),
+            executor: self.executor.or(parent.executor),
+            arguments_struct: self.arguments_struct.or(parent.arguments_struct),
+        }
+    }
+
+    /// Find the defaults set on `schema { }`. Invalid directives are ignored, they're reported
+    /// when generating the schema.
+    pub fn of_schema(schema_def: &SchemaDefinition) -> FieldDefaults {
+        schema_def
+            .directives
+            .iter()
+            .filter_map(|dir| JuniperDirective::<FieldDefaultsArgs>::from_directive(dir).ok())
+            .fold(FieldDefaults::default(), |defaults, dir| {
+                FieldDefaults::from_args(dir.args).or(defaults)
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
 pub enum Ownership {
     Owned,
     Borrowed,
+    BorrowedFromContext,
     AsRef,
+    Str,
+    Slice,
+    Arc,
+    Cow,
+    Iter,
+}
+
+impl Ownership {
+    /// The value of the `ownership` argument.
+    pub fn name(self) -> &'static str {
+        match self {
+            Ownership::Owned => "owned",
+            Ownership::Borrowed => "borrowed",
+            Ownership::BorrowedFromContext => "borrowed_from_context",
+            Ownership::AsRef => "as_ref",
+            Ownership::Str => "str",
+            Ownership::Slice => "slice",
+            Ownership::Arc => "arc",
+            Ownership::Cow => "cow",
+            Ownership::Iter => "iter",
+        }
+    }
 }
 
 impl Default for Ownership {
@@ -178,7 +468,13 @@ impl FromDirectiveArguments for Ownership {
             let ownership = match ownership_raw {
                 "owned" => Ownership::Owned,
                 "borrowed" => Ownership::Borrowed,
+                "borrowed_from_context" => Ownership::BorrowedFromContext,
                 "as_ref" => Ownership::AsRef,
+                "str" => Ownership::Str,
+                "slice" => Ownership::Slice,
+                "arc" => Ownership::Arc,
+                "cow" => Ownership::Cow,
+                "iter" => Ownership::Iter,
                 value => {
                     return Err(ErrorKind::UnsupportedDirective(
                         UnsupportedDirectiveKind::Ownership(error::Ownership::InvalidValue(value)),
@@ -192,7 +488,7 @@ impl FromDirectiveArguments for Ownership {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Infallible {
     pub value: bool,
 }
@@ -220,6 +516,160 @@ impl FromDirectiveArguments for Infallible {
     }
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct Trail {
+    pub value: bool,
+}
+
+impl Default for Trail {
+    fn default() -> Self {
+        Trail { value: true }
+    }
+}
+
+impl FromDirectiveArguments for Trail {
+    const KEY: &'static str = "trail";
+
+    fn from_directive_args((key, value): &(String, Value)) -> Option<Result<Self, ErrorKind>> {
+        if key != Self::KEY {
+            return None;
+        }
+
+        let directive = (|| {
+            let value = value_as_bool(value)?;
+            Ok(Self { value })
+        })();
+
+        Some(directive)
+    }
+}
+
+/// `executor: false` passes the context to field methods instead of the executor.
+#[derive(Debug, Clone, Copy)]
+pub struct Executor {
+    pub value: bool,
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Executor { value: true }
+    }
+}
+
+impl FromDirectiveArguments for Executor {
+    const KEY: &'static str = "executor";
+
+    fn from_directive_args((key, value): &(String, Value)) -> Option<Result<Self, ErrorKind>> {
+        if key != Self::KEY {
+            return None;
+        }
+
+        let directive = (|| {
+            let value = value_as_bool(value)?;
+            Ok(Self { value })
+        })();
+
+        Some(directive)
+    }
+}
+
+/// `arguments_struct: true` passes the arguments of a field to its field method as a generated
+/// struct instead of one parameter per argument.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ArgumentsStruct {
+    pub value: bool,
+}
+
+impl FromDirectiveArguments for ArgumentsStruct {
+    const KEY: &'static str = "arguments_struct";
+
+    fn from_directive_args((key, value): &(String, Value)) -> Option<Result<Self, ErrorKind>> {
+        if key != Self::KEY {
+            return None;
+        }
+
+        let directive = (|| {
+            let value = value_as_bool(value)?;
+            Ok(Self { value })
+        })();
+
+        Some(directive)
+    }
+}
+
+/// `derive: [String!]` on types, such as `derive: ["serde::Serialize"]`.
+#[derive(Debug, Default)]
+pub struct Derive {
+    pub paths: Vec<syn::Path>,
+}
+
+impl FromDirectiveArguments for Derive {
+    const KEY: &'static str = "derive";
+
+    fn from_directive_args((key, value): &(String, Value)) -> Option<Result<Self, ErrorKind>> {
+        if key != Self::KEY {
+            return None;
+        }
+
+        let directive = value_as_string_list(value).and_then(|values| {
+            let paths = values
+                .into_iter()
+                .map(|value| {
+                    syn::parse_str(value).map_err(|_| {
+                        ErrorKind::UnsupportedDirective(UnsupportedDirectiveKind::Juniper(
+                            Juniper::UnparsableDerive(value),
+                        ))
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Self { paths })
+        });
+
+        Some(directive)
+    }
+}
+
+/// `attributes: [String!]` on types, such as `attributes: ["#[serde(rename_all = \"camelCase\")]"]`.
+#[derive(Debug, Default)]
+pub struct Attributes {
+    pub attributes: Vec<syn::Attribute>,
+}
+
+impl FromDirectiveArguments for Attributes {
+    const KEY: &'static str = "attributes";
+
+    fn from_directive_args((key, value): &(String, Value)) -> Option<Result<Self, ErrorKind>> {
+        if key != Self::KEY {
+            return None;
+        }
+
+        let directive = value_as_string_list(value).and_then(|values| {
+            let attributes = values
+                .into_iter()
+                .map(|value| {
+                    let invalid = || {
+                        ErrorKind::UnsupportedDirective(UnsupportedDirectiveKind::Juniper(
+                            Juniper::UnparsableAttribute(value),
+                        ))
+                    };
+
+                    let mut attributes =
+                        syn::parse::Parser::parse_str(syn::Attribute::parse_outer, value)
+                            .map_err(|_| invalid())?;
+
+                    match (attributes.pop(), attributes.is_empty()) {
+                        (Some(attribute), true) => Ok(attribute),
+                        _ => Err(invalid()),
+                    }
+                })
+                .collect::<Result<_, _>>()?;
+            Ok(Self { attributes })
+        });
+
+        Some(directive)
+    }
+}
+
 #[derive(Debug)]
 pub struct DateTimeScalarArguments {
     pub with_time_zone: bool,
@@ -262,6 +712,30 @@ fn value_as_string(value: &Value) -> Result<&str, ErrorKind> {
     }
 }
 
+fn value_as_string_list(value: &Value) -> Result<Vec<&str>, ErrorKind> {
+    match value {
+        Value::List(values) => values.iter().map(value_as_string).collect(),
+        other => Err(ErrorKind::UnsupportedDirective(
+            UnsupportedDirectiveKind::InvalidType {
+                expected: ValueType::List,
+                actual: ValueType::from(other),
+            },
+        )),
+    }
+}
+
+fn value_as_int(value: &Value) -> Result<i64, ErrorKind> {
+    match value {
+        Value::Int(x) => Ok(x.as_i64().unwrap_or_default()),
+        other => Err(ErrorKind::UnsupportedDirective(
+            UnsupportedDirectiveKind::InvalidType {
+                expected: ValueType::Int,
+                actual: ValueType::from(other),
+            },
+        )),
+    }
+}
+
 fn value_as_bool(value: &Value) -> Result<bool, ErrorKind> {
     match value {
         Value::Boolean(x) => Ok(*x),
@@ -280,20 +754,54 @@ pub trait ParseDirective<T> {
     fn parse_directives(&mut self, input: T) -> Self::Output;
 }
 
-impl<'doc> ParseDirective<&'doc Field> for CodeGenPass<'doc> {
+/// A field along with the defaults from its type and the schema.
+#[derive(Debug)]
+pub struct FieldWithDefaults<'a>(pub &'a Field, pub FieldDefaults);
+
+impl<'doc> ParseDirective<FieldWithDefaults<'doc>> for CodeGenPass<'doc> {
     type Output = FieldArguments;
 
-    fn parse_directives(&mut self, input: &'doc Field) -> Self::Output {
-        let mut ownership = Ownership::default();
+    fn parse_directives(&mut self, input: FieldWithDefaults<'doc>) -> Self::Output {
+        let FieldWithDefaults(input, defaults) = input;
+        let mut ownership = defaults.ownership.unwrap_or_default();
         let mut deprecated = None::<Deprecation>;
-        let mut infallible = Infallible::default();
+        let mut infallible = defaults.infallible.unwrap_or_default();
+        let mut trail = defaults.trail.unwrap_or_default();
+        let mut executor = defaults.executor.unwrap_or_default();
+        let mut arguments_struct = defaults.arguments_struct.unwrap_or_default();
 
         for dir in &input.directives {
+            if dir.name == "cost" {
+                let cost = Cost::from_directive(dir)
+                    .and_then(|cost| cost.validate_multipliers(input).map(|_| cost));
+                if let Err(err) = cost {
+                    self.emit_non_fatal_error(dir.position, err);
+                }
+                continue;
+            }
+
+            if dir.name == "sql" {
+                if let Err(err) = SqlField::from_directive(dir) {
+                    self.emit_non_fatal_error(dir.position, err);
+                }
+                continue;
+            }
+
             if let Ok(juniper_directive) =
-                JuniperDirective::<(Ownership, Infallible)>::from_directive(dir)
+                JuniperDirective::<FieldDefaultsArgs>::from_directive(dir)
             {
-                ownership = juniper_directive.args.0;
-                infallible = juniper_directive.args.1;
+                let (
+                    field_ownership,
+                    field_infallible,
+                    field_trail,
+                    field_executor,
+                    field_arguments_struct,
+                ) = juniper_directive.args;
+                ownership = field_ownership.unwrap_or(ownership);
+                infallible = field_infallible.unwrap_or(infallible);
+                trail = field_trail.unwrap_or(trail);
+                executor = field_executor.unwrap_or(executor);
+                arguments_struct = field_arguments_struct.unwrap_or(arguments_struct);
                 continue;
             }
 
@@ -314,6 +822,9 @@ impl<'doc> ParseDirective<&'doc Field> for CodeGenPass<'doc> {
             ownership,
             deprecated,
             infallible,
+            trail,
+            executor,
+            arguments_struct,
         }
     }
 }
@@ -366,30 +877,163 @@ where
     }
 }
 
-macro_rules! supports_no_directives {
+macro_rules! supports_type_attributes {
     ($ty:ty) => {
         impl<'doc> ParseDirective<&'doc $ty> for CodeGenPass<'doc> {
-            type Output = ();
+            type Output = TypeAttributes;
 
             fn parse_directives(&mut self, input: &'doc $ty) -> Self::Output {
-                for directive in &input.directives {
-                    self.emit_non_fatal_error(
-                        directive.position,
-                        ErrorKind::UnknownDirective {
-                            suggestions: vec![],
-                        },
-                    );
+                let mut attributes = TypeAttributes::default();
+
+                for dir in &input.directives {
+                    match JuniperDirective::<(Derive, Attributes)>::from_directive(dir) {
+                        Ok(x) => {
+                            attributes.derives.extend(x.args.0.paths);
+                            attributes.attributes.extend(x.args.1.attributes);
+                        }
+                        Err(err) => {
+                            self.emit_non_fatal_error(dir.position, err);
+                        }
+                    }
                 }
+
+                attributes
             }
         }
     };
 }
 
-supports_no_directives!(SchemaDefinition);
-supports_no_directives!(ScalarType);
-supports_no_directives!(ObjectType);
-supports_no_directives!(InterfaceType);
-supports_no_directives!(UnionType);
-supports_no_directives!(EnumType);
-supports_no_directives!(InputObjectType);
-supports_no_directives!(InputValue);
+impl<'doc> ParseDirective<&'doc SchemaDefinition> for CodeGenPass<'doc> {
+    type Output = FieldDefaults;
+
+    fn parse_directives(&mut self, input: &'doc SchemaDefinition) -> Self::Output {
+        let mut defaults = FieldDefaults::default();
+
+        for dir in &input.directives {
+            match JuniperDirective::<FieldDefaultsArgs>::from_directive(dir) {
+                Ok(x) => {
+                    defaults = FieldDefaults::from_args(x.args).or(defaults);
+                }
+                Err(err) => {
+                    self.emit_non_fatal_error(dir.position, err);
+                }
+            }
+        }
+
+        defaults
+    }
+}
+
+supports_type_attributes!(ScalarType);
+
+impl<'doc> ParseDirective<&'doc ObjectType> for CodeGenPass<'doc> {
+    type Output = FieldDefaults;
+
+    fn parse_directives(&mut self, input: &'doc ObjectType) -> Self::Output {
+        let mut defaults = FieldDefaults::default();
+
+        for dir in &input.directives {
+            let result = if dir.name == "sql" {
+                SqlTable::from_directive(dir).map(|_| ())
+            } else {
+                JuniperDirective::<FieldDefaultsArgs>::from_directive(dir).map(|x| {
+                    defaults = FieldDefaults::from_args(x.args).or(defaults);
+                })
+            };
+
+            if let Err(err) = result {
+                self.emit_non_fatal_error(dir.position, err);
+            }
+        }
+
+        defaults
+    }
+}
+
+impl<'doc> ParseDirective<&'doc InterfaceType> for CodeGenPass<'doc> {
+    type Output = (TypeAttributes, FieldDefaults);
+
+    fn parse_directives(&mut self, input: &'doc InterfaceType) -> Self::Output {
+        let mut attributes = TypeAttributes::default();
+        let mut defaults = FieldDefaults::default();
+
+        for dir in &input.directives {
+            match JuniperDirective::<(
+                Derive,
+                Attributes,
+                Option<Ownership>,
+                Option<Infallible>,
+                Option<Trail>,
+                Option<Executor>,
+                Option<ArgumentsStruct>,
+            )>::from_directive(dir)
+            {
+                Ok(x) => {
+                    let (derive, attrs, ownership, infallible, trail, executor, arguments_struct) =
+                        x.args;
+                    attributes.derives.extend(derive.paths);
+                    attributes.attributes.extend(attrs.attributes);
+                    defaults = FieldDefaults::from_args((
+                        ownership,
+                        infallible,
+                        trail,
+                        executor,
+                        arguments_struct,
+                    ))
+                    .or(defaults);
+                }
+                Err(err) => {
+                    self.emit_non_fatal_error(dir.position, err);
+                }
+            }
+        }
+
+        (attributes, defaults)
+    }
+}
+
+supports_type_attributes!(UnionType);
+supports_type_attributes!(EnumType);
+supports_type_attributes!(InputObjectType);
+
+/// Arguments are owned unless they have `@juniper(ownership: "borrowed")`.
+impl<'doc> ParseDirective<&'doc InputValue> for CodeGenPass<'doc> {
+    type Output = Ownership;
+
+    fn parse_directives(&mut self, input: &'doc InputValue) -> Self::Output {
+        let mut ownership = Ownership::Owned;
+
+        for dir in &input.directives {
+            if dir.name != "juniper" {
+                self.emit_non_fatal_error(
+                    dir.position,
+                    ErrorKind::UnknownDirective {
+                        suggestions: vec![],
+                    },
+                );
+                continue;
+            }
+
+            match JuniperDirective::<Option<Ownership>>::from_directive(dir) {
+                Ok(x) => match x.args {
+                    Some(arg @ Ownership::Owned) | Some(arg @ Ownership::Borrowed) => {
+                        ownership = arg;
+                    }
+                    Some(other) => self.emit_non_fatal_error(
+                        dir.position,
+                        ErrorKind::OwnershipNotSupportedForType {
+                            ownership: other.name(),
+                            supported: "fields",
+                        },
+                    ),
+                    None => {}
+                },
+                Err(err) => {
+                    self.emit_non_fatal_error(dir.position, err);
+                }
+            }
+        }
+
+        ownership
+    }
+}
@@ -1,3 +1,4 @@
+use crate::diagnostics::{Diagnostic, Severity};
 use colored::*;
 use graphql_parser::{query::Value, Pos};
 use std::fmt::{self, Write};
@@ -9,6 +10,31 @@ pub struct Error<'doc> {
     pub(super) raw_schema: &'doc str,
 }
 
+impl<'a> Error<'a> {
+    pub fn to_diagnostic(&self, file: Option<&str>) -> Diagnostic {
+        let suggestions = match &self.kind {
+            ErrorKind::UnknownDirective { suggestions } => suggestions.clone(),
+            _ => Vec::new(),
+        };
+
+        Diagnostic {
+            file: file.map(|file| file.to_string()),
+            line: self.pos.line,
+            column: self.pos.column,
+            severity: Severity::Error,
+            code: self.kind.code(),
+            message: self.kind.description(),
+            notes: self
+                .kind
+                .notes()
+                .map(|notes| notes.trim_end().to_string())
+                .into_iter()
+                .collect(),
+            suggestions,
+        }
+    }
+}
+
 impl<'a> fmt::Display for Error<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // TODO: Handle lines that are really long and cause wrapping (screenshot on desktop)
@@ -135,7 +161,7 @@ impl<'doc> fmt::Display for Ownership<'doc> {
         match self {
             Self::InvalidValue(name) => write!(
                 f,
-                "Invalid value. Expected `owned`, `borrowed`, or `as_ref`, got `{}`",
+                "Invalid value. Expected `owned`, `borrowed`, `borrowed_from_context`, `as_ref`, `str`, `slice`, `arc`, `cow`, or `iter`, got `{}`",
                 name
             ),
         }
@@ -145,12 +171,94 @@ impl<'doc> fmt::Display for Ownership<'doc> {
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub enum Juniper<'doc> {
     InvalidName(&'doc str),
+    UnparsableDerive(&'doc str),
+    UnparsableAttribute(&'doc str),
 }
 
 impl<'doc> fmt::Display for Juniper<'doc> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             Self::InvalidName(name) => write!(f, "Invalid name `{}`. Expected `juniper`", name),
+            Self::UnparsableDerive(derive) => write!(
+                f,
+                "Invalid derive `{}`. Expected a path such as `serde::Serialize`",
+                derive
+            ),
+            Self::UnparsableAttribute(attribute) => write!(
+                f,
+                "Invalid attribute `{}`. Expected an attribute such as `#[serde(rename_all = \"camelCase\")]`",
+                attribute
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Cost<'doc> {
+    InvalidName(&'doc str),
+    InvalidKey(&'doc str),
+    NegativeValue(i64),
+    UnknownMultiplier(String),
+}
+
+impl<'doc> fmt::Display for Cost<'doc> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(f, "Invalid name `{}`. Expected `cost`", name),
+            Self::InvalidKey(key) => write!(
+                f,
+                "Invalid key. Expected `value` or `multipliers`, got `{}`",
+                key
+            ),
+            Self::NegativeValue(value) => {
+                write!(
+                    f,
+                    "Invalid value. Costs cannot be negative, got `{}`",
+                    value
+                )
+            }
+            Self::UnknownMultiplier(name) => write!(
+                f,
+                "Invalid multiplier `{}`. Multipliers must be `Int` arguments of the field",
+                name
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Sql<'doc> {
+    InvalidName(&'doc str),
+    InvalidTypeKey(&'doc str),
+    InvalidFieldKey(&'doc str),
+    MissingTable,
+    JoinWithoutTable(&'doc str),
+    JoinWithoutAliases,
+}
+
+impl<'doc> fmt::Display for Sql<'doc> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(f, "Invalid name `{}`. Expected `sql`", name),
+            Self::InvalidTypeKey(key) => {
+                write!(f, "Invalid key. Expected `table` on types, got `{}`", key)
+            }
+            Self::InvalidFieldKey(key) => write!(
+                f,
+                "Invalid key. Expected `column` or `join` on fields, got `{}`",
+                key
+            ),
+            Self::MissingTable => write!(f, "Missing key `table`"),
+            Self::JoinWithoutTable(name) => write!(
+                f,
+                "Cannot join `{}` because it doesn't have a `@sql(table: ...)` directive",
+                name
+            ),
+            Self::JoinWithoutAliases => write!(
+                f,
+                "Join conditions must refer to the joined table as `{{alias}}` and to the parent \
+                 table as `{{parent}}`"
+            ),
         }
     }
 }
@@ -160,6 +268,8 @@ pub enum UnsupportedDirectiveKind<'doc> {
     Deprecation(Deprecation<'doc>),
     Ownership(Ownership<'doc>),
     Juniper(Juniper<'doc>),
+    Cost(Cost<'doc>),
+    Sql(Sql<'doc>),
     InvalidType {
         actual: ValueType,
         expected: ValueType,
@@ -172,6 +282,8 @@ impl<'doc> fmt::Display for UnsupportedDirectiveKind<'doc> {
             Self::Deprecation(inner) => write!(f, "{}", inner),
             Self::Ownership(inner) => write!(f, "{}", inner),
             Self::Juniper(inner) => write!(f, "{}", inner),
+            Self::Cost(inner) => write!(f, "{}", inner),
+            Self::Sql(inner) => write!(f, "{}", inner),
             Self::InvalidType { expected, actual } => {
                 write!(f, "Invalid type. Expected `{}`, got `{}`", expected, actual)
             }
@@ -186,6 +298,7 @@ pub enum ErrorKind<'doc> {
     UuidScalarNotDefined,
     UrlScalarNotDefined,
     SpecialCaseScalarWithDescription,
+    SpecialCaseScalarWithAttributes,
     UnsupportedDirective(UnsupportedDirectiveKind<'doc>),
     UnknownDirective {
         suggestions: Vec<String>,
@@ -194,23 +307,51 @@ pub enum ErrorKind<'doc> {
     NonnullableFieldWithDefaultValue,
     SubscriptionsNotSupported,
     TypeExtensionNotSupported,
-    UnionFieldTypeMismatch {
-        union_name: &'doc str,
-        field_name: &'doc str,
-        type_a: &'doc str,
-        field_type_a: &'doc str,
-        type_b: &'doc str,
-        field_type_b: &'doc str,
-    },
     VariableDefaultValue,
     InputTypeFieldWithDefaultValue,
     AsRefOwnershipForNamedType,
+    OwnershipNotSupportedForType {
+        ownership: &'static str,
+        supported: &'static str,
+    },
     FieldNameInSnakeCase,
     UppercaseUuidScalar,
     InvalidJuniperDirective(String, Option<String>),
+    FieldEnumNameClash(String),
 }
 
 impl<'doc> ErrorKind<'doc> {
+    /// A code that uniquely identifies the kind of error.
+    ///
+    /// These are part of the machine readable output so they must never change. New variants get
+    /// the next unused number.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::DateTimeScalarNotDefined => "JFS0001",
+            ErrorKind::DateScalarNotDefined => "JFS0002",
+            ErrorKind::UuidScalarNotDefined => "JFS0003",
+            ErrorKind::UrlScalarNotDefined => "JFS0004",
+            ErrorKind::SpecialCaseScalarWithDescription => "JFS0005",
+            ErrorKind::UnsupportedDirective(_) => "JFS0006",
+            ErrorKind::UnknownDirective { .. } => "JFS0007",
+            ErrorKind::NoQueryType => "JFS0008",
+            ErrorKind::NonnullableFieldWithDefaultValue => "JFS0009",
+            ErrorKind::SubscriptionsNotSupported => "JFS0010",
+            ErrorKind::TypeExtensionNotSupported => "JFS0011",
+            // JFS0012 was used for union members with conflicting field types, which are allowed
+            // now.
+            ErrorKind::VariableDefaultValue => "JFS0013",
+            ErrorKind::InputTypeFieldWithDefaultValue => "JFS0014",
+            ErrorKind::AsRefOwnershipForNamedType => "JFS0015",
+            ErrorKind::FieldNameInSnakeCase => "JFS0016",
+            ErrorKind::UppercaseUuidScalar => "JFS0017",
+            ErrorKind::InvalidJuniperDirective(_, _) => "JFS0018",
+            ErrorKind::SpecialCaseScalarWithAttributes => "JFS0019",
+            ErrorKind::OwnershipNotSupportedForType { .. } => "JFS0020",
+            ErrorKind::FieldEnumNameClash(_) => "JFS0021",
+        }
+    }
+
     fn description(&self) -> String {
         match self {
             ErrorKind::DateTimeScalarNotDefined => {
@@ -228,6 +369,9 @@ impl<'doc> ErrorKind<'doc> {
             ErrorKind::SpecialCaseScalarWithDescription => {
                 "Special case scalars don't support having descriptions because the Rust types are defined in external crates".to_string()
             }
+            ErrorKind::SpecialCaseScalarWithAttributes => {
+                "Special case scalars don't support derives or attributes because the Rust types are defined in external crates".to_string()
+            }
             ErrorKind::UnsupportedDirective(_) => {
                 "Unsupported directive.".to_string()
             }
@@ -245,10 +389,6 @@ impl<'doc> ErrorKind<'doc> {
                 "Default arguments cannot refer to variables".to_string()
             }
             ErrorKind::TypeExtensionNotSupported => "Type extentions are not supported".to_string(),
-            ErrorKind::UnionFieldTypeMismatch { union_name, .. } => format!(
-                "Error while generating `QueryTrail` for union `{}`",
-                union_name
-            ),
             ErrorKind::InputTypeFieldWithDefaultValue => {
                 "Default values for input type fields are not supported".to_string()
             }
@@ -256,6 +396,12 @@ impl<'doc> ErrorKind<'doc> {
                 "@juniper(ownership: \"as_ref\") is only supported on `Option` and `Vec` types"
                     .to_string()
             }
+            ErrorKind::OwnershipNotSupportedForType { ownership, supported } => {
+                format!(
+                    "@juniper(ownership: \"{}\") is only supported on {}",
+                    ownership, supported
+                )
+            }
             ErrorKind::FieldNameInSnakeCase => {
                 "Field names must be camelCase, not snake_case".to_string()
             }
@@ -265,6 +411,9 @@ impl<'doc> ErrorKind<'doc> {
             ErrorKind::InvalidJuniperDirective(msg, _) => {
                 msg.clone()
             }
+            ErrorKind::FieldEnumNameClash(name) => {
+                format!("The `{}` enum generated for the fields of this type has the same name as a type in the schema", name)
+            }
         }
     }
 
@@ -275,17 +424,6 @@ impl<'doc> ErrorKind<'doc> {
                 "Subscriptions are currently not supported by Juniper so we're unsure when\nor if we'll support them"
                     .to_string(),
             ),
-            ErrorKind::UnionFieldTypeMismatch { union_name, field_name, type_a, type_b, field_type_a, field_type_b } => {
-                let mut f = String::new();
-
-                writeln!(f, "`{}.{}` and `{}.{}` are not the same type", type_a, field_name, type_b, field_name);
-                writeln!(f, "    `{}.{}` is of type `{}`", type_a, field_name, field_type_a);
-                writeln!(f, "    `{}.{}` is of type `{}`", type_b, field_name, field_type_b);
-                writeln!(f, "That makes it impossible to generate code for the method `QueryTrail<_, {}, _>::{}()`", union_name, field_name);
-                writeln!(f, "It would have to return `{}` if `{}` is `{},` but `{}` if it is a `{}`", field_type_a, union_name, type_a, field_type_b, type_b);
-
-                Some(f)
-            }
             ErrorKind::DateTimeScalarNotDefined => {
                 Some("Insert `scalar DateTimeUtc` into your schema".to_string())
             }
@@ -322,6 +460,9 @@ impl<'doc> ErrorKind<'doc> {
             ErrorKind::InvalidJuniperDirective(_, notes) => {
                 notes.to_owned()
             }
+            ErrorKind::FieldEnumNameClash(_) => {
+                Some("Rename the type or change the name of the enum with the `field_enum` config".to_string())
+            }
             _ => None,
         }
     }
@@ -367,4 +508,24 @@ mod test {
         assert_eq!(2, number_of_digits(10));
         assert_eq!(7, number_of_digits(1_000_000));
     }
+
+    #[test]
+    fn test_to_diagnostic() {
+        let error = Error {
+            pos: Pos { line: 2, column: 3 },
+            kind: ErrorKind::UnknownDirective {
+                suggestions: vec!["juniper".to_string()],
+            },
+            raw_schema: "",
+        };
+
+        let diagnostic = error.to_diagnostic(Some("schema.graphql"));
+
+        assert_eq!(diagnostic.file, Some("schema.graphql".to_string()));
+        assert_eq!((diagnostic.line, diagnostic.column), (2, 3));
+        assert_eq!(diagnostic.code, "JFS0007");
+        assert_eq!(diagnostic.message, "Unknown directive");
+        assert_eq!(diagnostic.notes, vec!["Did you mean: juniper?".to_string()]);
+        assert_eq!(diagnostic.suggestions, vec!["juniper".to_string()]);
+    }
 }
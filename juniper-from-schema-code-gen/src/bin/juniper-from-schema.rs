@@ -0,0 +1,248 @@
+//! Command line tools for GraphQL schemas used with juniper-from-schema.
+
+#![deny(unused_imports, dead_code, unused_variables, unused_must_use)]
+
+use juniper_from_schema_code_gen::{
+    diagnostics,
+    docs::{self, DocsFormat},
+    formatting::{self, FormatOptions},
+    introspection, schema_diff, Naming, SchemaError,
+};
+use std::{env, fs, process};
+
+const USAGE: &str = "\
+Usage: juniper-from-schema <command> [options]
+
+Commands:
+    check [--json] [--naming <config>=<pattern>]... <schema>...
+        Check schemas for errors without compiling anything. With --json the errors are printed as
+        a JSON array. Use --naming for each naming config of graphql_schema_from_file!, such as
+        --naming field_enum={type}SelectedField. Exits with status 1 if there are errors
+
+    docs <schema> [--html] [-o <file>]
+        Print Markdown reference documentation for a schema, or write it to <file>. With --html
+        the documentation is generated as HTML instead
+
+    introspection <schema> [-o <file>]
+        Print the introspection JSON for a schema, or write it to <file>
+
+    diff <old schema> <new schema>
+        Print the changes between two schemas and whether they are breaking, dangerous or safe.
+        Exits with status 1 if there are breaking changes
+
+    fmt [--check] [--sort] [--strip-comments] <schema>...
+        Format schema files in place. With --check the files are not changed and the command exits
+        with status 1 if any of them are not formatted. With --sort types and fields are sorted by
+        name. Formatting removes comments, so files with comments are left untouched and the
+        command exits with status 1 unless --strip-comments is given
+";
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+
+    let result = match args.first().map(String::as_str) {
+        Some("check") => check_command(&args[1..]),
+        Some("docs") => docs_command(&args[1..]),
+        Some("introspection") => introspection_command(&args[1..]),
+        Some("diff") => diff_command(&args[1..]),
+        Some("fmt") => fmt_command(&args[1..]),
+        Some("help") | Some("-h") | Some("--help") => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        _ => usage_error(),
+    };
+
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn check_command(args: &[String]) -> Result<(), SchemaError> {
+    let mut json = false;
+    let mut naming = Naming::default();
+    let mut paths = Vec::new();
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--naming" => {
+                let config = args.next().unwrap_or_else(usage_error);
+                let mut parts = config.splitn(2, '=');
+                let name = parts.next().unwrap_or_default();
+                let pattern = parts.next().unwrap_or_else(usage_error);
+                naming
+                    .set_pattern(name, pattern)
+                    .map_err(SchemaError::Invalid)?;
+            }
+            path => paths.push(path),
+        }
+    }
+
+    if paths.is_empty() {
+        usage_error()
+    }
+
+    let mut all_diagnostics = Vec::new();
+    for path in paths {
+        let schema = fs::read_to_string(path)?;
+        all_diagnostics.extend(diagnostics::check_schema(&schema, Some(path), &naming));
+    }
+
+    if json {
+        println!("{}", diagnostics::diagnostics_to_json(&all_diagnostics));
+    } else {
+        for diagnostic in &all_diagnostics {
+            println!("{}", diagnostic);
+        }
+    }
+
+    if !all_diagnostics.is_empty() {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn docs_command(args: &[String]) -> Result<(), SchemaError> {
+    let mut schema_path = None;
+    let mut out_path = None;
+    let mut format = DocsFormat::Markdown;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => out_path = Some(args.next().unwrap_or_else(usage_error)),
+            "--html" => format = DocsFormat::Html,
+            path if schema_path.is_none() => schema_path = Some(path),
+            _ => usage_error(),
+        }
+    }
+
+    let schema_path = schema_path.unwrap_or_else(usage_error);
+    let schema = fs::read_to_string(schema_path)?;
+    let docs = docs::generate_docs(&schema, format)?;
+
+    match out_path {
+        Some(out_path) => fs::write(out_path, docs)?,
+        None => print!("{}", docs),
+    }
+
+    Ok(())
+}
+
+fn introspection_command(args: &[String]) -> Result<(), SchemaError> {
+    let mut schema_path = None;
+    let mut out_path = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => out_path = Some(args.next().unwrap_or_else(usage_error)),
+            path if schema_path.is_none() => schema_path = Some(path),
+            _ => usage_error(),
+        }
+    }
+
+    let schema_path = schema_path.unwrap_or_else(usage_error);
+    let schema = fs::read_to_string(schema_path)?;
+    let json = introspection::introspection_json(&schema)?;
+
+    match out_path {
+        Some(out_path) => fs::write(out_path, json)?,
+        None => print!("{}", json),
+    }
+
+    Ok(())
+}
+
+fn diff_command(args: &[String]) -> Result<(), SchemaError> {
+    let (old_path, new_path) = match args {
+        [old_path, new_path] => (old_path, new_path),
+        _ => usage_error(),
+    };
+
+    let old = fs::read_to_string(old_path)?;
+    let new = fs::read_to_string(new_path)?;
+    let changes = schema_diff::diff_schemas(&old, &new)?;
+
+    if changes.is_empty() {
+        println!("No changes");
+    }
+
+    for change in &changes {
+        println!("{}", change);
+    }
+
+    if changes.iter().any(|change| change.is_breaking()) {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn fmt_command(args: &[String]) -> Result<(), SchemaError> {
+    let mut check = false;
+    let mut strip_comments = false;
+    let mut options = FormatOptions::default();
+    let mut paths = Vec::new();
+
+    for arg in args {
+        match arg.as_str() {
+            "--check" => check = true,
+            "--sort" => options.sort = true,
+            "--strip-comments" => strip_comments = true,
+            flag if flag.starts_with('-') => {
+                eprintln!("Unknown option {}", flag);
+                usage_error()
+            }
+            path => paths.push(path),
+        }
+    }
+
+    if paths.is_empty() {
+        usage_error()
+    }
+
+    let mut unformatted = false;
+
+    for path in paths {
+        let schema = fs::read_to_string(path)?;
+
+        if !strip_comments {
+            if let Some(line) = formatting::first_comment_line(&schema) {
+                eprintln!(
+                    "{} has a comment on line {} which formatting would remove. \
+                     Remove the comments or pass --strip-comments",
+                    path, line,
+                );
+                unformatted = true;
+                continue;
+            }
+        }
+
+        let formatted = formatting::format_schema(&schema, &options)?;
+
+        if formatted != schema {
+            if check {
+                println!("{} is not formatted", path);
+                unformatted = true;
+            } else {
+                fs::write(path, formatted)?;
+            }
+        }
+    }
+
+    if unformatted {
+        process::exit(1);
+    }
+
+    Ok(())
+}
+
+fn usage_error<T>() -> T {
+    eprint!("{}", USAGE);
+    process::exit(2)
+}
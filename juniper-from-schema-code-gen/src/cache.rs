@@ -0,0 +1,299 @@
+//! Caching of the code generated by `graphql_schema_from_file!`.
+//!
+//! Each invocation gets a file in the cache directory, named after the schema path and macro
+//! configuration. The file starts with a key computed from everything that affects the generated
+//! code, and the cached code is only used if the key matches. That way changing the schema
+//! overwrites the old entry rather than adding a new one.
+
+use proc_macro2::TokenStream;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+const CACHE_ENV_VAR: &str = "JUNIPER_FROM_SCHEMA_CACHE";
+const CACHE_DIR_ENV_VAR: &str = "JUNIPER_FROM_SCHEMA_CACHE_DIR";
+
+pub struct Cache {
+    dir: Option<PathBuf>,
+}
+
+impl Cache {
+    /// The cache used for macro invocations. Disabled if `JUNIPER_FROM_SCHEMA_CACHE` is `0` or if
+    /// there is no cache directory.
+    pub fn from_env() -> Self {
+        let disabled = matches!(env::var(CACHE_ENV_VAR).as_deref(), Ok("0"));
+        let dir = if disabled || crate::debugging_enabled() {
+            None
+        } else {
+            cache_dir(|name| env::var(name).ok())
+        };
+        Cache { dir }
+    }
+
+    #[cfg(test)]
+    fn in_dir(dir: PathBuf) -> Self {
+        Cache { dir: Some(dir) }
+    }
+
+    /// Get the cached code for a schema, or generate and cache it.
+    pub fn get_or_insert_with(
+        &self,
+        entry: &CacheEntry<'_>,
+        gen: impl FnOnce() -> TokenStream,
+    ) -> TokenStream {
+        let dir = match &self.dir {
+            Some(dir) => dir,
+            None => return gen(),
+        };
+
+        let path = dir.join(entry.file_name());
+        let key = entry.key();
+
+        if let Some(tokens) = read_entry(&path, &key) {
+            return tokens;
+        }
+
+        let tokens = gen();
+        // Failing to write the cache shouldn't fail the build, we'll just generate the code again
+        // next time.
+        if let Err(err) = write_entry(dir, &path, &key, &tokens) {
+            eprintln!(
+                "warning: juniper-from-schema failed to write {}: {}. Set {}=0 to disable the cache",
+                path.display(),
+                err,
+                CACHE_ENV_VAR,
+            );
+        }
+        tokens
+    }
+}
+
+/// Everything that affects the code generated for a schema.
+pub struct CacheEntry<'a> {
+    pub schema_path: &'a Path,
+    pub schema: &'a str,
+    /// The input to the macro, which contains the configs.
+    pub config: &'a str,
+}
+
+impl CacheEntry<'_> {
+    fn file_name(&self) -> String {
+        let invocation = format!("{}\n{}", self.schema_path.display(), self.config);
+        format!("{:016x}.rs", fnv1a(invocation.as_bytes()))
+    }
+
+    fn key(&self) -> String {
+        let schema_hash = fnv1a(self.schema.as_bytes());
+        format!(
+            "// {} {} {:016x}",
+            env!("CARGO_PKG_VERSION"),
+            env!("JUNIPER_FROM_SCHEMA_CODE_GEN_FINGERPRINT"),
+            schema_hash,
+        )
+    }
+}
+
+fn read_entry(path: &Path, key: &str) -> Option<TokenStream> {
+    let contents = fs::read_to_string(path).ok()?;
+    let mut parts = contents.splitn(2, '\n');
+    if parts.next()? != key {
+        return None;
+    }
+    parts.next()?.parse().ok()
+}
+
+fn write_entry(dir: &Path, path: &Path, key: &str, tokens: &TokenStream) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    // Write to a temporary file first, so other compilations running at the same time never see
+    // a partially written entry.
+    let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    fs::write(&tmp_path, format!("{}\n{}", key, tokens))?;
+    fs::rename(&tmp_path, path)
+}
+
+/// `JUNIPER_FROM_SCHEMA_CACHE_DIR`, or `juniper-from-schema` inside `CARGO_TARGET_DIR`, looking
+/// up variables with `var`.
+///
+/// Cargo doesn't tell procedural macros where the target directory is otherwise. Guessing it from
+/// the manifest directory would write into the sources of dependencies, such as the registry or
+/// vendored crates, so without either variable the cache is disabled. Relative paths are ignored
+/// for the same reason, since they're resolved against the directory of the crate being compiled.
+fn cache_dir(var: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    let absolute = |name| var(name).map(PathBuf::from).filter(|dir| dir.is_absolute());
+
+    if let Some(dir) = var(CACHE_DIR_ENV_VAR) {
+        return absolute(CACHE_DIR_ENV_VAR).or_else(|| {
+            eprintln!(
+                "warning: juniper-from-schema ignores {}={} since it isn't an absolute path",
+                CACHE_DIR_ENV_VAR, dir,
+            );
+            None
+        });
+    }
+
+    absolute("CARGO_TARGET_DIR").map(|dir| dir.join("juniper-from-schema"))
+}
+
+// 64 bit FNV-1a. Used rather than `DefaultHasher` since that isn't guaranteed to be stable across
+// versions of Rust, and the cache is shared between them.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use quote::quote;
+    use std::cell::Cell;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "juniper-from-schema-cache-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_reuses_generated_code_until_the_schema_changes() {
+        let dir = temp_dir("reuse");
+        let cache = Cache::in_dir(dir.clone());
+        let calls = Cell::new(0);
+
+        let gen = |schema: &str, config: &str| {
+            let entry = CacheEntry {
+                schema_path: Path::new("schema.graphql"),
+                schema,
+                config,
+            };
+            cache.get_or_insert_with(&entry, || {
+                calls.set(calls.get() + 1);
+                quote! { pub struct Generated; }
+            })
+        };
+
+        let first = gen("type Query { a: Int! }", "");
+        let second = gen("type Query { a: Int! }", "");
+        assert_eq!(calls.get(), 1);
+        assert_eq!(first.to_string(), second.to_string());
+
+        gen("type Query { b: Int! }", "");
+        assert_eq!(calls.get(), 2);
+
+        // The new schema replaced the old entry.
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        gen("type Query { b: Int! }", "context_type: MyContext");
+        assert_eq!(calls.get(), 3);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_cached_code_is_the_same_as_generated_code() {
+        let dir = temp_dir("roundtrip");
+        let cache = Cache::in_dir(dir.clone());
+        let schema = include_str!("../../juniper-from-schema/tests/schemas/complex_schema.graphql");
+        let entry = CacheEntry {
+            schema_path: Path::new("complex_schema.graphql"),
+            schema,
+            config: "",
+        };
+        let gen = || {
+            crate::parse_and_gen_schema(
+                schema,
+                None,
+                crate::default_error_type(),
+                crate::default_context_type(),
+                crate::Naming::default(),
+                crate::AttributesConfig::default(),
+            )
+        };
+
+        let generated = cache.get_or_insert_with(&entry, gen);
+        let cached = cache.get_or_insert_with(&entry, || panic!("should be cached"));
+        assert_eq!(generated.to_string(), cached.to_string());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_disabled_cache_always_generates() {
+        let cache = Cache { dir: None };
+        let entry = CacheEntry {
+            schema_path: Path::new("schema.graphql"),
+            schema: "",
+            config: "",
+        };
+        let calls = Cell::new(0);
+
+        for _ in 0..2 {
+            cache.get_or_insert_with(&entry, || {
+                calls.set(calls.get() + 1);
+                quote! {}
+            });
+        }
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn test_failing_to_write_still_generates() {
+        // A file where the cache directory should be, so it can't be created.
+        let dir = temp_dir("unwritable");
+        fs::write(&dir, "").unwrap();
+        let cache = Cache::in_dir(dir.clone());
+        let entry = CacheEntry {
+            schema_path: Path::new("schema.graphql"),
+            schema: "",
+            config: "",
+        };
+
+        let tokens = cache.get_or_insert_with(&entry, || quote! { pub struct Generated; });
+        assert_eq!(tokens.to_string(), "pub struct Generated ;");
+
+        fs::remove_file(&dir).unwrap();
+    }
+
+    fn cache_dir_with(vars: &[(&str, &str)]) -> Option<PathBuf> {
+        cache_dir(|name| {
+            vars.iter()
+                .find(|(var, _)| *var == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_cache_dir() {
+        assert_eq!(cache_dir_with(&[]), None);
+        assert_eq!(
+            cache_dir_with(&[("CARGO_TARGET_DIR", "/tmp/target")]),
+            Some(PathBuf::from("/tmp/target/juniper-from-schema"))
+        );
+        assert_eq!(
+            cache_dir_with(&[
+                ("CARGO_TARGET_DIR", "/tmp/target"),
+                (CACHE_DIR_ENV_VAR, "/tmp/schema-cache"),
+            ]),
+            Some(PathBuf::from("/tmp/schema-cache"))
+        );
+    }
+
+    #[test]
+    fn test_cache_dir_ignores_relative_paths() {
+        assert_eq!(cache_dir_with(&[("CARGO_TARGET_DIR", "target")]), None);
+        assert_eq!(
+            cache_dir_with(&[
+                ("CARGO_TARGET_DIR", "/tmp/target"),
+                (CACHE_DIR_ENV_VAR, "schema-cache"),
+            ]),
+            None
+        );
+    }
+}
@@ -0,0 +1,275 @@
+//! Machine readable diagnostics for editors and CI annotations.
+//!
+//! Set the env var `JUNIPER_FROM_SCHEMA_DIAGNOSTICS` to `json` when compiling to make the
+//! procedural macros report errors as a JSON array instead of colored text:
+//!
+//! ```bash
+//! JUNIPER_FROM_SCHEMA_DIAGNOSTICS=json cargo build
+//! ```
+//!
+//! Schemas can also be checked without compiling anything using [`check_schema`] or
+//! `juniper-from-schema check --json schema.graphql`.
+//!
+//! Each diagnostic looks like this:
+//!
+//! ```json
+//! {
+//!   "file": "schema.graphql",
+//!   "line": 4,
+//!   "column": 3,
+//!   "severity": "error",
+//!   "code": "JFS0007",
+//!   "message": "Unknown directive",
+//!   "notes": ["Did you mean: juniper?"],
+//!   "suggestions": ["juniper"]
+//! }
+//! ```
+//!
+//! `file` is `null` for schemas written directly in Rust code with `graphql_schema!`. The codes are
+//! stable and unique per kind of error. Errors from the GraphQL parser use the code `JFS0000`.
+//!
+//! [`check_schema`]: fn.check_schema.html
+
+use crate::{
+    ast_pass::{ast_data_pass::AstData, CodeGenPass},
+    naming::Naming,
+    parse_input::{default_context_type, default_error_type},
+    type_attributes::AttributesConfig,
+};
+use graphql_parser::{parse_schema, schema::ParseError};
+use serde_json::{json, Value};
+use std::fmt;
+
+const DIAGNOSTICS_ENV_VAR: &str = "JUNIPER_FROM_SCHEMA_DIAGNOSTICS";
+
+const PARSE_ERROR_CODE: &str = "JFS0000";
+
+/// How severe a diagnostic is.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum Severity {
+    /// The schema cannot be used to generate code.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single problem found in a schema.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Diagnostic {
+    /// The schema file, if the schema was read from a file.
+    pub file: Option<String>,
+    /// One-based line number.
+    pub line: usize,
+    /// One-based column number.
+    pub column: usize,
+    /// How severe the problem is.
+    pub severity: Severity,
+    /// Stable code identifying the kind of problem, such as `JFS0007`.
+    pub code: &'static str,
+    /// Short description of the problem.
+    pub message: String,
+    /// Additional explanations.
+    pub notes: Vec<String>,
+    /// Replacements that might fix the problem, such as the names of similar directives.
+    pub suggestions: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Convert the diagnostic into JSON.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "file": self.file,
+            "line": self.line,
+            "column": self.column,
+            "severity": self.severity.to_string(),
+            "code": self.code,
+            "message": self.message,
+            "notes": self.notes,
+            "suggestions": self.suggestions,
+        })
+    }
+
+    fn from_parse_error(error: &ParseError, file: Option<&str>) -> Self {
+        let message = error.to_string();
+        let (line, column) = parse_error_position(&message).unwrap_or((1, 1));
+
+        Diagnostic {
+            file: file.map(|file| file.to_string()),
+            line,
+            column,
+            severity: Severity::Error,
+            code: PARSE_ERROR_CODE,
+            message,
+            notes: Vec::new(),
+            suggestions: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{}:{}:{}: {}[{}]: {}",
+            self.file.as_deref().unwrap_or("schema"),
+            self.line,
+            self.column,
+            self.severity,
+            self.code,
+            self.message,
+        )?;
+
+        for note in &self.notes {
+            for line in note.lines() {
+                writeln!(f, "    {}", line)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Check a schema for errors without generating any code.
+///
+/// Returns the same errors the procedural macros would report. `file` is only used to fill in
+/// [`Diagnostic::file`](struct.Diagnostic.html#structfield.file). `naming` should match the
+/// configs of the `graphql_schema_from_file!` invocation, since some errors, such as generated
+/// names clashing with schema types, depend on them.
+pub fn check_schema(schema: &str, file: Option<&str>, naming: &Naming) -> Vec<Diagnostic> {
+    let doc = match parse_schema(schema) {
+        Ok(doc) => doc,
+        Err(parse_error) => return vec![Diagnostic::from_parse_error(&parse_error, file)],
+    };
+
+    let ast_data = match AstData::new_from_schema_and_doc(schema, &doc) {
+        Ok(ast_data) => ast_data,
+        Err(errors) => {
+            return errors
+                .iter()
+                .map(|error| error.to_diagnostic(file))
+                .collect()
+        }
+    };
+
+    let output = CodeGenPass::new(
+        schema,
+        default_error_type(),
+        default_context_type(),
+        naming.clone(),
+        AttributesConfig::default(),
+        ast_data,
+    );
+
+    match output.gen_juniper_code(&doc) {
+        Ok(_) => Vec::new(),
+        Err(errors) => errors
+            .iter()
+            .map(|error| error.to_diagnostic(file))
+            .collect(),
+    }
+}
+
+/// Render diagnostics as a pretty printed JSON array.
+pub fn diagnostics_to_json(diagnostics: &[Diagnostic]) -> String {
+    let json = diagnostics
+        .iter()
+        .map(Diagnostic::to_json)
+        .collect::<Vec<_>>();
+    serde_json::to_string_pretty(&json).expect("Failed to serialize diagnostics")
+}
+
+pub(crate) fn json_diagnostics_enabled() -> bool {
+    if let Ok(val) = std::env::var(DIAGNOSTICS_ENV_VAR) {
+        if &val == "json" {
+            return true;
+        }
+    }
+
+    false
+}
+
+pub(crate) fn parse_error_to_json(error: &ParseError, file: Option<&str>) -> String {
+    diagnostics_to_json(&[Diagnostic::from_parse_error(error, file)])
+}
+
+// The parser doesn't expose the position of errors so we have to find it in the message, which
+// looks like "schema parse error: Parse error at 3:5\n...".
+fn parse_error_position(message: &str) -> Option<(usize, usize)> {
+    let rest = &message[message.find(" at ")? + " at ".len()..];
+    let position = rest.split_whitespace().next()?;
+
+    let mut parts = position.splitn(2, ':');
+    let line = parts.next()?.parse().ok()?;
+    let column = parts.next()?.parse().ok()?;
+    Some((line, column))
+}
+
+#[cfg(test)]
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_valid_schema_has_no_diagnostics() {
+        let schema = "schema { query: Query } type Query { id: ID! }";
+        assert_eq!(check_schema(schema, None, &Naming::default()), vec![]);
+    }
+
+    #[test]
+    fn test_schema_errors() {
+        let schema = "schema { query: Query }\ntype Query {\n  id: ID! @foo\n}";
+
+        let diagnostics = check_schema(schema, Some("schema.graphql"), &Naming::default());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].to_json(),
+            json!({
+                "file": "schema.graphql",
+                "line": 3,
+                "column": 11,
+                "severity": "error",
+                "code": "JFS0007",
+                "message": "Unknown directive",
+                "notes": [],
+                "suggestions": [],
+            })
+        );
+    }
+
+    #[test]
+    fn test_uses_configured_naming() {
+        let schema =
+            "schema { query: Query } type Query { role: QueryField! } enum QueryField { A }";
+
+        let diagnostics = check_schema(schema, None, &Naming::default());
+        assert_eq!(
+            diagnostics.iter().map(|d| d.code).collect::<Vec<_>>(),
+            vec!["JFS0021"]
+        );
+
+        let mut naming = Naming::default();
+        naming
+            .set_pattern("field_enum", "{type}SelectedField")
+            .unwrap();
+        assert_eq!(check_schema(schema, None, &naming), vec![]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let schema = "type Query {\n  id: \n}";
+
+        let diagnostics = check_schema(schema, None, &Naming::default());
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, PARSE_ERROR_CODE);
+        assert_eq!((diagnostics[0].line, diagnostics[0].column), (3, 1));
+        assert_eq!(diagnostics[0].file, None);
+    }
+}
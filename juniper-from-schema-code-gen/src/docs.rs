@@ -0,0 +1,605 @@
+//! Generate reference documentation for a schema as Markdown or HTML.
+//!
+//! The output is a single page documenting every type, field, argument and enum value along with
+//! their descriptions and deprecation reasons. Types link to each other, interfaces list the types
+//! that implement them and object types list the unions they're part of.
+//!
+//! From a build script:
+//!
+//! ```ignore
+//! // build.rs
+//! use juniper_from_schema_code_gen::docs::{write_docs, DocsFormat};
+//!
+//! fn main() {
+//!     write_docs("schema.graphql", "schema.md", DocsFormat::Markdown).unwrap();
+//! }
+//! ```
+//!
+//! Or with the binary: `juniper-from-schema docs schema.graphql --html -o schema.html`.
+
+use crate::{
+    ast_pass::ast_data_pass::AstData,
+    introspection::{deprecation, type_definition_kind, type_definition_name, type_definitions},
+    render_errors, SchemaError,
+};
+use graphql_parser::{
+    parse_schema,
+    schema::{Definition, Document, EnumValue, Field, InputValue, Type, TypeDefinition},
+};
+use std::{collections::BTreeMap, fmt::Write, path::Path};
+
+/// The format of the generated documentation.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DocsFormat {
+    /// A Markdown document. Descriptions are included as-is since they're Markdown already.
+    Markdown,
+    /// A standalone HTML document.
+    Html,
+}
+
+/// Generate documentation for a schema.
+pub fn generate_docs(schema: &str, format: DocsFormat) -> Result<String, SchemaError> {
+    let doc = parse_schema(schema)?;
+    let ast_data = AstData::new_from_schema_and_doc(schema, &doc)
+        .map_err(|errors| SchemaError::Invalid(render_errors(errors)))?;
+
+    let docs = Docs::new(&doc, &ast_data);
+
+    let out = match format {
+        DocsFormat::Markdown => docs.render(Markdown::default()),
+        DocsFormat::Html => docs.render(Html::default()),
+    };
+    Ok(out)
+}
+
+/// Read a schema file and write its documentation to `out_path`.
+///
+/// Meant to be called from build scripts. It tells Cargo to re-run the build script if the schema
+/// changes.
+pub fn write_docs<P, Q>(schema_path: P, out_path: Q, format: DocsFormat) -> Result<(), SchemaError>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let schema_path = schema_path.as_ref();
+    println!("cargo:rerun-if-changed={}", schema_path.display());
+
+    let schema = std::fs::read_to_string(schema_path)?;
+    let docs = generate_docs(&schema, format)?;
+    std::fs::write(out_path, docs)?;
+    Ok(())
+}
+
+struct Docs<'doc> {
+    doc: &'doc Document,
+    ast_data: &'doc AstData<'doc>,
+    types: BTreeMap<&'doc str, &'doc TypeDefinition>,
+    unions_containing: BTreeMap<&'doc str, Vec<&'doc str>>,
+}
+
+impl<'doc> Docs<'doc> {
+    fn new(doc: &'doc Document, ast_data: &'doc AstData<'doc>) -> Self {
+        let types = type_definitions(doc)
+            .map(|type_def| (type_definition_name(type_def), type_def))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut unions_containing = BTreeMap::new();
+        for type_def in types.values() {
+            if let TypeDefinition::Union(union) = type_def {
+                for member in &union.types {
+                    unions_containing
+                        .entry(member.as_str())
+                        .or_insert_with(Vec::new)
+                        .push(union.name.as_str());
+                }
+            }
+        }
+
+        Self {
+            doc,
+            ast_data,
+            types,
+            unions_containing,
+        }
+    }
+
+    fn render<R: Renderer>(&self, mut out: R) -> String {
+        out.start("GraphQL schema");
+
+        out.heading(1, "GraphQL schema", None);
+
+        out.start_list();
+        for (operation, name) in self.root_types() {
+            let text = format!("{}: {}", out.text(operation), self.named_type(&out, name));
+            out.list_item(&text);
+            out.end_item();
+        }
+        out.end_list();
+
+        let sections = [
+            ("Objects", "OBJECT"),
+            ("Interfaces", "INTERFACE"),
+            ("Unions", "UNION"),
+            ("Enums", "ENUM"),
+            ("Input objects", "INPUT_OBJECT"),
+            ("Scalars", "SCALAR"),
+        ];
+
+        for (title, kind) in sections.iter() {
+            let types = self
+                .types
+                .values()
+                .filter(|type_def| type_definition_kind(type_def) == *kind)
+                .collect::<Vec<_>>();
+
+            if types.is_empty() {
+                continue;
+            }
+
+            out.heading(2, title, None);
+            for type_def in types {
+                self.type_definition(&mut out, type_def);
+            }
+        }
+
+        out.finish()
+    }
+
+    fn root_types(&self) -> Vec<(&'static str, &'doc str)> {
+        let schema_def = self.doc.definitions.iter().find_map(|def| match def {
+            Definition::SchemaDefinition(schema_def) => Some(schema_def),
+            _ => None,
+        });
+
+        let roots = match schema_def {
+            Some(schema_def) => vec![
+                ("Query", schema_def.query.as_deref()),
+                ("Mutation", schema_def.mutation.as_deref()),
+                ("Subscription", schema_def.subscription.as_deref()),
+            ],
+            None => vec![
+                ("Query", self.defined("Query")),
+                ("Mutation", self.defined("Mutation")),
+                ("Subscription", self.defined("Subscription")),
+            ],
+        };
+
+        roots
+            .into_iter()
+            .filter_map(|(operation, name)| name.map(|name| (operation, name)))
+            .collect()
+    }
+
+    fn defined(&self, name: &'static str) -> Option<&'doc str> {
+        self.types
+            .get(name)
+            .map(|type_def| type_definition_name(type_def))
+    }
+
+    fn type_definition<R: Renderer>(&self, out: &mut R, type_def: &'doc TypeDefinition) {
+        let name = type_definition_name(type_def);
+        out.heading(3, name, Some(&anchor(name)));
+
+        match type_def {
+            TypeDefinition::Scalar(scalar) => {
+                self.description(out, &scalar.description);
+            }
+            TypeDefinition::Object(obj) => {
+                self.description(out, &obj.description);
+                self.related_types(out, "Implements", &obj.implements_interfaces);
+                if let Some(unions) = self.unions_containing.get(name) {
+                    self.related_types(out, "Member of", unions);
+                }
+                self.fields(out, &obj.fields);
+            }
+            TypeDefinition::Interface(interface) => {
+                self.description(out, &interface.description);
+                if let Some(implementors) = self.ast_data.get_implementors_of_interface(name) {
+                    let mut implementors = implementors.clone();
+                    implementors.sort();
+                    self.related_types(out, "Implemented by", &implementors);
+                }
+                self.fields(out, &interface.fields);
+            }
+            TypeDefinition::Union(union) => {
+                self.description(out, &union.description);
+                self.related_types(out, "Possible types", &union.types);
+            }
+            TypeDefinition::Enum(enum_type) => {
+                self.description(out, &enum_type.description);
+                self.enum_values(out, &enum_type.values);
+            }
+            TypeDefinition::InputObject(input) => {
+                self.description(out, &input.description);
+                self.input_values(out, "Fields", &input.fields);
+            }
+        }
+    }
+
+    fn description<R: Renderer>(&self, out: &mut R, description: &Option<String>) {
+        if let Some(description) = description {
+            out.paragraph(&out.text(description.trim()));
+        }
+    }
+
+    fn related_types<R: Renderer, S: AsRef<str>>(&self, out: &mut R, label: &str, names: &[S]) {
+        if names.is_empty() {
+            return;
+        }
+
+        let links = names
+            .iter()
+            .map(|name| self.named_type(out, name.as_ref()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        out.paragraph(&format!("{}: {}", out.strong(label), links));
+    }
+
+    fn fields<R: Renderer>(&self, out: &mut R, fields: &[Field]) {
+        out.paragraph(&out.strong("Fields"));
+
+        out.start_list();
+        for field in fields {
+            let mut text = format!(
+                "{}: {}",
+                out.code(&field.name),
+                self.type_ref(out, &field.field_type)
+            );
+            append_description(out, &mut text, &field.description);
+            out.list_item(&text);
+
+            let (deprecated, reason) = deprecation(&field.directives);
+            if deprecated || !field.arguments.is_empty() {
+                out.start_list();
+                if deprecated {
+                    out.list_item(&deprecation_text(out, reason));
+                    out.end_item();
+                }
+                if !field.arguments.is_empty() {
+                    out.list_item(&out.text("Arguments:"));
+                    self.input_value_list(out, &field.arguments);
+                    out.end_item();
+                }
+                out.end_list();
+            }
+
+            out.end_item();
+        }
+        out.end_list();
+    }
+
+    fn input_values<R: Renderer>(&self, out: &mut R, label: &str, values: &[InputValue]) {
+        out.paragraph(&out.strong(label));
+        self.input_value_list(out, values);
+    }
+
+    fn input_value_list<R: Renderer>(&self, out: &mut R, values: &[InputValue]) {
+        out.start_list();
+        for value in values {
+            let mut text = format!(
+                "{}: {}",
+                out.code(&value.name),
+                self.type_ref(out, &value.value_type)
+            );
+            if let Some(default_value) = &value.default_value {
+                write!(
+                    text,
+                    " {}",
+                    out.text(&format!(
+                        "(default: {})",
+                        out.code(&default_value.to_string())
+                    ))
+                )
+                .unwrap();
+            }
+            append_description(out, &mut text, &value.description);
+            out.list_item(&text);
+            out.end_item();
+        }
+        out.end_list();
+    }
+
+    fn enum_values<R: Renderer>(&self, out: &mut R, values: &[EnumValue]) {
+        out.paragraph(&out.strong("Values"));
+
+        out.start_list();
+        for value in values {
+            let mut text = out.code(&value.name);
+            append_description(out, &mut text, &value.description);
+            out.list_item(&text);
+
+            let (deprecated, reason) = deprecation(&value.directives);
+            if deprecated {
+                out.start_list();
+                out.list_item(&deprecation_text(out, reason));
+                out.end_item();
+                out.end_list();
+            }
+
+            out.end_item();
+        }
+        out.end_list();
+    }
+
+    fn type_ref<R: Renderer>(&self, out: &R, ty: &Type) -> String {
+        match ty {
+            Type::NamedType(name) => self.named_type(out, name),
+            Type::ListType(inner) => out.list_type(&self.type_ref(out, inner)),
+            Type::NonNullType(inner) => format!("{}!", self.type_ref(out, inner)),
+        }
+    }
+
+    fn named_type<R: Renderer>(&self, out: &R, name: &str) -> String {
+        if self.types.contains_key(name) {
+            out.link(&out.code(name), &anchor(name))
+        } else {
+            out.code(name)
+        }
+    }
+}
+
+fn anchor(name: &str) -> String {
+    name.to_lowercase()
+}
+
+fn append_description<R: Renderer>(out: &R, text: &mut String, description: &Option<String>) {
+    if let Some(description) = description {
+        text.push_str(&out.line_break());
+        text.push_str(&out.text(description.trim()));
+    }
+}
+
+fn deprecation_text<R: Renderer>(out: &R, reason: Option<String>) -> String {
+    match reason {
+        Some(reason) => format!("{} {}", out.strong("Deprecated:"), out.text(&reason)),
+        None => out.strong("Deprecated"),
+    }
+}
+
+/// The markup specific parts of the output.
+///
+/// Block level methods write to the output. Inline methods return the markup so it can be
+/// combined into larger pieces of text.
+trait Renderer {
+    fn start(&mut self, title: &str);
+    fn finish(self) -> String;
+
+    fn heading(&mut self, level: usize, text: &str, anchor: Option<&str>);
+    fn paragraph(&mut self, text: &str);
+    fn start_list(&mut self);
+    fn list_item(&mut self, text: &str);
+    fn end_item(&mut self);
+    fn end_list(&mut self);
+
+    fn text(&self, text: &str) -> String;
+    fn code(&self, text: &str) -> String;
+    fn strong(&self, text: &str) -> String;
+    fn link(&self, text: &str, anchor: &str) -> String;
+    fn list_type(&self, inner: &str) -> String;
+    fn line_break(&self) -> String;
+}
+
+#[derive(Default)]
+struct Markdown {
+    out: String,
+    list_depth: usize,
+}
+
+impl Renderer for Markdown {
+    fn start(&mut self, _title: &str) {}
+
+    fn finish(self) -> String {
+        let mut out = self.out.trim_end().to_string();
+        out.push('\n');
+        out
+    }
+
+    // Markdown renderers generate the anchors from the heading text
+    fn heading(&mut self, level: usize, text: &str, _anchor: Option<&str>) {
+        writeln!(self.out, "{} {}\n", "#".repeat(level), text).unwrap();
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        writeln!(self.out, "{}\n", text).unwrap();
+    }
+
+    fn start_list(&mut self) {
+        self.list_depth += 1;
+    }
+
+    fn list_item(&mut self, text: &str) {
+        let indent = "  ".repeat(self.list_depth - 1);
+        let text = text.replace('\n', &format!("\n{}  ", indent));
+        writeln!(self.out, "{}- {}", indent, text).unwrap();
+    }
+
+    fn end_item(&mut self) {}
+
+    fn end_list(&mut self) {
+        self.list_depth -= 1;
+        if self.list_depth == 0 {
+            self.out.push('\n');
+        }
+    }
+
+    fn text(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn code(&self, text: &str) -> String {
+        format!("`{}`", text)
+    }
+
+    fn strong(&self, text: &str) -> String {
+        format!("**{}**", text)
+    }
+
+    fn link(&self, text: &str, anchor: &str) -> String {
+        format!("[{}](#{})", text, anchor)
+    }
+
+    fn list_type(&self, inner: &str) -> String {
+        format!("\\[{}\\]", inner)
+    }
+
+    fn line_break(&self) -> String {
+        "  \n".to_string()
+    }
+}
+
+#[derive(Default)]
+struct Html {
+    out: String,
+}
+
+impl Renderer for Html {
+    fn start(&mut self, title: &str) {
+        writeln!(self.out, "<!DOCTYPE html>").unwrap();
+        writeln!(self.out, "<html>").unwrap();
+        writeln!(self.out, "<head>").unwrap();
+        writeln!(self.out, "<meta charset=\"utf-8\">").unwrap();
+        writeln!(self.out, "<title>{}</title>", escape_html(title)).unwrap();
+        writeln!(self.out, "</head>").unwrap();
+        writeln!(self.out, "<body>").unwrap();
+    }
+
+    fn finish(mut self) -> String {
+        writeln!(self.out, "</body>").unwrap();
+        writeln!(self.out, "</html>").unwrap();
+        self.out
+    }
+
+    fn heading(&mut self, level: usize, text: &str, anchor: Option<&str>) {
+        match anchor {
+            Some(anchor) => writeln!(
+                self.out,
+                "<h{level} id=\"{anchor}\">{text}</h{level}>",
+                level = level,
+                anchor = escape_html(anchor),
+                text = escape_html(text)
+            ),
+            None => writeln!(
+                self.out,
+                "<h{level}>{text}</h{level}>",
+                level = level,
+                text = escape_html(text)
+            ),
+        }
+        .unwrap();
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        writeln!(self.out, "<p>{}</p>", text).unwrap();
+    }
+
+    fn start_list(&mut self) {
+        writeln!(self.out, "<ul>").unwrap();
+    }
+
+    fn list_item(&mut self, text: &str) {
+        writeln!(self.out, "<li>{}", text).unwrap();
+    }
+
+    fn end_item(&mut self) {
+        writeln!(self.out, "</li>").unwrap();
+    }
+
+    fn end_list(&mut self) {
+        writeln!(self.out, "</ul>").unwrap();
+    }
+
+    fn text(&self, text: &str) -> String {
+        escape_html(text)
+    }
+
+    fn code(&self, text: &str) -> String {
+        format!("<code>{}</code>", escape_html(text))
+    }
+
+    fn strong(&self, text: &str) -> String {
+        format!("<strong>{}</strong>", escape_html(text))
+    }
+
+    fn link(&self, text: &str, anchor: &str) -> String {
+        format!("<a href=\"#{}\">{}</a>", escape_html(anchor), text)
+    }
+
+    fn list_type(&self, inner: &str) -> String {
+        format!("[{}]", inner)
+    }
+
+    fn line_break(&self) -> String {
+        "<br>\n".to_string()
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        schema { query: Query }
+
+        "The root query"
+        type Query {
+            "Find a user"
+            user(id: ID!, format: Format = SHORT): User
+            search: [SearchResult!]! @deprecated(reason: "Use `find`")
+        }
+
+        interface Entity { id: ID! }
+
+        type User implements Entity { id: ID! }
+
+        type Post implements Entity { id: ID! }
+
+        union SearchResult = User | Post
+
+        enum Format { SHORT LONG @deprecated }
+    "#;
+
+    #[test]
+    fn test_markdown() {
+        let docs = generate_docs(SCHEMA, DocsFormat::Markdown).unwrap();
+
+        assert!(docs.contains("- Query: [`Query`](#query)\n"));
+        assert!(docs.contains("### Query\n\nThe root query\n"));
+        assert!(docs.contains("- `user`: [`User`](#user)  \n  Find a user\n"));
+        assert!(docs.contains("    - `format`: [`Format`](#format) (default: `SHORT`)\n"));
+        assert!(docs.contains("- `search`: \\[[`SearchResult`](#searchresult)!\\]!\n"));
+        assert!(docs.contains("  - **Deprecated:** Use `find`\n"));
+        assert!(docs.contains("**Implemented by**: [`Post`](#post), [`User`](#user)\n"));
+        assert!(docs.contains("**Member of**: [`SearchResult`](#searchresult)\n"));
+        assert!(docs.contains("- `LONG`\n  - **Deprecated**\n"));
+        assert!(docs.contains("- `id`: `ID`!\n"));
+    }
+
+    #[test]
+    fn test_html() {
+        let docs = generate_docs(SCHEMA, DocsFormat::Html).unwrap();
+
+        assert!(docs.starts_with("<!DOCTYPE html>\n"));
+        assert!(docs.contains("<h3 id=\"user\">User</h3>\n"));
+        assert!(docs.contains(
+            "<li><code>search</code>: [<a href=\"#searchresult\"><code>SearchResult</code></a>!]!\n"
+        ));
+        assert!(docs.contains("<strong>Deprecated:</strong> Use `find`"));
+        assert!(docs.ends_with("</body>\n</html>\n"));
+    }
+
+    #[test]
+    fn test_output_is_stable() {
+        assert_eq!(
+            generate_docs(SCHEMA, DocsFormat::Markdown).unwrap(),
+            generate_docs(SCHEMA, DocsFormat::Markdown).unwrap()
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Print schemas as canonical SDL.
+//!
+//! The schema is parsed into the same `Document` the procedural macros use and printed back out, so
+//! descriptions and directives such as `@juniper(...)` are preserved. Comments (lines starting with
+//! `#`) are not part of the document and are removed. Use
+//! [`first_comment_line`](fn.first_comment_line.html) to check for comments before formatting.
+//!
+//! ```ignore
+//! use juniper_from_schema_code_gen::formatting::{format_schema, FormatOptions};
+//!
+//! let formatted = format_schema(&schema, &FormatOptions::default())?;
+//! ```
+//!
+//! Or with the binary: `juniper-from-schema fmt schema.graphql`. Use `--check` to only check if the
+//! files are formatted, for example on CI. The binary refuses to format files that contain comments
+//! unless `--strip-comments` is passed.
+
+use crate::{introspection::type_definition_name, SchemaError};
+use graphql_parser::{
+    parse_schema,
+    schema::{Definition, Document, TypeDefinition},
+    Style,
+};
+
+/// Options for [`format_schema`](fn.format_schema.html).
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    /// Sort type definitions, directive definitions and fields by name.
+    ///
+    /// The schema definition is always kept first. Arguments and enum values keep their order
+    /// since it determines the order of arguments in the generated methods and the order of the
+    /// generated enum variants.
+    pub sort: bool,
+    /// Number of spaces to indent with.
+    pub indent: u32,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            sort: false,
+            indent: 2,
+        }
+    }
+}
+
+/// Format a schema as canonical SDL.
+pub fn format_schema(schema: &str, options: &FormatOptions) -> Result<String, SchemaError> {
+    let mut doc = parse_schema(schema)?;
+
+    if options.sort {
+        sort_document(&mut doc);
+    }
+
+    Ok(doc.format(Style::default().indent(options.indent)))
+}
+
+/// Check if a schema is already formatted according to `options`.
+pub fn is_formatted(schema: &str, options: &FormatOptions) -> Result<bool, SchemaError> {
+    Ok(format_schema(schema, options)? == schema)
+}
+
+/// Find the line number of the first comment in a schema, if it has any.
+///
+/// Comments are removed by [`format_schema`](fn.format_schema.html). `#` inside strings and block
+/// strings doesn't start a comment.
+pub fn first_comment_line(schema: &str) -> Option<usize> {
+    const BLOCK_QUOTE: &str = "\"\"\"";
+    const ESCAPED_BLOCK_QUOTE: &str = "\\\"\"\"";
+
+    enum State {
+        Normal,
+        String,
+        BlockString,
+    }
+
+    let mut state = State::Normal;
+    let mut line = 1;
+    let mut chars = schema.char_indices();
+
+    while let Some((idx, c)) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+
+        match state {
+            State::Normal => match c {
+                '#' => return Some(line),
+                '"' if schema[idx..].starts_with(BLOCK_QUOTE) => {
+                    chars.nth(1);
+                    state = State::BlockString;
+                }
+                '"' => state = State::String,
+                _ => {}
+            },
+            State::String => match c {
+                '\\' => {
+                    chars.next();
+                }
+                '"' | '\n' => state = State::Normal,
+                _ => {}
+            },
+            State::BlockString => match c {
+                '\\' if schema[idx..].starts_with(ESCAPED_BLOCK_QUOTE) => {
+                    chars.nth(2);
+                }
+                '"' if schema[idx..].starts_with(BLOCK_QUOTE) => {
+                    chars.nth(1);
+                    state = State::Normal;
+                }
+                _ => {}
+            },
+        }
+    }
+
+    None
+}
+
+fn sort_document(doc: &mut Document) {
+    doc.definitions
+        .sort_by(|a, b| sort_key(a).cmp(&sort_key(b)));
+
+    for def in &mut doc.definitions {
+        if let Definition::TypeDefinition(type_def) = def {
+            match type_def {
+                TypeDefinition::Object(obj) => obj.fields.sort_by(|a, b| a.name.cmp(&b.name)),
+                TypeDefinition::Interface(interface) => {
+                    interface.fields.sort_by(|a, b| a.name.cmp(&b.name))
+                }
+                TypeDefinition::InputObject(input) => {
+                    input.fields.sort_by(|a, b| a.name.cmp(&b.name))
+                }
+                TypeDefinition::Scalar(_) | TypeDefinition::Union(_) | TypeDefinition::Enum(_) => {}
+            }
+        }
+    }
+}
+
+fn sort_key(def: &Definition) -> (u8, &str) {
+    match def {
+        Definition::SchemaDefinition(_) => (0, ""),
+        Definition::DirectiveDefinition(dir) => (1, &dir.name),
+        Definition::TypeDefinition(type_def) => (2, type_definition_name(type_def)),
+        Definition::TypeExtension(_) => (3, ""),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_formatting_preserves_descriptions_and_directives() {
+        let schema = r#"
+            schema { query: Query }
+
+            "The root"
+            type Query {
+                "Hello"
+                hello(name: String! = "World"): String! @juniper(ownership: "owned")
+                old: Int @deprecated(reason: "Use hello")
+            }
+        "#;
+
+        let formatted = format_schema(schema, &FormatOptions::default()).unwrap();
+
+        assert_eq!(
+            formatted,
+            r#"schema {
+  query: Query
+}
+
+"The root"
+type Query {
+  "Hello"
+  hello(name: String! = "World"): String! @juniper(ownership: "owned")
+  old: Int @deprecated(reason: "Use hello")
+}
+"#
+        );
+        assert!(is_formatted(&formatted, &FormatOptions::default()).unwrap());
+        assert!(!is_formatted(schema, &FormatOptions::default()).unwrap());
+    }
+
+    #[test]
+    fn test_sorting() {
+        let schema = r#"
+            type User { name: String! id: ID! }
+            enum Color { RED GREEN }
+            schema { query: Query }
+            type Query { user(b: Int, a: Int): User }
+        "#;
+
+        let options = FormatOptions {
+            sort: true,
+            ..FormatOptions::default()
+        };
+        let formatted = format_schema(schema, &options).unwrap();
+
+        assert_eq!(
+            formatted,
+            r#"schema {
+  query: Query
+}
+
+enum Color {
+  RED
+  GREEN
+}
+
+type Query {
+  user(b: Int, a: Int): User
+}
+
+type User {
+  id: ID!
+  name: String!
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_first_comment_line() {
+        assert_eq!(first_comment_line("type Query { a: Int }"), None);
+        assert_eq!(
+            first_comment_line("type Query {\n  # The answer\n  a: Int\n}"),
+            Some(2)
+        );
+        assert_eq!(
+            first_comment_line("type Query { a: Int } # trailing"),
+            Some(1)
+        );
+        assert_eq!(
+            first_comment_line(r##"type Query { a(b: String = "#1"): Int }"##),
+            None
+        );
+        assert_eq!(
+            first_comment_line(
+                r##"type Query { a(b: String = "\"#"): Int }
+                # comment"##
+            ),
+            Some(2)
+        );
+        assert_eq!(
+            first_comment_line(
+                r#"
+                """
+                # Not a comment \""" # still not a comment
+                """
+                type Query { a: Int }
+                "#
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_formatting_is_idempotent() {
+        let schema = include_str!("../../juniper-from-schema/tests/schemas/complex_schema.graphql");
+
+        let options = FormatOptions {
+            sort: true,
+            ..FormatOptions::default()
+        };
+        let formatted = format_schema(schema, &options).unwrap();
+
+        assert!(is_formatted(&formatted, &options).unwrap());
+    }
+}
@@ -0,0 +1,579 @@
+//! Generate the standard GraphQL introspection JSON for a schema without running Juniper.
+//!
+//! Client side tooling such as code generators, linters and editor plugins often want a
+//! `schema.json` file containing the result of the introspection query. This module produces that
+//! file directly from the schema. Descriptions, deprecations and default values are included and
+//! the output is byte-for-byte stable for a given schema, so it can be checked into version control.
+//!
+//! From a build script:
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     juniper_from_schema_code_gen::introspection::write_introspection_json(
+//!         "schema.graphql",
+//!         "schema.json",
+//!     )
+//!     .unwrap();
+//! }
+//! ```
+//!
+//! Or with the binary: `juniper-from-schema introspection schema.graphql -o schema.json`.
+
+use crate::{
+    ast_pass::{
+        ast_data_pass::AstData,
+        directive_parsing::{Deprecation, FromDirective},
+    },
+    render_errors, SchemaError,
+};
+use graphql_parser::{
+    parse_schema,
+    schema::{Definition, Directive, Document, EnumValue, Field, InputValue, Type, TypeDefinition},
+};
+use serde_json::{json, Value};
+use std::{collections::HashMap, path::Path};
+
+/// The built-in scalars and the types used by the introspection system itself. They are part of
+/// every schema Juniper serves so they are part of the introspection output as well.
+const BUILT_IN_TYPES: &str = r#"
+scalar Int
+scalar Float
+scalar String
+scalar Boolean
+scalar ID
+
+type __Schema {
+  types: [__Type!]!
+  queryType: __Type!
+  mutationType: __Type
+  subscriptionType: __Type
+  directives: [__Directive!]!
+}
+
+type __Type {
+  kind: __TypeKind!
+  name: String
+  description: String
+  fields(includeDeprecated: Boolean = false): [__Field!]
+  interfaces: [__Type!]
+  possibleTypes: [__Type!]
+  enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
+  inputFields: [__InputValue!]
+  ofType: __Type
+}
+
+type __Field {
+  name: String!
+  description: String
+  args: [__InputValue!]!
+  type: __Type!
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __InputValue {
+  name: String!
+  description: String
+  type: __Type!
+  defaultValue: String
+}
+
+type __EnumValue {
+  name: String!
+  description: String
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+type __Directive {
+  name: String!
+  description: String
+  locations: [__DirectiveLocation!]!
+  args: [__InputValue!]!
+}
+
+enum __TypeKind {
+  SCALAR
+  OBJECT
+  INTERFACE
+  UNION
+  ENUM
+  INPUT_OBJECT
+  LIST
+  NON_NULL
+}
+
+enum __DirectiveLocation {
+  QUERY
+  MUTATION
+  SUBSCRIPTION
+  FIELD
+  FRAGMENT_DEFINITION
+  FRAGMENT_SPREAD
+  INLINE_FRAGMENT
+}
+"#;
+
+/// Generate the introspection JSON for a schema.
+///
+/// The output has the same shape as the response to the standard introspection query, that is
+/// `{ "__schema": { ... } }`, pretty printed and terminated by a newline.
+pub fn introspection_json(schema: &str) -> Result<String, SchemaError> {
+    let doc = parse_schema(schema)?;
+    let ast_data = AstData::new_from_schema_and_doc(schema, &doc)
+        .map_err(|errors| SchemaError::Invalid(render_errors(errors)))?;
+
+    let built_in = parse_schema(BUILT_IN_TYPES).expect("Failed to parse built-in types");
+
+    let introspection = Introspection::new(&doc, &built_in, &ast_data).build()?;
+
+    let mut json =
+        serde_json::to_string_pretty(&introspection).expect("Failed to serialize introspection");
+    json.push('\n');
+    Ok(json)
+}
+
+/// Read a schema file and write its introspection JSON to `out_path`.
+///
+/// Meant to be called from build scripts. It tells Cargo to re-run the build script if the schema
+/// changes.
+pub fn write_introspection_json<P, Q>(schema_path: P, out_path: Q) -> Result<(), SchemaError>
+where
+    P: AsRef<Path>,
+    Q: AsRef<Path>,
+{
+    let schema_path = schema_path.as_ref();
+    println!("cargo:rerun-if-changed={}", schema_path.display());
+
+    let schema = std::fs::read_to_string(schema_path)?;
+    let json = introspection_json(&schema)?;
+    std::fs::write(out_path, json)?;
+    Ok(())
+}
+
+struct Introspection<'doc> {
+    doc: &'doc Document,
+    built_in: &'doc Document,
+    ast_data: &'doc AstData<'doc>,
+    type_kinds: HashMap<&'doc str, &'static str>,
+}
+
+impl<'doc> Introspection<'doc> {
+    fn new(doc: &'doc Document, built_in: &'doc Document, ast_data: &'doc AstData<'doc>) -> Self {
+        let mut type_kinds = HashMap::new();
+
+        for type_def in type_definitions(built_in).chain(type_definitions(doc)) {
+            type_kinds.insert(
+                type_definition_name(type_def),
+                type_definition_kind(type_def),
+            );
+        }
+
+        Self {
+            doc,
+            built_in,
+            ast_data,
+            type_kinds,
+        }
+    }
+
+    fn build(&self) -> Result<Value, SchemaError> {
+        let mut type_defs = type_definitions(self.built_in)
+            .chain(type_definitions(self.doc))
+            .collect::<Vec<_>>();
+        type_defs.sort_by_key(|type_def| type_definition_name(type_def));
+
+        let types = type_defs
+            .into_iter()
+            .map(|type_def| self.full_type(type_def))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let (query, mutation, subscription) = self.root_types();
+
+        Ok(json!({
+            "__schema": {
+                "queryType": query.map(|name| json!({ "name": name })),
+                "mutationType": mutation.map(|name| json!({ "name": name })),
+                "subscriptionType": subscription.map(|name| json!({ "name": name })),
+                "types": types,
+                "directives": [
+                    directive("include"),
+                    directive("skip"),
+                ],
+            }
+        }))
+    }
+
+    fn root_types(&self) -> (Option<&'doc str>, Option<&'doc str>, Option<&'doc str>) {
+        for def in &self.doc.definitions {
+            if let Definition::SchemaDefinition(schema_def) = def {
+                return (
+                    schema_def.query.as_deref(),
+                    schema_def.mutation.as_deref(),
+                    schema_def.subscription.as_deref(),
+                );
+            }
+        }
+
+        let defined = |name: &'static str| {
+            if self.type_kinds.contains_key(name) {
+                Some(name)
+            } else {
+                None
+            }
+        };
+
+        (
+            defined("Query"),
+            defined("Mutation"),
+            defined("Subscription"),
+        )
+    }
+
+    fn full_type(&self, type_def: &'doc TypeDefinition) -> Result<Value, SchemaError> {
+        let mut fields = Value::Null;
+        let mut input_fields = Value::Null;
+        let mut interfaces = Value::Null;
+        let mut enum_values = Value::Null;
+        let mut possible_types = Value::Null;
+
+        let description = match type_def {
+            TypeDefinition::Scalar(scalar) => &scalar.description,
+            TypeDefinition::Object(obj) => {
+                fields = self.fields(&obj.fields)?;
+                interfaces = obj
+                    .implements_interfaces
+                    .iter()
+                    .map(|name| self.named_type_ref(name))
+                    .collect::<Result<_, _>>()?;
+                &obj.description
+            }
+            TypeDefinition::Interface(interface) => {
+                fields = self.fields(&interface.fields)?;
+                possible_types = self
+                    .ast_data
+                    .get_implementors_of_interface(&interface.name)
+                    .map(|implementors| implementors.as_slice())
+                    .unwrap_or(&[])
+                    .iter()
+                    .map(|name| self.named_type_ref(name))
+                    .collect::<Result<_, _>>()?;
+                &interface.description
+            }
+            TypeDefinition::Union(union) => {
+                possible_types = union
+                    .types
+                    .iter()
+                    .map(|name| self.named_type_ref(name))
+                    .collect::<Result<_, _>>()?;
+                &union.description
+            }
+            TypeDefinition::Enum(enum_type) => {
+                enum_values = enum_type.values.iter().map(enum_value).collect();
+                &enum_type.description
+            }
+            TypeDefinition::InputObject(input_object) => {
+                input_fields = self.input_values(&input_object.fields)?;
+                &input_object.description
+            }
+        };
+
+        Ok(json!({
+            "kind": type_definition_kind(type_def),
+            "name": type_definition_name(type_def),
+            "description": description,
+            "fields": fields,
+            "inputFields": input_fields,
+            "interfaces": interfaces,
+            "enumValues": enum_values,
+            "possibleTypes": possible_types,
+        }))
+    }
+
+    fn fields(&self, fields: &'doc [Field]) -> Result<Value, SchemaError> {
+        fields
+            .iter()
+            .map(|field| {
+                let (is_deprecated, deprecation_reason) = deprecation(&field.directives);
+
+                Ok(json!({
+                    "name": field.name,
+                    "description": field.description,
+                    "args": self.input_values(&field.arguments)?,
+                    "type": self.type_ref(&field.field_type)?,
+                    "isDeprecated": is_deprecated,
+                    "deprecationReason": deprecation_reason,
+                }))
+            })
+            .collect()
+    }
+
+    fn input_values(&self, input_values: &'doc [InputValue]) -> Result<Value, SchemaError> {
+        input_values
+            .iter()
+            .map(|input_value| {
+                Ok(json!({
+                    "name": input_value.name,
+                    "description": input_value.description,
+                    "type": self.type_ref(&input_value.value_type)?,
+                    "defaultValue": input_value
+                        .default_value
+                        .as_ref()
+                        .map(|value| value.to_string()),
+                }))
+            })
+            .collect()
+    }
+
+    fn type_ref(&self, ty: &'doc Type) -> Result<Value, SchemaError> {
+        match ty {
+            Type::NamedType(name) => self.named_type_ref(name),
+            Type::ListType(inner) => Ok(json!({
+                "kind": "LIST",
+                "name": null,
+                "ofType": self.type_ref(inner)?,
+            })),
+            Type::NonNullType(inner) => Ok(json!({
+                "kind": "NON_NULL",
+                "name": null,
+                "ofType": self.type_ref(inner)?,
+            })),
+        }
+    }
+
+    fn named_type_ref(&self, name: &str) -> Result<Value, SchemaError> {
+        let kind = self
+            .type_kinds
+            .get(name)
+            .ok_or_else(|| SchemaError::Invalid(format!("Unknown type `{}`", name)))?;
+
+        Ok(json!({
+            "kind": kind,
+            "name": name,
+            "ofType": null,
+        }))
+    }
+}
+
+fn enum_value(enum_value: &EnumValue) -> Value {
+    let (is_deprecated, deprecation_reason) = deprecation(&enum_value.directives);
+
+    json!({
+        "name": enum_value.name,
+        "description": enum_value.description,
+        "isDeprecated": is_deprecated,
+        "deprecationReason": deprecation_reason,
+    })
+}
+
+// Other directives, such as `@juniper`, are only meaningful to the code generation and are not
+// part of the introspection output.
+pub(crate) fn deprecation(directives: &[Directive]) -> (bool, Option<String>) {
+    for dir in directives {
+        if let Ok(Deprecation::Deprecated(reason)) = Deprecation::from_directive(dir) {
+            return (true, reason);
+        }
+    }
+
+    (false, None)
+}
+
+// These are the directives Juniper supports.
+fn directive(name: &str) -> Value {
+    json!({
+        "name": name,
+        "description": null,
+        "locations": ["FIELD", "FRAGMENT_SPREAD", "INLINE_FRAGMENT"],
+        "args": [
+            {
+                "name": "if",
+                "description": null,
+                "type": {
+                    "kind": "NON_NULL",
+                    "name": null,
+                    "ofType": { "kind": "SCALAR", "name": "Boolean", "ofType": null },
+                },
+                "defaultValue": null,
+            }
+        ],
+    })
+}
+
+pub(crate) fn type_definitions(doc: &Document) -> impl Iterator<Item = &TypeDefinition> {
+    doc.definitions.iter().filter_map(|def| match def {
+        Definition::TypeDefinition(type_def) => Some(type_def),
+        _ => None,
+    })
+}
+
+pub(crate) fn type_definition_name(type_def: &TypeDefinition) -> &str {
+    match type_def {
+        TypeDefinition::Scalar(inner) => &inner.name,
+        TypeDefinition::Object(inner) => &inner.name,
+        TypeDefinition::Interface(inner) => &inner.name,
+        TypeDefinition::Union(inner) => &inner.name,
+        TypeDefinition::Enum(inner) => &inner.name,
+        TypeDefinition::InputObject(inner) => &inner.name,
+    }
+}
+
+pub(crate) fn type_definition_kind(type_def: &TypeDefinition) -> &'static str {
+    match type_def {
+        TypeDefinition::Scalar(_) => "SCALAR",
+        TypeDefinition::Object(_) => "OBJECT",
+        TypeDefinition::Interface(_) => "INTERFACE",
+        TypeDefinition::Union(_) => "UNION",
+        TypeDefinition::Enum(_) => "ENUM",
+        TypeDefinition::InputObject(_) => "INPUT_OBJECT",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SCHEMA: &str = r#"
+        schema {
+          query: Query
+        }
+
+        "Root query"
+        type Query {
+          "Search for anything"
+          search(query: String!, first: Int = 10): [SearchResult!]! @juniper(ownership: "owned")
+          node(id: ID!): Node
+          oldField: String @deprecated(reason: "Use `search`")
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        type User implements Node {
+          id: ID!
+          role: Role!
+        }
+
+        union SearchResult = User
+
+        enum Role {
+          ADMIN
+          MEMBER @deprecated
+        }
+
+        input Pagination {
+          first: Int!
+        }
+    "#;
+
+    fn schema_json() -> Value {
+        let json = introspection_json(SCHEMA).unwrap();
+        serde_json::from_str(&json).unwrap()
+    }
+
+    fn find_type<'a>(json: &'a Value, name: &str) -> &'a Value {
+        json["__schema"]["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|ty| ty["name"] == name)
+            .unwrap_or_else(|| panic!("type `{}` not found", name))
+    }
+
+    #[test]
+    fn test_output_is_stable() {
+        assert_eq!(
+            introspection_json(SCHEMA).unwrap(),
+            introspection_json(SCHEMA).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_root_types() {
+        let json = schema_json();
+        assert_eq!(json["__schema"]["queryType"], json!({ "name": "Query" }));
+        assert_eq!(json["__schema"]["mutationType"], Value::Null);
+    }
+
+    #[test]
+    fn test_types_include_built_ins_and_are_sorted() {
+        let json = schema_json();
+        let names = json["__schema"]["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|ty| ty["name"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        let mut sorted = names.clone();
+        sorted.sort();
+        assert_eq!(names, sorted);
+
+        assert!(names.contains(&"Boolean".to_string()));
+        assert!(names.contains(&"__Schema".to_string()));
+    }
+
+    #[test]
+    fn test_fields_descriptions_and_default_values() {
+        let json = schema_json();
+        let query = find_type(&json, "Query");
+        assert_eq!(query["description"], "Root query");
+
+        let search = &query["fields"][0];
+        assert_eq!(search["description"], "Search for anything");
+        assert_eq!(search["args"][1]["defaultValue"], "10");
+        assert_eq!(
+            search["type"],
+            json!({
+                "kind": "NON_NULL",
+                "name": null,
+                "ofType": {
+                    "kind": "LIST",
+                    "name": null,
+                    "ofType": {
+                        "kind": "NON_NULL",
+                        "name": null,
+                        "ofType": { "kind": "UNION", "name": "SearchResult", "ofType": null },
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_deprecations() {
+        let json = schema_json();
+
+        let old_field = &find_type(&json, "Query")["fields"][2];
+        assert_eq!(old_field["isDeprecated"], true);
+        assert_eq!(old_field["deprecationReason"], "Use `search`");
+
+        let member = &find_type(&json, "Role")["enumValues"][1];
+        assert_eq!(member["isDeprecated"], true);
+        assert_eq!(member["deprecationReason"], Value::Null);
+    }
+
+    #[test]
+    fn test_interfaces_and_possible_types() {
+        let json = schema_json();
+
+        let user = find_type(&json, "User");
+        assert_eq!(user["interfaces"][0]["name"], "Node");
+
+        let node = find_type(&json, "Node");
+        assert_eq!(node["possibleTypes"][0]["name"], "User");
+
+        let search_result = find_type(&json, "SearchResult");
+        assert_eq!(search_result["possibleTypes"][0]["name"], "User");
+    }
+
+    #[test]
+    fn test_unknown_type() {
+        let schema = "schema { query: Query } type Query { user: User }";
+        match introspection_json(schema) {
+            Err(SchemaError::Invalid(msg)) => assert_eq!(msg, "Unknown type `User`"),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
@@ -10,17 +10,25 @@
 #![recursion_limit = "256"]
 #![doc(html_root_url = "https://docs.rs/juniper-from-schema-code-gen/0.5.2")]
 
-extern crate proc_macro;
-extern crate proc_macro2;
-
 mod ast_pass;
+mod cache;
+pub mod diagnostics;
+pub mod docs;
+pub mod formatting;
+pub mod introspection;
+mod naming;
 mod nullable_type;
 mod parse_input;
 mod pretty_print;
+pub mod schema_diff;
+mod schema_error;
+mod type_attributes;
 
 use self::{
     ast_pass::{ast_data_pass::AstData, error::Error, CodeGenPass},
+    cache::{Cache, CacheEntry},
     parse_input::{default_context_type, default_error_type, GraphqlSchemaFromFileInput},
+    type_attributes::AttributesConfig,
 };
 use graphql_parser::parse_schema;
 use proc_macro2::{Span, TokenStream};
@@ -28,6 +36,8 @@ use quote::quote;
 use std::{collections::BTreeSet, path::Path};
 use syn::Type;
 
+pub use self::{naming::Naming, schema_error::SchemaError};
+
 const DATE_TIME_SCALAR_NAME: &str = "DateTimeUtc";
 const DATE_SCALAR_NAME: &str = "Date";
 const UUID_SCALAR_NAME: &str = "Uuid";
@@ -35,18 +45,38 @@ const URL_SCALAR_NAME: &str = "Url";
 
 /// Read a GraphQL schema file and generate corresponding Juniper macro calls.
 ///
-/// See [the crate level docs](index.html) for an example.
-#[proc_macro]
-pub fn graphql_schema_from_file(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let parsed = match syn::parse::<GraphqlSchemaFromFileInput>(input) {
+/// This backs the `graphql_schema_from_file!` macro from "juniper-from-schema".
+pub fn graphql_schema_from_file(input: TokenStream) -> TokenStream {
+    let config = input.to_string();
+    let GraphqlSchemaFromFileInput {
+        schema_path,
+        error_type,
+        context_type,
+        naming,
+        attributes,
+    } = match syn::parse2::<GraphqlSchemaFromFileInput>(input) {
         Ok(p) => p,
-        Err(e) => return e.to_compile_error().into(),
+        Err(e) => return e.to_compile_error(),
     };
 
-    match std::fs::read_to_string(&parsed.schema_path) {
+    match std::fs::read_to_string(&schema_path) {
         Ok(schema) => {
-            let mut tokens = parse_and_gen_schema(&schema, parsed.error_type, parsed.context_type);
-            include_literal_schema(&mut tokens, &parsed.schema_path);
+            let entry = CacheEntry {
+                schema_path: &schema_path,
+                schema: &schema,
+                config: &config,
+            };
+            let mut tokens = Cache::from_env().get_or_insert_with(&entry, || {
+                parse_and_gen_schema(
+                    &schema,
+                    Some(&schema_path),
+                    error_type,
+                    context_type,
+                    naming,
+                    attributes,
+                )
+            });
+            include_literal_schema(&mut tokens, &schema_path);
             tokens
         }
         Err(err) => panic!("{}", err),
@@ -55,7 +85,7 @@ pub fn graphql_schema_from_file(input: proc_macro::TokenStream) -> proc_macro::T
 
 // This should cause the Rust schema to be rebuild even if the user only changes the GraphQL schema
 // file.
-fn include_literal_schema(tokens: &mut proc_macro::TokenStream, schema_path: &Path) {
+fn include_literal_schema(tokens: &mut TokenStream, schema_path: &Path) {
     let schema_path = syn::LitStr::new(
         schema_path
             .to_str()
@@ -63,81 +93,87 @@ fn include_literal_schema(tokens: &mut proc_macro::TokenStream, schema_path: &Pa
         Span::call_site(),
     );
 
-    tokens.extend(proc_macro::TokenStream::from(quote! {
+    tokens.extend(quote! {
         const _: &str = std::include_str!(#schema_path);
-    }));
+    });
 }
 
-/// Write your GraphQL schema directly in your Rust code.
-///
-/// This is mostly useful for testing. Prefer using [`graphql_schema_from_file`][] for larger
-/// schemas.
-///
-/// [`graphql_schema_from_file`]: macro.graphql_schema_from_file.html
-///
-/// # Example
+/// Generate Juniper macro calls from a GraphQL schema written directly in Rust code.
 ///
-/// ```ignore
-/// graphql_schema! {
-///     schema {
-///         query: Query
-///     }
-///
-///     type Query {
-///         helloWorld: String! @juniper(ownership: "owned")
-///     }
-/// }
-///
-/// pub struct Query;
-///
-/// impl QueryFields for Query {
-///     fn field_hello_world(
-///         &self,
-///         executor: &Executor<'_, Context>,
-///     ) -> FieldResult<String> {
-///         Ok("Hello, World!".to_string())
-///     }
-/// }
-/// ```
-#[proc_macro]
-pub fn graphql_schema(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let input: TokenStream = input.into();
+/// This backs the `graphql_schema!` macro from "juniper-from-schema".
+pub fn graphql_schema(input: TokenStream) -> TokenStream {
     let schema = input.to_string();
-    parse_and_gen_schema(&schema, default_error_type(), default_context_type())
+    parse_and_gen_schema(
+        &schema,
+        None,
+        default_error_type(),
+        default_context_type(),
+        Naming::default(),
+        AttributesConfig::default(),
+    )
 }
 
 fn parse_and_gen_schema(
     schema: &str,
+    schema_path: Option<&Path>,
     error_type: Type,
     context_type: Type,
-) -> proc_macro::TokenStream {
+    naming: Naming,
+    attributes: AttributesConfig,
+) -> TokenStream {
+    let file = schema_path.map(|path| path.to_string_lossy());
+    let file = file.as_deref();
+
     let doc = match parse_schema(&schema) {
         Ok(doc) => doc,
-        Err(parse_error) => panic!("{}", parse_error),
+        Err(parse_error) => {
+            if diagnostics::json_diagnostics_enabled() {
+                panic!("{}", diagnostics::parse_error_to_json(&parse_error, file))
+            } else {
+                panic!("{}", parse_error)
+            }
+        }
     };
 
     let ast_data = match AstData::new_from_schema_and_doc(schema, &doc) {
         Ok(x) => x,
-        Err(errors) => print_and_panic_if_errors(errors),
+        Err(errors) => print_and_panic_if_errors(errors, file),
     };
 
-    let output = CodeGenPass::new(schema, error_type, context_type, ast_data);
+    let output = CodeGenPass::new(
+        schema,
+        error_type,
+        context_type,
+        naming,
+        attributes,
+        ast_data,
+    );
 
     match output.gen_juniper_code(&doc) {
         Ok(tokens) => {
-            let out: proc_macro::TokenStream = tokens.into();
-
             if debugging_enabled() {
-                self::pretty_print::code_gen_debug(out.to_string());
+                self::pretty_print::code_gen_debug(tokens.to_string());
             }
 
-            out
+            tokens
         }
-        Err(errors) => print_and_panic_if_errors(errors),
+        Err(errors) => print_and_panic_if_errors(errors, file),
+    }
+}
+
+fn print_and_panic_if_errors<T>(errors: BTreeSet<Error>, file: Option<&str>) -> T {
+    if diagnostics::json_diagnostics_enabled() {
+        let diagnostics = errors
+            .iter()
+            .map(|error| error.to_diagnostic(file))
+            .collect::<Vec<_>>();
+        panic!("{}", diagnostics::diagnostics_to_json(&diagnostics))
+    } else {
+        panic!("{}", render_errors(errors))
     }
 }
 
-fn print_and_panic_if_errors<T>(errors: BTreeSet<Error>) -> T {
+fn render_errors(errors: BTreeSet<Error>) -> String {
     let count = errors.len();
 
     let out = errors
@@ -147,9 +183,9 @@ fn print_and_panic_if_errors<T>(errors: BTreeSet<Error>) -> T {
         .join("\n\n");
 
     if count == 1 {
-        panic!("\n\n{}\n\naborting due to previous error\n", out)
+        format!("\n\n{}\n\naborting due to previous error\n", out)
     } else {
-        panic!("\n\n{}\n\naborting due to {} errors\n", out, count)
+        format!("\n\n{}\n\naborting due to {} errors\n", out, count)
     }
 }
 
@@ -0,0 +1,243 @@
+//! The names of the items generated for a schema.
+//!
+//! The defaults can be changed with `graphql_schema_from_file!` configs, so several schemas can be
+//! generated in the same module without their items colliding.
+
+use crate::ast_pass::ident;
+use heck::{CamelCase, SnakeCase};
+use syn::{Ident, LitStr};
+
+const TYPE_PLACEHOLDER: &str = "{type}";
+const FIELD_PLACEHOLDER: &str = "{field}";
+
+/// The names of the items generated for a schema, as configured with `graphql_schema_from_file!`.
+#[derive(Debug, Clone)]
+pub struct Naming {
+    schema_name: String,
+    query_trails_module: String,
+    fields_trait: String,
+    field_method: String,
+    query_trail_extensions: String,
+    args_type: String,
+    arguments_type: String,
+    field_enum: String,
+}
+
+impl Default for Naming {
+    fn default() -> Self {
+        Naming {
+            schema_name: "Schema".to_string(),
+            query_trails_module: "query_trails".to_string(),
+            fields_trait: "{type}Fields".to_string(),
+            field_method: "field_{field}".to_string(),
+            query_trail_extensions: "QueryTrail{type}Extensions".to_string(),
+            args_type: "{type}{field}Args".to_string(),
+            arguments_type: "{type}{field}Arguments".to_string(),
+            field_enum: "{type}Field".to_string(),
+        }
+    }
+}
+
+impl Naming {
+    /// The configs that can be used to change the names.
+    pub const CONFIGS: &'static [&'static str] = &[
+        "schema_name",
+        "query_trails_module",
+        "fields_trait",
+        "field_method",
+        "query_trail_extensions",
+        "args_type",
+        "arguments_type",
+        "field_enum",
+    ];
+
+    /// Change one of the names, like the `graphql_schema_from_file!` config with the same name.
+    /// Returns an error if the config is unknown, or if the pattern is missing placeholders or
+    /// wouldn't result in valid identifiers.
+    pub fn set_pattern(&mut self, config: &str, pattern: &str) -> Result<(), String> {
+        let (target, placeholders): (_, &[&str]) = match config {
+            "schema_name" => (&mut self.schema_name, &[]),
+            "query_trails_module" => (&mut self.query_trails_module, &[]),
+            "fields_trait" => (&mut self.fields_trait, &[TYPE_PLACEHOLDER]),
+            "field_method" => (&mut self.field_method, &[FIELD_PLACEHOLDER]),
+            "query_trail_extensions" => (&mut self.query_trail_extensions, &[TYPE_PLACEHOLDER]),
+            "args_type" => (&mut self.args_type, &[TYPE_PLACEHOLDER, FIELD_PLACEHOLDER]),
+            "arguments_type" => (
+                &mut self.arguments_type,
+                &[TYPE_PLACEHOLDER, FIELD_PLACEHOLDER],
+            ),
+            "field_enum" => (&mut self.field_enum, &[TYPE_PLACEHOLDER]),
+            _ => return Err(format!("Unknown naming config `{}`", config)),
+        };
+
+        for placeholder in placeholders {
+            if !pattern.contains(placeholder) {
+                return Err(format!("`{}` must contain `{}`", config, placeholder));
+            }
+        }
+
+        let example = pattern
+            .replace(TYPE_PLACEHOLDER, "Type")
+            .replace(FIELD_PLACEHOLDER, "field");
+        if syn::parse_str::<Ident>(&example).is_err() {
+            let mut msg = format!("`{}` is not a valid Rust identifier", pattern);
+            if !placeholders.is_empty() {
+                msg.push_str(" pattern. Supported placeholders are `{type}` and `{field}`");
+            }
+            return Err(msg);
+        }
+
+        *target = pattern.to_string();
+        Ok(())
+    }
+
+    /// Like [`set_pattern`](#method.set_pattern) but reports errors at the span of `value`.
+    pub(crate) fn set(&mut self, config: &str, value: &LitStr) -> syn::Result<()> {
+        self.set_pattern(config, &value.value())
+            .map_err(|msg| syn::Error::new(value.span(), msg))
+    }
+
+    /// The `juniper::RootNode` type alias.
+    pub fn schema_name(&self) -> Ident {
+        ident(&self.schema_name)
+    }
+
+    /// The module containing the `QueryTrail` extension traits.
+    pub fn query_trails_module(&self) -> Ident {
+        ident(&self.query_trails_module)
+    }
+
+    /// The trait users implement to resolve the fields of `type_name`.
+    pub fn fields_trait(&self, type_name: &str) -> Ident {
+        ident(self.fields_trait.replace(TYPE_PLACEHOLDER, type_name))
+    }
+
+    /// The method of the fields trait that resolves `field_name`.
+    pub fn field_method(&self, field_name: &str) -> Ident {
+        ident(
+            self.field_method
+                .replace(FIELD_PLACEHOLDER, &field_name.to_snake_case()),
+        )
+    }
+
+    /// A `QueryTrail` extension trait. `name` is the type name, possibly followed by what the
+    /// trait contains, such as `UserArguments`.
+    pub fn query_trail_extensions(&self, name: &str) -> Ident {
+        ident(self.query_trail_extensions.replace(TYPE_PLACEHOLDER, name))
+    }
+
+    /// The `OwnedQueryTrail` version of `query_trail_extensions`.
+    pub fn owned_query_trail_extensions(&self, name: &str) -> Ident {
+        ident(format!(
+            "Owned{}",
+            self.query_trail_extensions.replace(TYPE_PLACEHOLDER, name)
+        ))
+    }
+
+    /// The type returned by the `*_args` methods of query trails.
+    pub fn args_type(&self, type_name: &str, field_name: &str) -> Ident {
+        ident(
+            self.args_type
+                .replace(TYPE_PLACEHOLDER, type_name)
+                .replace(FIELD_PLACEHOLDER, &field_name.to_camel_case()),
+        )
+    }
+
+    /// The struct passed to field methods using `@juniper(arguments_struct: true)`.
+    pub fn arguments_type(&self, type_name: &str, field_name: &str) -> Ident {
+        ident(
+            self.arguments_type
+                .replace(TYPE_PLACEHOLDER, type_name)
+                .replace(FIELD_PLACEHOLDER, &field_name.to_camel_case()),
+        )
+    }
+
+    /// The enum with a variant for each field of `type_name`, returned by
+    /// `QueryTrail::selected_fields`.
+    pub fn field_enum(&self, type_name: &str) -> Ident {
+        ident(self.field_enum.replace(TYPE_PLACEHOLDER, type_name))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proc_macro2::Span;
+
+    fn set(config: &str, value: &str) -> syn::Result<Naming> {
+        let mut naming = Naming::default();
+        naming.set(config, &LitStr::new(value, Span::call_site()))?;
+        Ok(naming)
+    }
+
+    #[test]
+    fn test_defaults() {
+        let naming = Naming::default();
+
+        assert_eq!(naming.schema_name(), "Schema");
+        assert_eq!(naming.query_trails_module(), "query_trails");
+        assert_eq!(naming.fields_trait("User"), "UserFields");
+        assert_eq!(naming.field_method("firstName"), "field_first_name");
+        assert_eq!(
+            naming.query_trail_extensions("UserArguments"),
+            "QueryTrailUserArgumentsExtensions"
+        );
+        assert_eq!(
+            naming.owned_query_trail_extensions("User"),
+            "OwnedQueryTrailUserExtensions"
+        );
+        assert_eq!(naming.args_type("User", "firstName"), "UserFirstNameArgs");
+        assert_eq!(
+            naming.arguments_type("User", "firstName"),
+            "UserFirstNameArguments"
+        );
+        assert_eq!(naming.field_enum("User"), "UserField");
+    }
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(
+            set("fields_trait", "Admin{type}Fields")
+                .unwrap()
+                .fields_trait("User"),
+            "AdminUserFields"
+        );
+        assert_eq!(
+            set("field_method", "resolve_{field}")
+                .unwrap()
+                .field_method("firstName"),
+            "resolve_first_name"
+        );
+        assert_eq!(
+            set("args_type", "Admin{type}{field}Arguments")
+                .unwrap()
+                .args_type("User", "firstName"),
+            "AdminUserFirstNameArguments"
+        );
+        assert_eq!(
+            set("schema_name", "AdminSchema").unwrap().schema_name(),
+            "AdminSchema"
+        );
+    }
+
+    #[test]
+    fn test_invalid_patterns() {
+        let err = set("fields_trait", "AdminFields").unwrap_err();
+        assert_eq!(err.to_string(), "`fields_trait` must contain `{type}`");
+
+        let err = set("args_type", "{type}Args").unwrap_err();
+        assert_eq!(err.to_string(), "`args_type` must contain `{field}`");
+
+        let err = set("field_method", "{field}-{name}").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`{field}-{name}` is not a valid Rust identifier pattern. Supported placeholders are `{type}` and `{field}`"
+        );
+
+        let err = set("query_trails_module", "admin trails").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`admin trails` is not a valid Rust identifier"
+        );
+    }
+}
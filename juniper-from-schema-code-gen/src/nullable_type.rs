@@ -1,6 +1,6 @@
 use graphql_parser::schema::{Name, Type};
 
-#[derive(Eq, PartialEq, Debug)]
+#[derive(Eq, PartialEq, Debug, Clone)]
 pub enum NullableType<'a> {
     NamedType(&'a Name),
     ListType(Box<NullableType<'a>>),
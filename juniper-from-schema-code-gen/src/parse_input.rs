@@ -1,9 +1,11 @@
+use crate::{naming::Naming, type_attributes::AttributesConfig};
 use proc_macro2::Span;
 use std::{collections::HashMap, fmt::Write, path::PathBuf};
 use syn::{
-    self,
+    self, bracketed,
     parse::{Parse, ParseStream},
-    Ident, Token, Type,
+    punctuated::Punctuated,
+    Attribute, Ident, LitStr, Path, Token, Type,
 };
 
 #[derive(Debug)]
@@ -11,6 +13,8 @@ pub struct GraphqlSchemaFromFileInput {
     pub schema_path: PathBuf,
     pub error_type: Type,
     pub context_type: Type,
+    pub naming: Naming,
+    pub attributes: AttributesConfig,
 }
 
 impl Parse for GraphqlSchemaFromFileInput {
@@ -26,32 +30,66 @@ impl Parse for GraphqlSchemaFromFileInput {
         }
 
         let mut configs = input
-            .parse_terminated::<_, Token![,]>(TypeConfig::parse)?
+            .parse_terminated::<_, Token![,]>(Config::parse)?
             .into_pairs()
             .map(|pair| {
                 let config = pair.into_value();
                 (
                     config.ident.to_string(),
-                    (config.type_, config.ident.span()),
+                    (config.value, config.ident.span()),
                 )
             })
-            .collect::<HashMap<String, (Type, Span)>>();
+            .collect::<HashMap<String, (ConfigValue, Span)>>();
 
         let error_type = configs
             .remove("error_type")
-            .map(|(t, _)| t)
+            .map(|(value, span)| value.into_type("error_type", span))
+            .transpose()?
             .unwrap_or_else(default_error_type);
 
         let context_type = configs
             .remove("context_type")
-            .map(|(t, _)| t)
+            .map(|(value, span)| value.into_type("context_type", span))
+            .transpose()?
             .unwrap_or_else(default_context_type);
 
+        let mut naming = Naming::default();
+        for name in Naming::CONFIGS {
+            if let Some((value, span)) = configs.remove(*name) {
+                naming.set(name, &value.into_str(name, span)?)?;
+            }
+        }
+
+        let mut attributes = AttributesConfig::default();
+        for name in AttributesConfig::configs() {
+            match configs.remove(&name) {
+                Some((ConfigValue::Derives(derives), _)) => {
+                    let kind = AttributesConfig::derives_config(&name).unwrap();
+                    attributes.add_derives(kind, derives);
+                }
+                Some((ConfigValue::Attributes(attrs), _)) => {
+                    let kind = AttributesConfig::attributes_config(&name).unwrap();
+                    attributes.add_attributes(kind, attrs);
+                }
+                Some(_) => unreachable!("`{}` is always parsed as a list", name),
+                None => {}
+            }
+        }
+
         #[allow(clippy::never_loop)]
         for (name, (_, span)) in configs {
+            let supported = ["error_type", "context_type"]
+                .iter()
+                .chain(Naming::CONFIGS)
+                .map(|name| name.to_string())
+                .chain(AttributesConfig::configs())
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ");
+
             let mut msg = String::new();
             writeln!(msg, "Unknown `graphql_schema_from_file` config `{}`", name).unwrap();
-            writeln!(msg, "Supported configs are `error_type` and `context_type`").unwrap();
+            writeln!(msg, "Supported configs are {}", supported).unwrap();
             return Err(syn::parse::Error::new(span, msg));
         }
 
@@ -59,6 +97,8 @@ impl Parse for GraphqlSchemaFromFileInput {
             schema_path,
             error_type,
             context_type,
+            naming,
+            attributes,
         })
     }
 }
@@ -71,16 +111,66 @@ pub fn default_context_type() -> Type {
     syn::parse_str("Context").expect("Failed to parse default context type")
 }
 
-struct TypeConfig {
+struct Config {
     ident: Ident,
-    type_: Type,
+    value: ConfigValue,
 }
 
-impl Parse for TypeConfig {
+impl Parse for Config {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let ident = input.parse::<syn::Ident>()?;
         input.parse::<Token![:]>()?;
-        let type_ = input.parse::<Type>()?;
-        Ok(TypeConfig { ident, type_ })
+        let name = ident.to_string();
+        let value = if AttributesConfig::derives_config(&name).is_some() {
+            let content;
+            bracketed!(content in input);
+            let derives = Punctuated::<Path, Token![,]>::parse_terminated(&content)?;
+            ConfigValue::Derives(derives.into_iter().collect())
+        } else if AttributesConfig::attributes_config(&name).is_some() {
+            let content;
+            bracketed!(content in input);
+            let mut attributes = Vec::new();
+            while !content.is_empty() {
+                attributes.extend(content.call(Attribute::parse_outer)?);
+                if !content.is_empty() {
+                    content.parse::<Token![,]>()?;
+                }
+            }
+            ConfigValue::Attributes(attributes)
+        } else if input.peek(LitStr) {
+            ConfigValue::Str(input.parse()?)
+        } else {
+            ConfigValue::Type(Box::new(input.parse()?))
+        };
+        Ok(Config { ident, value })
+    }
+}
+
+enum ConfigValue {
+    Type(Box<Type>),
+    Str(LitStr),
+    Derives(Vec<Path>),
+    Attributes(Vec<Attribute>),
+}
+
+impl ConfigValue {
+    fn into_type(self, name: &str, span: Span) -> syn::Result<Type> {
+        match self {
+            ConfigValue::Type(type_) => Ok(*type_),
+            _ => Err(syn::parse::Error::new(
+                span,
+                format!("`{}` must be a type", name),
+            )),
+        }
+    }
+
+    fn into_str(self, name: &str, span: Span) -> syn::Result<LitStr> {
+        match self {
+            ConfigValue::Str(lit) => Ok(lit),
+            _ => Err(syn::parse::Error::new(
+                span,
+                format!("`{}` must be a string literal", name),
+            )),
+        }
     }
 }
@@ -0,0 +1,773 @@
+//! Find the changes between two versions of a schema and classify how they affect clients.
+//!
+//! Every change is either:
+//!
+//! - **Breaking**: Existing queries might stop working. For example removing a field or making an
+//!   argument required.
+//! - **Dangerous**: Existing queries keep working but clients might behave differently. For example
+//!   adding an enum value that clients don't know how to handle.
+//! - **Safe**: Existing queries are unaffected. For example adding a field.
+//!
+//! ```ignore
+//! use juniper_from_schema_code_gen::schema_diff::diff_schemas;
+//!
+//! let changes = diff_schemas(&old_schema, &new_schema)?;
+//! if changes.iter().any(|change| change.is_breaking()) {
+//!     // ...
+//! }
+//! ```
+//!
+//! Or with the binary: `juniper-from-schema diff old.graphql new.graphql`. It exits with a
+//! non-zero status if there are breaking changes.
+
+use crate::{
+    introspection::{deprecation, type_definition_kind, type_definition_name, type_definitions},
+    SchemaError,
+};
+use graphql_parser::{
+    parse_schema,
+    schema::{
+        Definition, Directive, DirectiveDefinition, Document, EnumType, Field, InputValue,
+        SchemaDefinition, Type, TypeDefinition, UnionType,
+    },
+};
+use std::{collections::BTreeMap, fmt};
+
+/// How a change affects existing clients.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Criticality {
+    /// Existing queries might stop working.
+    Breaking,
+    /// Existing queries keep working but might behave differently.
+    Dangerous,
+    /// Existing queries are unaffected.
+    Safe,
+}
+
+impl fmt::Display for Criticality {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Criticality::Breaking => write!(f, "breaking"),
+            Criticality::Dangerous => write!(f, "dangerous"),
+            Criticality::Safe => write!(f, "safe"),
+        }
+    }
+}
+
+/// A single change between two versions of a schema.
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Change {
+    /// How the change affects existing clients.
+    pub criticality: Criticality,
+    /// The schema element that changed, such as `User`, `User.name` or `User.name(format:)`.
+    pub path: String,
+    /// Human readable description of the change.
+    pub message: String,
+}
+
+impl Change {
+    /// Whether this change might break existing queries.
+    pub fn is_breaking(&self) -> bool {
+        self.criticality == Criticality::Breaking
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.criticality, self.message)
+    }
+}
+
+/// Find the changes between two versions of a schema.
+///
+/// The changes are sorted by criticality, most critical first, and then by path.
+pub fn diff_schemas(old: &str, new: &str) -> Result<Vec<Change>, SchemaError> {
+    let old = parse_schema(old)?;
+    let new = parse_schema(new)?;
+    Ok(diff_documents(&old, &new))
+}
+
+/// Like [`diff_schemas`](fn.diff_schemas.html) but for already parsed schemas.
+pub fn diff_documents(old: &Document, new: &Document) -> Vec<Change> {
+    let mut diff = Diff::default();
+
+    diff.schema_definition(schema_definition(old), schema_definition(new));
+
+    let old_types = type_definitions(old)
+        .map(|type_def| (type_definition_name(type_def), type_def))
+        .collect::<BTreeMap<_, _>>();
+    let new_types = type_definitions(new)
+        .map(|type_def| (type_definition_name(type_def), type_def))
+        .collect::<BTreeMap<_, _>>();
+    diff.by_name(
+        &old_types,
+        &new_types,
+        |diff, name, _| {
+            diff.push(
+                Criticality::Breaking,
+                name,
+                format!("Type `{}` was removed", name),
+            )
+        },
+        |diff, name, _| {
+            diff.push(
+                Criticality::Safe,
+                name,
+                format!("Type `{}` was added", name),
+            )
+        },
+        |diff, _, old, new| diff.type_definition(old, new),
+    );
+
+    let old_directives = directive_definitions(old);
+    let new_directives = directive_definitions(new);
+    diff.by_name(
+        &old_directives,
+        &new_directives,
+        |diff, name, _| {
+            diff.push(
+                Criticality::Breaking,
+                &format!("@{}", name),
+                format!("Directive `@{}` was removed", name),
+            )
+        },
+        |diff, name, _| {
+            diff.push(
+                Criticality::Safe,
+                &format!("@{}", name),
+                format!("Directive `@{}` was added", name),
+            )
+        },
+        |diff, name, old, new| {
+            diff.arguments(&format!("@{}", name), &old.arguments, &new.arguments)
+        },
+    );
+
+    let mut changes = diff.changes;
+    changes.sort();
+    changes
+}
+
+#[derive(Default)]
+struct Diff {
+    changes: Vec<Change>,
+}
+
+impl Diff {
+    fn push(&mut self, criticality: Criticality, path: &str, message: String) {
+        self.changes.push(Change {
+            criticality,
+            path: path.to_string(),
+            message,
+        });
+    }
+
+    /// Call `removed`, `added` or `both` for each name in `old` and `new`.
+    fn by_name<'a, T: ?Sized>(
+        &mut self,
+        old: &BTreeMap<&'a str, &'a T>,
+        new: &BTreeMap<&'a str, &'a T>,
+        removed: impl Fn(&mut Self, &str, &T),
+        added: impl Fn(&mut Self, &str, &T),
+        both: impl Fn(&mut Self, &str, &T, &T),
+    ) {
+        for (name, old_value) in old {
+            match new.get(name) {
+                Some(new_value) => both(self, name, old_value, new_value),
+                None => removed(self, name, old_value),
+            }
+        }
+
+        for (name, new_value) in new {
+            if !old.contains_key(name) {
+                added(self, name, new_value);
+            }
+        }
+    }
+
+    fn schema_definition(&mut self, old: RootTypes, new: RootTypes) {
+        let roots = [
+            ("query", old.query, new.query),
+            ("mutation", old.mutation, new.mutation),
+            ("subscription", old.subscription, new.subscription),
+        ];
+
+        for (operation, old, new) in roots.iter() {
+            let criticality = match (old, new) {
+                (Some(_), None) => Criticality::Breaking,
+                (Some(old), Some(new)) if old != new => Criticality::Breaking,
+                (None, Some(_)) => Criticality::Safe,
+                _ => continue,
+            };
+
+            self.push(
+                criticality,
+                "schema",
+                format!(
+                    "Root {} type changed from {} to {}",
+                    operation,
+                    root_type_name(*old),
+                    root_type_name(*new),
+                ),
+            );
+        }
+    }
+
+    fn type_definition(&mut self, old: &TypeDefinition, new: &TypeDefinition) {
+        let name = type_definition_name(old);
+
+        match (old, new) {
+            (TypeDefinition::Scalar(_), TypeDefinition::Scalar(_)) => {}
+            (TypeDefinition::Object(old), TypeDefinition::Object(new)) => {
+                self.interfaces(name, &old.implements_interfaces, &new.implements_interfaces);
+                self.fields(name, &old.fields, &new.fields);
+            }
+            (TypeDefinition::Interface(old), TypeDefinition::Interface(new)) => {
+                self.fields(name, &old.fields, &new.fields);
+            }
+            (TypeDefinition::Union(old), TypeDefinition::Union(new)) => self.union(old, new),
+            (TypeDefinition::Enum(old), TypeDefinition::Enum(new)) => self.enum_(old, new),
+            (TypeDefinition::InputObject(old), TypeDefinition::InputObject(new)) => {
+                self.input_fields(name, &old.fields, &new.fields);
+            }
+            (old, new) => self.push(
+                Criticality::Breaking,
+                name,
+                format!(
+                    "Type `{}` changed kind from {} to {}",
+                    name,
+                    type_definition_kind(old),
+                    type_definition_kind(new),
+                ),
+            ),
+        }
+    }
+
+    fn interfaces(&mut self, type_name: &str, old: &[String], new: &[String]) {
+        for interface in old.iter().filter(|interface| !new.contains(interface)) {
+            self.push(
+                Criticality::Breaking,
+                type_name,
+                format!(
+                    "Type `{}` no longer implements interface `{}`",
+                    type_name, interface
+                ),
+            );
+        }
+
+        for interface in new.iter().filter(|interface| !old.contains(interface)) {
+            self.push(
+                Criticality::Dangerous,
+                type_name,
+                format!(
+                    "Type `{}` now implements interface `{}`",
+                    type_name, interface
+                ),
+            );
+        }
+    }
+
+    fn fields(&mut self, type_name: &str, old: &[Field], new: &[Field]) {
+        self.by_name(
+            &by_field_name(old),
+            &by_field_name(new),
+            |diff, name, _| {
+                let path = format!("{}.{}", type_name, name);
+                diff.push(
+                    Criticality::Breaking,
+                    &path,
+                    format!("Field `{}` was removed", path),
+                )
+            },
+            |diff, name, _| {
+                let path = format!("{}.{}", type_name, name);
+                diff.push(
+                    Criticality::Safe,
+                    &path,
+                    format!("Field `{}` was added", path),
+                )
+            },
+            |diff, name, old, new| {
+                let path = format!("{}.{}", type_name, name);
+
+                if old.field_type != new.field_type {
+                    let criticality =
+                        if is_safe_output_type_change(&old.field_type, &new.field_type) {
+                            Criticality::Safe
+                        } else {
+                            Criticality::Breaking
+                        };
+                    diff.push(
+                        criticality,
+                        &path,
+                        format!(
+                            "Field `{}` changed type from `{}` to `{}`",
+                            path, old.field_type, new.field_type
+                        ),
+                    );
+                }
+
+                diff.deprecation(&path, "Field", &old.directives, &new.directives);
+                diff.arguments(&path, &old.arguments, &new.arguments);
+            },
+        );
+    }
+
+    fn arguments(&mut self, field_path: &str, old: &[InputValue], new: &[InputValue]) {
+        self.by_name(
+            &by_input_value_name(old),
+            &by_input_value_name(new),
+            |diff, name, _| {
+                let path = format!("{}({}:)", field_path, name);
+                diff.push(
+                    Criticality::Breaking,
+                    &path,
+                    format!("Argument `{}` was removed from `{}`", name, field_path),
+                )
+            },
+            |diff, name, new| {
+                let path = format!("{}({}:)", field_path, name);
+                if is_required(new) {
+                    diff.push(
+                        Criticality::Breaking,
+                        &path,
+                        format!("Required argument `{}` was added to `{}`", name, field_path),
+                    )
+                } else {
+                    diff.push(
+                        Criticality::Safe,
+                        &path,
+                        format!("Optional argument `{}` was added to `{}`", name, field_path),
+                    )
+                }
+            },
+            |diff, name, old, new| {
+                let path = format!("{}({}:)", field_path, name);
+                diff.input_value(&path, &format!("Argument `{}`", path), old, new);
+            },
+        );
+    }
+
+    fn input_fields(&mut self, type_name: &str, old: &[InputValue], new: &[InputValue]) {
+        self.by_name(
+            &by_input_value_name(old),
+            &by_input_value_name(new),
+            |diff, name, _| {
+                let path = format!("{}.{}", type_name, name);
+                diff.push(
+                    Criticality::Breaking,
+                    &path,
+                    format!("Input field `{}` was removed", path),
+                )
+            },
+            |diff, name, new| {
+                let path = format!("{}.{}", type_name, name);
+                if is_required(new) {
+                    diff.push(
+                        Criticality::Breaking,
+                        &path,
+                        format!("Required input field `{}` was added", path),
+                    )
+                } else {
+                    diff.push(
+                        Criticality::Safe,
+                        &path,
+                        format!("Optional input field `{}` was added", path),
+                    )
+                }
+            },
+            |diff, name, old, new| {
+                let path = format!("{}.{}", type_name, name);
+                diff.input_value(&path, &format!("Input field `{}`", path), old, new);
+            },
+        );
+    }
+
+    fn input_value(&mut self, path: &str, what: &str, old: &InputValue, new: &InputValue) {
+        if old.value_type != new.value_type {
+            let criticality = if is_safe_input_type_change(&old.value_type, &new.value_type) {
+                Criticality::Safe
+            } else {
+                Criticality::Breaking
+            };
+            self.push(
+                criticality,
+                path,
+                format!(
+                    "{} changed type from `{}` to `{}`",
+                    what, old.value_type, new.value_type
+                ),
+            );
+        }
+
+        if old.default_value != new.default_value {
+            self.push(
+                Criticality::Dangerous,
+                path,
+                format!(
+                    "{} changed default value from {} to {}",
+                    what,
+                    default_value_description(old),
+                    default_value_description(new),
+                ),
+            );
+        }
+    }
+
+    fn union(&mut self, old: &UnionType, new: &UnionType) {
+        for member in old
+            .types
+            .iter()
+            .filter(|member| !new.types.contains(member))
+        {
+            self.push(
+                Criticality::Breaking,
+                &old.name,
+                format!("Type `{}` was removed from union `{}`", member, old.name),
+            );
+        }
+
+        for member in new
+            .types
+            .iter()
+            .filter(|member| !old.types.contains(member))
+        {
+            self.push(
+                Criticality::Dangerous,
+                &new.name,
+                format!("Type `{}` was added to union `{}`", member, new.name),
+            );
+        }
+    }
+
+    fn enum_(&mut self, old: &EnumType, new: &EnumType) {
+        let old_values = old
+            .values
+            .iter()
+            .map(|value| (value.name.as_str(), value))
+            .collect::<BTreeMap<_, _>>();
+        let new_values = new
+            .values
+            .iter()
+            .map(|value| (value.name.as_str(), value))
+            .collect::<BTreeMap<_, _>>();
+        let enum_name = &old.name;
+
+        self.by_name(
+            &old_values,
+            &new_values,
+            |diff, name, _| {
+                let path = format!("{}.{}", enum_name, name);
+                diff.push(
+                    Criticality::Breaking,
+                    &path,
+                    format!("Enum value `{}` was removed", path),
+                )
+            },
+            |diff, name, _| {
+                let path = format!("{}.{}", enum_name, name);
+                diff.push(
+                    Criticality::Dangerous,
+                    &path,
+                    format!("Enum value `{}` was added", path),
+                )
+            },
+            |diff, name, old, new| {
+                let path = format!("{}.{}", enum_name, name);
+                diff.deprecation(&path, "Enum value", &old.directives, &new.directives);
+            },
+        );
+    }
+
+    fn deprecation(&mut self, path: &str, what: &str, old: &[Directive], new: &[Directive]) {
+        match (deprecation(old).0, deprecation(new).0) {
+            (false, true) => self.push(
+                Criticality::Safe,
+                path,
+                format!("{} `{}` was deprecated", what, path),
+            ),
+            (true, false) => self.push(
+                Criticality::Safe,
+                path,
+                format!("{} `{}` is no longer deprecated", what, path),
+            ),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct RootTypes<'a> {
+    query: Option<&'a str>,
+    mutation: Option<&'a str>,
+    subscription: Option<&'a str>,
+}
+
+fn schema_definition(doc: &Document) -> RootTypes {
+    let schema_def = doc.definitions.iter().find_map(|def| match def {
+        Definition::SchemaDefinition(schema_def) => Some(schema_def),
+        _ => None,
+    });
+
+    match schema_def {
+        Some(SchemaDefinition {
+            query,
+            mutation,
+            subscription,
+            ..
+        }) => RootTypes {
+            query: query.as_deref(),
+            mutation: mutation.as_deref(),
+            subscription: subscription.as_deref(),
+        },
+        None => {
+            let defined = |name: &'static str| {
+                type_definitions(doc)
+                    .find(|type_def| type_definition_name(type_def) == name)
+                    .map(|_| name)
+            };
+
+            RootTypes {
+                query: defined("Query"),
+                mutation: defined("Mutation"),
+                subscription: defined("Subscription"),
+            }
+        }
+    }
+}
+
+fn root_type_name(name: Option<&str>) -> String {
+    match name {
+        Some(name) => format!("`{}`", name),
+        None => "nothing".to_string(),
+    }
+}
+
+fn directive_definitions(doc: &Document) -> BTreeMap<&str, &DirectiveDefinition> {
+    doc.definitions
+        .iter()
+        .filter_map(|def| match def {
+            Definition::DirectiveDefinition(dir) => Some((dir.name.as_str(), dir)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn by_field_name(fields: &[Field]) -> BTreeMap<&str, &Field> {
+    fields
+        .iter()
+        .map(|field| (field.name.as_str(), field))
+        .collect()
+}
+
+fn by_input_value_name(values: &[InputValue]) -> BTreeMap<&str, &InputValue> {
+    values
+        .iter()
+        .map(|value| (value.name.as_str(), value))
+        .collect()
+}
+
+fn is_required(input_value: &InputValue) -> bool {
+    match input_value.value_type {
+        Type::NonNullType(_) => input_value.default_value.is_none(),
+        _ => false,
+    }
+}
+
+fn default_value_description(input_value: &InputValue) -> String {
+    match &input_value.default_value {
+        Some(value) => format!("`{}`", value),
+        None => "nothing".to_string(),
+    }
+}
+
+/// Clients reading a field are fine with getting a value that is "more specific" than before, for
+/// example `String!` instead of `String`.
+fn is_safe_output_type_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::NamedType(old), Type::NamedType(new)) => old == new,
+        (Type::ListType(old), Type::ListType(new)) => is_safe_output_type_change(old, new),
+        (Type::NonNullType(old), Type::NonNullType(new)) => is_safe_output_type_change(old, new),
+        (old, Type::NonNullType(new)) => is_safe_output_type_change(old, new),
+        _ => false,
+    }
+}
+
+/// Clients sending a value are fine with a type that is "less specific" than before, for example
+/// `String` instead of `String!`.
+fn is_safe_input_type_change(old: &Type, new: &Type) -> bool {
+    match (old, new) {
+        (Type::NamedType(old), Type::NamedType(new)) => old == new,
+        (Type::ListType(old), Type::ListType(new)) => is_safe_input_type_change(old, new),
+        (Type::NonNullType(old), Type::NonNullType(new)) => is_safe_input_type_change(old, new),
+        (Type::NonNullType(old), new) => is_safe_input_type_change(old, new),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[allow(unused_imports)]
+    use super::*;
+
+    fn changes(old: &str, new: &str) -> Vec<(Criticality, String)> {
+        diff_schemas(old, new)
+            .unwrap()
+            .into_iter()
+            .map(|change| (change.criticality, change.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_identical_schemas_have_no_changes() {
+        let schema = "type Query { users(first: Int = 10): [User!]! } type User { id: ID! }";
+        assert_eq!(changes(schema, schema), vec![]);
+    }
+
+    #[test]
+    fn test_removed_and_added_fields() {
+        let old = "type Query { a: Int b: Int }";
+        let new = "type Query { a: Int c: Int }";
+        assert_eq!(
+            changes(old, new),
+            vec![
+                (
+                    Criticality::Breaking,
+                    "Field `Query.b` was removed".to_string()
+                ),
+                (Criticality::Safe, "Field `Query.c` was added".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_output_nullability() {
+        let nullable = "type Query { a: [Int] }";
+        let non_null = "type Query { a: [Int!]! }";
+        assert_eq!(changes(nullable, non_null)[0].0, Criticality::Safe);
+        assert_eq!(changes(non_null, nullable)[0].0, Criticality::Breaking);
+    }
+
+    #[test]
+    fn test_input_nullability() {
+        let nullable = "type Query { a(x: Int): Int } input I { f: [Int] }";
+        let non_null = "type Query { a(x: Int!): Int } input I { f: [Int!]! }";
+
+        let changes_to_non_null = changes(nullable, non_null);
+        assert_eq!(changes_to_non_null.len(), 2);
+        assert!(changes_to_non_null
+            .iter()
+            .all(|(criticality, _)| *criticality == Criticality::Breaking));
+
+        let changes_to_nullable = changes(non_null, nullable);
+        assert_eq!(changes_to_nullable.len(), 2);
+        assert!(changes_to_nullable
+            .iter()
+            .all(|(criticality, _)| *criticality == Criticality::Safe));
+    }
+
+    #[test]
+    fn test_added_arguments() {
+        let old = "type Query { a: Int }";
+        let new = "type Query { a(x: Int!, y: Int, z: Int! = 1): Int }";
+        assert_eq!(
+            changes(old, new),
+            vec![
+                (
+                    Criticality::Breaking,
+                    "Required argument `x` was added to `Query.a`".to_string()
+                ),
+                (
+                    Criticality::Safe,
+                    "Optional argument `y` was added to `Query.a`".to_string()
+                ),
+                (
+                    Criticality::Safe,
+                    "Optional argument `z` was added to `Query.a`".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_changed_default_value() {
+        let old = "type Query { a(x: Int = 1): Int }";
+        let new = "type Query { a(x: Int = 2): Int }";
+        assert_eq!(
+            changes(old, new),
+            vec![(
+                Criticality::Dangerous,
+                "Argument `Query.a(x:)` changed default value from `1` to `2`".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_enum_values() {
+        let old = "type Query { a: E } enum E { A B }";
+        let new = "type Query { a: E } enum E { A C }";
+        assert_eq!(
+            changes(old, new),
+            vec![
+                (
+                    Criticality::Breaking,
+                    "Enum value `E.B` was removed".to_string()
+                ),
+                (
+                    Criticality::Dangerous,
+                    "Enum value `E.C` was added".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_union_members_and_interfaces() {
+        let old = "type Query { a: U } union U = A | B type A implements I { a: Int } type B { a: Int } interface I { a: Int }";
+        let new = "type Query { a: U } union U = A | C type A { a: Int } type B { a: Int } type C { a: Int } interface I { a: Int }";
+        assert_eq!(
+            changes(old, new),
+            vec![
+                (
+                    Criticality::Breaking,
+                    "Type `A` no longer implements interface `I`".to_string()
+                ),
+                (
+                    Criticality::Breaking,
+                    "Type `B` was removed from union `U`".to_string()
+                ),
+                (
+                    Criticality::Dangerous,
+                    "Type `C` was added to union `U`".to_string()
+                ),
+                (Criticality::Safe, "Type `C` was added".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_kind_changed() {
+        let old = "type Query { a: A } type A { a: Int }";
+        let new = "type Query { a: A } interface A { a: Int }";
+        assert_eq!(
+            changes(old, new),
+            vec![(
+                Criticality::Breaking,
+                "Type `A` changed kind from OBJECT to INTERFACE".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_deprecation_is_safe() {
+        let old = "type Query { a: Int }";
+        let new = "type Query { a: Int @deprecated }";
+        assert_eq!(
+            changes(old, new),
+            vec![(
+                Criticality::Safe,
+                "Field `Query.a` was deprecated".to_string()
+            )]
+        );
+    }
+}
@@ -0,0 +1,40 @@
+use graphql_parser::schema::ParseError;
+use std::{fmt, io};
+
+/// Errors that can happen when processing a schema outside of the procedural macros, for example
+/// from a build script or the `juniper-from-schema` binary.
+#[derive(Debug)]
+pub enum SchemaError {
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// The schema isn't valid GraphQL.
+    Parse(ParseError),
+    /// The schema is valid GraphQL but isn't supported by juniper-from-schema.
+    ///
+    /// Contains the same error messages the procedural macros would print.
+    Invalid(String),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SchemaError::Io(err) => write!(f, "{}", err),
+            SchemaError::Parse(err) => write!(f, "{}", err),
+            SchemaError::Invalid(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl From<io::Error> for SchemaError {
+    fn from(err: io::Error) -> Self {
+        SchemaError::Io(err)
+    }
+}
+
+impl From<ParseError> for SchemaError {
+    fn from(err: ParseError) -> Self {
+        SchemaError::Parse(err)
+    }
+}
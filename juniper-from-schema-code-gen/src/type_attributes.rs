@@ -0,0 +1,254 @@
+//! Extra derives and attributes for the Rust types generated for a schema.
+//!
+//! They can be added to every type of a kind with `graphql_schema_from_file!` configs, such as
+//! `input_object_derives: [serde::Serialize]`, and to single types with
+//! `@juniper(derive: [...], attributes: [...])`.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::HashMap;
+use syn::{Attribute, Path};
+
+/// The kinds of GraphQL types that Rust types are generated for.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum GeneratedType {
+    Enum,
+    InputObject,
+    Scalar,
+    Interface,
+    Union,
+}
+
+impl GeneratedType {
+    const ALL: &'static [GeneratedType] = &[
+        GeneratedType::Enum,
+        GeneratedType::InputObject,
+        GeneratedType::Scalar,
+        GeneratedType::Interface,
+        GeneratedType::Union,
+    ];
+
+    fn config_prefix(self) -> &'static str {
+        match self {
+            GeneratedType::Enum => "enum",
+            GeneratedType::InputObject => "input_object",
+            GeneratedType::Scalar => "scalar",
+            GeneratedType::Interface => "interface",
+            GeneratedType::Union => "union",
+        }
+    }
+
+    /// The traits that are always derived, and therefore don't have to be derived again.
+    fn builtin_derives(self) -> &'static [&'static str] {
+        match self {
+            GeneratedType::Enum => &[
+                "GraphQLEnum",
+                "Debug",
+                "Eq",
+                "PartialEq",
+                "Copy",
+                "Clone",
+                "Hash",
+            ],
+            GeneratedType::InputObject => &["GraphQLInputObject", "Debug", "Clone"],
+            GeneratedType::Scalar => &[
+                "Debug",
+                "Eq",
+                "PartialEq",
+                "Ord",
+                "PartialOrd",
+                "Clone",
+                "Hash",
+            ],
+            GeneratedType::Interface | GeneratedType::Union => &[],
+        }
+    }
+}
+
+/// Derives and attributes added to a generated type.
+#[derive(Debug, Clone, Default)]
+pub struct TypeAttributes {
+    pub derives: Vec<Path>,
+    pub attributes: Vec<Attribute>,
+}
+
+impl TypeAttributes {
+    fn extend(&mut self, other: TypeAttributes) {
+        self.derives.extend(other.derives);
+        self.attributes.extend(other.attributes);
+    }
+
+    fn to_tokens(&self, kind: GeneratedType) -> TokenStream {
+        let builtin = kind.builtin_derives();
+        let derives = self
+            .derives
+            .iter()
+            .filter(|path| {
+                let name = path
+                    .segments
+                    .last()
+                    .map(|segment| segment.ident.to_string());
+                !builtin
+                    .iter()
+                    .any(|builtin| Some(*builtin) == name.as_deref())
+            })
+            .collect::<Vec<_>>();
+        let attributes = &self.attributes;
+
+        let derive = if derives.is_empty() {
+            quote! {}
+        } else {
+            quote! { #[derive(#(#derives),*)] }
+        };
+
+        // The derives have to come first since attributes like `#[serde(...)]` are only allowed
+        // after the derive that introduces them.
+        quote! {
+            #derive
+            #(#attributes)*
+        }
+    }
+}
+
+/// The derives and attributes configured for each kind of type.
+#[derive(Debug, Clone, Default)]
+pub struct AttributesConfig {
+    by_kind: HashMap<GeneratedType, TypeAttributes>,
+}
+
+impl AttributesConfig {
+    /// The names of the configs, such as `enum_derives` and `enum_attributes`.
+    pub fn configs() -> Vec<String> {
+        GeneratedType::ALL
+            .iter()
+            .flat_map(|kind| {
+                let prefix = kind.config_prefix();
+                vec![
+                    format!("{}_derives", prefix),
+                    format!("{}_attributes", prefix),
+                ]
+            })
+            .collect()
+    }
+
+    /// The kind of type a `*_derives` config applies to.
+    pub fn derives_config(config: &str) -> Option<GeneratedType> {
+        Self::kind_of_config(config, "_derives")
+    }
+
+    /// The kind of type a `*_attributes` config applies to.
+    pub fn attributes_config(config: &str) -> Option<GeneratedType> {
+        Self::kind_of_config(config, "_attributes")
+    }
+
+    fn kind_of_config(config: &str, suffix: &str) -> Option<GeneratedType> {
+        let prefix = config.strip_suffix(suffix)?;
+        GeneratedType::ALL
+            .iter()
+            .copied()
+            .find(|kind| kind.config_prefix() == prefix)
+    }
+
+    pub fn add_derives(&mut self, kind: GeneratedType, derives: Vec<Path>) {
+        self.by_kind
+            .entry(kind)
+            .or_default()
+            .derives
+            .extend(derives);
+    }
+
+    pub fn add_attributes(&mut self, kind: GeneratedType, attributes: Vec<Attribute>) {
+        self.by_kind
+            .entry(kind)
+            .or_default()
+            .attributes
+            .extend(attributes);
+    }
+
+    /// The derives and attributes for a type, combining the configs for its kind with the ones
+    /// from its `@juniper` directive.
+    pub fn tokens_for(&self, kind: GeneratedType, from_directive: TypeAttributes) -> TokenStream {
+        let mut attributes = self.by_kind.get(&kind).cloned().unwrap_or_default();
+        attributes.extend(from_directive);
+        attributes.to_tokens(kind)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_configs() {
+        assert_eq!(
+            AttributesConfig::derives_config("input_object_derives"),
+            Some(GeneratedType::InputObject)
+        );
+        assert_eq!(
+            AttributesConfig::attributes_config("union_attributes"),
+            Some(GeneratedType::Union)
+        );
+        assert_eq!(AttributesConfig::derives_config("union_attributes"), None);
+        assert_eq!(AttributesConfig::derives_config("object_derives"), None);
+        assert_eq!(AttributesConfig::configs().len(), 10);
+    }
+
+    #[test]
+    fn test_combines_configs_and_directives() {
+        let mut config = AttributesConfig::default();
+        config.add_derives(GeneratedType::Enum, vec![syn::parse_quote!(PartialOrd)]);
+        config.add_attributes(
+            GeneratedType::Enum,
+            vec![syn::parse_quote!(#[serde(rename_all = "camelCase")])],
+        );
+
+        let from_directive = TypeAttributes {
+            derives: vec![syn::parse_quote!(serde::Serialize)],
+            attributes: vec![],
+        };
+
+        assert_eq!(
+            config
+                .tokens_for(GeneratedType::Enum, from_directive)
+                .to_string(),
+            quote! {
+                #[derive(PartialOrd, serde::Serialize)]
+                #[serde(rename_all = "camelCase")]
+            }
+            .to_string()
+        );
+
+        assert!(config
+            .tokens_for(GeneratedType::Union, TypeAttributes::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn test_skips_builtin_derives() {
+        let config = AttributesConfig::default();
+        let from_directive = TypeAttributes {
+            derives: vec![
+                syn::parse_quote!(Debug),
+                syn::parse_quote!(std::clone::Clone),
+                syn::parse_quote!(PartialOrd),
+            ],
+            attributes: vec![],
+        };
+
+        assert_eq!(
+            config
+                .tokens_for(GeneratedType::InputObject, from_directive.clone())
+                .to_string(),
+            quote! { #[derive(PartialOrd)] }.to_string()
+        );
+        assert!(config
+            .tokens_for(
+                GeneratedType::Scalar,
+                TypeAttributes {
+                    derives: vec![syn::parse_quote!(PartialOrd)],
+                    attributes: vec![],
+                }
+            )
+            .is_empty());
+    }
+}
@@ -0,0 +1,66 @@
+use std::{env, fs, path::PathBuf, process::Command};
+
+const SCHEMA: &str = "
+    # The root query
+    type Query { hello: String! }
+    schema { query: Query }
+";
+
+fn write_schema(name: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("{}-{}.graphql", name, std::process::id()));
+    fs::write(&path, SCHEMA).unwrap();
+    path
+}
+
+fn fmt(args: &[&str], path: &PathBuf) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_juniper-from-schema"))
+        .arg("fmt")
+        .args(args)
+        .arg(path)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_fmt_refuses_to_remove_comments() {
+    let path = write_schema("fmt-comments");
+
+    let output = fmt(&[], &path);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let schema = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stderr.contains("has a comment on line 2"), "{}", stderr);
+    assert!(stderr.contains("--strip-comments"), "{}", stderr);
+    assert_eq!(schema, SCHEMA);
+}
+
+#[test]
+fn test_fmt_strip_comments() {
+    let path = write_schema("fmt-strip-comments");
+
+    let output = fmt(&["--strip-comments"], &path);
+    let schema = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(output.status.success());
+    assert_eq!(
+        schema,
+        "type Query {\n  hello: String!\n}\n\nschema {\n  query: Query\n}\n"
+    );
+}
+
+#[test]
+fn test_fmt_rejects_unknown_options() {
+    let path = write_schema("fmt-unknown-option");
+
+    let output = fmt(&["--chek"], &path);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let schema = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr.contains("Unknown option --chek"), "{}", stderr);
+    assert_eq!(schema, SCHEMA);
+}
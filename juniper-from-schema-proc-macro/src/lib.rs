@@ -0,0 +1,50 @@
+//! See the docs for "juniper-from-schema" for more info about this.
+
+#![deny(unused_imports, dead_code, unused_variables, unused_must_use)]
+#![doc(html_root_url = "https://docs.rs/juniper-from-schema-proc-macro/0.5.2")]
+
+extern crate proc_macro;
+
+/// Read a GraphQL schema file and generate corresponding Juniper macro calls.
+///
+/// See [the crate level docs](index.html) for an example.
+#[proc_macro]
+pub fn graphql_schema_from_file(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    juniper_from_schema_code_gen::graphql_schema_from_file(input.into()).into()
+}
+
+/// Write your GraphQL schema directly in your Rust code.
+///
+/// This is mostly useful for testing. Prefer using [`graphql_schema_from_file`][] for larger
+/// schemas.
+///
+/// [`graphql_schema_from_file`]: macro.graphql_schema_from_file.html
+///
+/// # Example
+///
+/// ```ignore
+/// graphql_schema! {
+///     schema {
+///         query: Query
+///     }
+///
+///     type Query {
+///         helloWorld: String! @juniper(ownership: "owned")
+///     }
+/// }
+///
+/// pub struct Query;
+///
+/// impl QueryFields for Query {
+///     fn field_hello_world(
+///         &self,
+///         executor: &Executor<'_, Context>,
+///     ) -> FieldResult<String> {
+///         Ok("Hello, World!".to_string())
+///     }
+/// }
+/// ```
+#[proc_macro]
+pub fn graphql_schema(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    juniper_from_schema_code_gen::graphql_schema(input.into()).into()
+}
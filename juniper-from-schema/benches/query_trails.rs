@@ -0,0 +1,334 @@
+//! Compares resolving fields that receive a `QueryTrail` with the same fields opted out using
+//! `@juniper(trail: false)`.
+//!
+//! Run with `cargo bench --bench query_trails`.
+
+#![allow(dead_code, unused_variables)]
+
+use juniper::Variables;
+use std::time::{Duration, Instant};
+
+const ROUNDS: usize = 5;
+const ITERATIONS: u32 = 5_000;
+const HUMANS: usize = 100;
+
+const QUERY: &str = r#"
+    query {
+        hero(episode: NEWHOPE) { id name }
+        search(text: "") { ... on Human { id name } ... on Droid { id name } }
+    }
+"#;
+
+pub struct Context {
+    humans: Vec<(String, String)>,
+}
+
+impl juniper::Context for Context {}
+
+/// The resolvers are the same for both schemas, only the field signatures differ.
+macro_rules! impl_resolvers {
+    () => {
+        use super::Context;
+        use juniper::{Executor, FieldResult, ID};
+
+        pub struct Human {
+            id: String,
+            name: String,
+        }
+
+        impl Human {
+            fn new((id, name): &(String, String)) -> Self {
+                Human {
+                    id: id.clone(),
+                    name: name.clone(),
+                }
+            }
+        }
+
+        pub struct Droid {
+            id: String,
+            name: String,
+        }
+
+        pub struct Review {
+            episode: Option<Episode>,
+            stars: i32,
+            commentary: Option<String>,
+            favorite_color: Option<ColorInput>,
+        }
+
+        impl HumanFields for Human {
+            fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<ID> {
+                Ok(ID::new(self.id.clone()))
+            }
+
+            fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+                Ok(&self.name)
+            }
+        }
+
+        impl DroidFields for Droid {
+            fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<ID> {
+                Ok(ID::new(self.id.clone()))
+            }
+
+            fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+                Ok(&self.name)
+            }
+        }
+
+        fn hero(executor: &Executor<'_, Context>) -> Option<Character> {
+            executor
+                .context()
+                .humans
+                .first()
+                .map(|human| Character::from(Human::new(human)))
+        }
+
+        fn search(executor: &Executor<'_, Context>) -> Vec<SearchResult> {
+            executor
+                .context()
+                .humans
+                .iter()
+                .map(|human| SearchResult::from(Human::new(human)))
+                .collect()
+        }
+    };
+}
+
+mod with_trails {
+    juniper_from_schema::graphql_schema_from_file!("tests/schemas/complex_schema.graphql");
+
+    impl_resolvers!();
+
+    pub struct Query;
+
+    impl QueryFields for Query {
+        fn field_hero<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+            trail: &QueryTrail<'a, Character, Walked>,
+            episode: Option<Episode>,
+        ) -> FieldResult<Option<Character>> {
+            Ok(hero(executor))
+        }
+
+        fn field_search<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+            trail: &QueryTrail<'a, SearchResult, Walked>,
+            text: Option<String>,
+        ) -> FieldResult<Option<Vec<SearchResult>>> {
+            Ok(Some(search(executor)))
+        }
+    }
+
+    pub struct Mutation;
+
+    impl MutationFields for Mutation {
+        fn field_create_review<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+            trail: &QueryTrail<'a, Review, Walked>,
+            episode: Option<Episode>,
+            review: ReviewInput,
+        ) -> FieldResult<Option<Review>> {
+            unimplemented!()
+        }
+    }
+
+    impl ReviewFields for Review {
+        fn field_episode<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+        ) -> FieldResult<&Option<Episode>> {
+            Ok(&self.episode)
+        }
+
+        fn field_stars<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&i32> {
+            Ok(&self.stars)
+        }
+
+        fn field_commentary<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+        ) -> FieldResult<&Option<String>> {
+            Ok(&self.commentary)
+        }
+
+        fn field_favorite_color<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+            trail: &QueryTrail<'a, ColorInput, Walked>,
+        ) -> FieldResult<&Option<ColorInput>> {
+            Ok(&self.favorite_color)
+        }
+    }
+}
+
+/// `tests/schemas/complex_schema.graphql` with `@juniper(trail: false)` on every field that
+/// returns a type.
+mod without_trails {
+    juniper_from_schema::graphql_schema! {
+        schema {
+          query: Query
+          mutation: Mutation
+        }
+
+        type Query {
+          hero(episode: Episode): Character @juniper(ownership: "owned", trail: false)
+          search(text: String): [SearchResult!] @juniper(ownership: "owned", trail: false)
+        }
+
+        type Mutation {
+          createReview(episode: Episode, review: ReviewInput!): Review
+            @juniper(ownership: "owned", trail: false)
+        }
+
+        enum Episode {
+          NEWHOPE
+          EMPIRE
+          JEDI
+        }
+
+        interface Character {
+          id: ID! @juniper(ownership: "owned")
+          name: String!
+        }
+
+        type Human implements Character {
+          id: ID! @juniper(ownership: "owned")
+          name: String!
+        }
+
+        type Droid implements Character {
+          id: ID! @juniper(ownership: "owned")
+          name: String!
+        }
+
+        union SearchResult = Human | Droid
+
+        input ReviewInput {
+          stars: Int!
+          commentary: String
+          favoriteColor: ColorInput
+        }
+
+        input ColorInput {
+          red: Int!
+          green: Int!
+          blue: Int!
+        }
+
+        type Review {
+          episode: Episode
+          stars: Int!
+          commentary: String
+          favoriteColor: ColorInput @juniper(trail: false)
+        }
+    }
+
+    impl_resolvers!();
+
+    pub struct Query;
+
+    impl QueryFields for Query {
+        fn field_hero<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+            episode: Option<Episode>,
+        ) -> FieldResult<Option<Character>> {
+            Ok(hero(executor))
+        }
+
+        fn field_search<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+            text: Option<String>,
+        ) -> FieldResult<Option<Vec<SearchResult>>> {
+            Ok(Some(search(executor)))
+        }
+    }
+
+    pub struct Mutation;
+
+    impl MutationFields for Mutation {
+        fn field_create_review<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+            episode: Option<Episode>,
+            review: ReviewInput,
+        ) -> FieldResult<Option<Review>> {
+            unimplemented!()
+        }
+    }
+
+    impl ReviewFields for Review {
+        fn field_episode<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+        ) -> FieldResult<&Option<Episode>> {
+            Ok(&self.episode)
+        }
+
+        fn field_stars<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&i32> {
+            Ok(&self.stars)
+        }
+
+        fn field_commentary<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+        ) -> FieldResult<&Option<String>> {
+            Ok(&self.commentary)
+        }
+
+        fn field_favorite_color<'a>(
+            &self,
+            executor: &Executor<'a, Context>,
+        ) -> FieldResult<&Option<ColorInput>> {
+            Ok(&self.favorite_color)
+        }
+    }
+}
+
+/// Time `f`, taking the fastest of a few rounds to reduce noise.
+fn time<F: Fn()>(f: F) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..ITERATIONS {
+                f();
+            }
+            start.elapsed() / ITERATIONS
+        })
+        .min()
+        .unwrap()
+}
+
+fn main() {
+    let ctx = Context {
+        humans: (0..HUMANS)
+            .map(|idx| (idx.to_string(), format!("Human {}", idx)))
+            .collect(),
+    };
+    let variables = Variables::new();
+
+    let schema = with_trails::Schema::new(with_trails::Query, with_trails::Mutation);
+    let with = time(|| {
+        let (_, errors) = juniper::execute(QUERY, None, &schema, &variables, &ctx).unwrap();
+        assert!(errors.is_empty());
+    });
+
+    let schema = without_trails::Schema::new(without_trails::Query, without_trails::Mutation);
+    let without = time(|| {
+        let (_, errors) = juniper::execute(QUERY, None, &schema, &variables, &ctx).unwrap();
+        assert!(errors.is_empty());
+    });
+
+    println!("with trails:    {:?} per query", with);
+    println!("without trails: {:?} per query", without);
+    println!(
+        "difference:     {:.1}%",
+        (with.as_secs_f64() / without.as_secs_f64() - 1.0) * 100.0
+    );
+}
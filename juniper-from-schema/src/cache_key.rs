@@ -0,0 +1,180 @@
+use crate::{QueryTrail, QueryTrailField, QueryTrailFields};
+use juniper::{DefaultScalarValue, LookAheadMethods, LookAheadSelection, LookAheadValue};
+
+impl<'a, T, K> QueryTrail<'a, T, K>
+where
+    T: QueryTrailFields,
+{
+    /// A canonical string describing the selection, for use as a cache key.
+    ///
+    /// It contains the selected fields, their aliases and arguments, and the types interfaces and
+    /// unions have inline fragments on, such as `(first:10){posts{author{name}},title:name}`.
+    /// Fields and arguments are sorted by name, and variables are replaced by their values, so
+    /// equivalent queries get the same key regardless of ordering, formatting, or whether
+    /// fragments are used.
+    ///
+    /// Returns `None` if the trail isn't part of the query, or if the selection can't be fully
+    /// described. That happens when a field is selected more than once under different aliases, or
+    /// when a field with subselections is selected more than once, since `juniper` only exposes
+    /// the arguments and subselection of the first of them.
+    pub fn cache_key(&self) -> Option<String> {
+        let look_ahead = self.look_ahead?;
+        let mut key = String::new();
+        write_arguments(look_ahead, &mut key);
+        write_selection(
+            look_ahead,
+            T::query_trail_fields(),
+            T::query_trail_possible_types(),
+            &mut key,
+        )?;
+        Some(key)
+    }
+
+    /// A hash of [`cache_key`](#method.cache_key).
+    ///
+    /// The hash is stable across compilations and versions of Rust, so it can be used for
+    /// caches shared between processes. Returns `None` when `cache_key` does.
+    pub fn cache_hash(&self) -> Option<u64> {
+        // 64 bit FNV-1a
+        self.cache_key().map(|key| {
+            key.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            })
+        })
+    }
+}
+
+/// Returns `None` if some of the selections can't be inspected.
+fn write_selection(
+    look_ahead: &LookAheadSelection<'_, DefaultScalarValue>,
+    fields: &'static [QueryTrailField],
+    possible_types: &'static [&'static str],
+    out: &mut String,
+) -> Option<()> {
+    if fields.is_empty() {
+        return Some(());
+    }
+
+    // `juniper` doesn't merge selections with the same response key, for example from a fragment
+    // and the query itself, and `select_child` only finds the first selection of a field.
+    let child_names = look_ahead.child_names();
+    let mut entries = Vec::new();
+    let mut seen_names = Vec::new();
+
+    for field in fields {
+        if let Some(child) = look_ahead.select_child(field.name) {
+            let children = (field.children)();
+            let response_key = child.field_name();
+            let selections = child_names
+                .iter()
+                .filter(|name| **name == response_key)
+                .count();
+            if selections > 1 && !children.is_empty() {
+                return None;
+            }
+
+            let mut entry = String::new();
+            if response_key != field.name {
+                entry.push_str(response_key);
+                entry.push(':');
+            }
+            entry.push_str(field.name);
+            write_arguments(child, &mut entry);
+            write_selection(child, children, (field.possible_types)(), &mut entry)?;
+
+            seen_names.push(response_key);
+            entries.push(entry);
+        }
+    }
+
+    // Any other response key is either `__typename` or an additional alias of a field, whose
+    // arguments and subselection can't be inspected.
+    for name in child_names {
+        if seen_names.contains(&name) {
+            continue;
+        }
+        if name != "__typename" {
+            return None;
+        }
+        seen_names.push(name);
+        entries.push(name.to_string());
+    }
+
+    if !possible_types.is_empty() {
+        let all_types = look_ahead.for_explicit_type("").child_names().len();
+        for type_name in possible_types {
+            if look_ahead.for_explicit_type(type_name).child_names().len() > all_types {
+                entries.push(format!("...on {}", type_name));
+            }
+        }
+    }
+
+    entries.sort();
+
+    out.push('{');
+    out.push_str(&entries.join(","));
+    out.push('}');
+
+    Some(())
+}
+
+fn write_arguments(look_ahead: &LookAheadSelection<'_, DefaultScalarValue>, out: &mut String) {
+    let mut arguments = look_ahead.arguments().iter().collect::<Vec<_>>();
+    if arguments.is_empty() {
+        return;
+    }
+    arguments.sort_by(|a, b| a.name().cmp(b.name()));
+
+    out.push('(');
+    for (idx, arg) in arguments.into_iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        out.push_str(arg.name());
+        out.push(':');
+        write_value(arg.value(), out);
+    }
+    out.push(')');
+}
+
+fn write_value(value: &LookAheadValue<'_, DefaultScalarValue>, out: &mut String) {
+    match value {
+        LookAheadValue::Null => out.push_str("null"),
+        LookAheadValue::Scalar(DefaultScalarValue::Int(value)) => out.push_str(&value.to_string()),
+        LookAheadValue::Scalar(DefaultScalarValue::Float(value)) => {
+            out.push_str(&format!("{:?}", value))
+        }
+        LookAheadValue::Scalar(DefaultScalarValue::String(value)) => {
+            out.push_str(&format!("{:?}", value))
+        }
+        LookAheadValue::Scalar(DefaultScalarValue::Boolean(value)) => {
+            out.push_str(&value.to_string())
+        }
+        LookAheadValue::Enum(name) => out.push_str(name),
+        LookAheadValue::List(values) => {
+            out.push('[');
+            for (idx, value) in values.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                write_value(value, out);
+            }
+            out.push(']');
+        }
+        LookAheadValue::Object(fields) => {
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_by_key(|(key, _)| *key);
+
+            out.push('{');
+            for (idx, (key, value)) in fields.into_iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                out.push_str(key);
+                out.push(':');
+                write_value(value, out);
+            }
+            out.push('}');
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Conversions used by the generated code that don't depend on the schema.
+//!
+//! Implementations for enums, input objects, and custom scalars are generated alongside those
+//! types.
+
+use crate::ConversionError;
+use juniper::{
+    parser::ScalarToken, DefaultScalarValue, InputValue, LookAheadValue, ParseScalarResult,
+};
+
+/// Convert a `juniper::DefaultScalarValue` into a concrete value.
+///
+/// This is used for `QueryTrail`.
+#[doc(hidden)]
+pub trait FromDefaultScalarValue<T> {
+    /// Perform the conversion.
+    fn from(self) -> Result<T, ConversionError>;
+}
+
+macro_rules! impl_from_default_scalar_value {
+    ( $( $to:ty => $variant:ident ),* $(,)? ) => {
+        $(
+            impl<'a, 'b> FromDefaultScalarValue<$to> for &'a &'b DefaultScalarValue {
+                fn from(self) -> Result<$to, ConversionError> {
+                    match self {
+                        DefaultScalarValue::$variant(x) => Ok(x.to_owned()),
+                        other => Err(ConversionError::new(
+                            stringify!($variant),
+                            format!("got `{}`", scalar_type_name(other)),
+                        )),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+fn scalar_type_name(value: &DefaultScalarValue) -> &'static str {
+    match value {
+        DefaultScalarValue::Int(_) => "Int",
+        DefaultScalarValue::String(_) => "String",
+        DefaultScalarValue::Float(_) => "Float",
+        DefaultScalarValue::Boolean(_) => "Boolean",
+    }
+}
+
+impl_from_default_scalar_value! {
+    i32 => Int,
+    String => String,
+    f64 => Float,
+    bool => Boolean,
+}
+
+impl<'a, 'b, T> FromDefaultScalarValue<Option<T>> for &'a &'b DefaultScalarValue
+where
+    &'a &'b DefaultScalarValue: FromDefaultScalarValue<T>,
+{
+    fn from(self) -> Result<Option<T>, ConversionError> {
+        self.from().map(Some)
+    }
+}
+
+/// Convert a `juniper::LookAheadValue` into a concrete value.
+///
+/// This is used for `QueryTrail`.
+#[doc(hidden)]
+pub trait FromLookAheadValue<T> {
+    /// Perform the conversion.
+    fn from(self) -> Result<T, ConversionError>;
+}
+
+macro_rules! impl_from_look_ahead_value_for_scalar {
+    ( $( $to:ty => $graphql_name:expr ),* $(,)? ) => {
+        $(
+            impl<'a, 'b> FromLookAheadValue<$to> for &'a LookAheadValue<'b, DefaultScalarValue> {
+                fn from(self) -> Result<$to, ConversionError> {
+                    match self {
+                        LookAheadValue::Scalar(scalar) => FromDefaultScalarValue::from(scalar),
+                        other => Err(ConversionError::unexpected_value($graphql_name, other)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_look_ahead_value_for_scalar! {
+    i32 => "Int",
+    String => "String",
+    f64 => "Float",
+    bool => "Boolean",
+}
+
+impl<'a, 'b, T> FromLookAheadValue<Option<T>> for &'a LookAheadValue<'b, DefaultScalarValue>
+where
+    &'a LookAheadValue<'b, DefaultScalarValue>: FromLookAheadValue<T>,
+{
+    fn from(self) -> Result<Option<T>, ConversionError> {
+        match self {
+            LookAheadValue::Null => Ok(None),
+            other => other.from().map(Some),
+        }
+    }
+}
+
+impl<'a, 'b, T> FromLookAheadValue<Vec<T>> for &'a LookAheadValue<'b, DefaultScalarValue>
+where
+    &'a LookAheadValue<'b, DefaultScalarValue>: FromLookAheadValue<T>,
+{
+    fn from(self) -> Result<Vec<T>, ConversionError> {
+        match self {
+            LookAheadValue::List(values) => values
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| value.from().map_err(|err| err.in_path(idx.to_string())))
+                .collect(),
+            other => Err(ConversionError::unexpected_value("list", other)),
+        }
+    }
+}
+
+impl<'a, 'b> FromLookAheadValue<juniper::ID> for &'a LookAheadValue<'b, DefaultScalarValue> {
+    fn from(self) -> Result<juniper::ID, ConversionError> {
+        match self {
+            LookAheadValue::Scalar(DefaultScalarValue::String(s)) => {
+                Ok(juniper::ID::new(s.to_owned()))
+            }
+            LookAheadValue::Scalar(DefaultScalarValue::Int(i)) => {
+                Ok(juniper::ID::new(i.to_string()))
+            }
+            other => Err(ConversionError::unexpected_value("ID", other)),
+        }
+    }
+}
+
+/// Convert a string scalar by parsing it. Used for the special case scalars.
+#[cfg(any(feature = "url", feature = "uuid", feature = "chrono"))]
+fn parse_string_scalar<T>(
+    value: &LookAheadValue<'_, DefaultScalarValue>,
+    graphql_name: &str,
+    parse: impl FnOnce(&str) -> Result<T, String>,
+) -> Result<T, ConversionError> {
+    match value {
+        LookAheadValue::Scalar(DefaultScalarValue::String(s)) => {
+            parse(s).map_err(|err| ConversionError::new(graphql_name, err))
+        }
+        other => Err(ConversionError::unexpected_value(graphql_name, other)),
+    }
+}
+
+#[cfg(feature = "url")]
+impl<'a, 'b> FromLookAheadValue<url::Url> for &'a LookAheadValue<'b, DefaultScalarValue> {
+    fn from(self) -> Result<url::Url, ConversionError> {
+        parse_string_scalar(self, "Url", |s| {
+            url::Url::parse(s).map_err(|e| format!("error parsing URL: {}", e))
+        })
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<'a, 'b> FromLookAheadValue<uuid::Uuid> for &'a LookAheadValue<'b, DefaultScalarValue> {
+    fn from(self) -> Result<uuid::Uuid, ConversionError> {
+        parse_string_scalar(self, "Uuid", |s| {
+            uuid::Uuid::parse_str(s).map_err(|e| format!("error parsing UUID: {}", e))
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a, 'b> FromLookAheadValue<chrono::NaiveDate> for &'a LookAheadValue<'b, DefaultScalarValue> {
+    fn from(self) -> Result<chrono::NaiveDate, ConversionError> {
+        parse_string_scalar(self, "Date", |s| {
+            chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map_err(|e| format!("error parsing NaiveDate. Format used is `%Y-%m-%d`: {}", e))
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a, 'b> FromLookAheadValue<chrono::DateTime<chrono::Utc>>
+    for &'a LookAheadValue<'b, DefaultScalarValue>
+{
+    fn from(self) -> Result<chrono::DateTime<chrono::Utc>, ConversionError> {
+        parse_string_scalar(self, "DateTimeUtc", |s| {
+            chrono::DateTime::parse_from_rfc3339(s)
+                .map(|date_time| date_time.with_timezone(&chrono::Utc))
+                .map_err(|e| {
+                    format!(
+                        "error parsing DateTime. Format used is RFC 3339 (aka ISO 8601): {}",
+                        e,
+                    )
+                })
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<'a, 'b> FromLookAheadValue<chrono::NaiveDateTime>
+    for &'a LookAheadValue<'b, DefaultScalarValue>
+{
+    fn from(self) -> Result<chrono::NaiveDateTime, ConversionError> {
+        parse_string_scalar(self, "DateTimeUtc", |s| {
+            chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").map_err(|e| {
+                format!(
+                    "error parsing NaiveDateTime. Format used is `%Y-%m-%d %H:%M:%S`: {}",
+                    e,
+                )
+            })
+        })
+    }
+}
+
+/// Resolve a custom scalar, which is represented as a string.
+#[doc(hidden)]
+pub fn resolve_custom_scalar(value: &str) -> juniper::Value {
+    juniper::Value::scalar(DefaultScalarValue::from(value))
+}
+
+/// Read a custom scalar from an input value.
+#[doc(hidden)]
+pub fn custom_scalar_from_input_value(value: &InputValue) -> Option<String> {
+    match value.as_scalar_value() {
+        Some(DefaultScalarValue::String(s)) => Some(s.to_owned()),
+        _ => None,
+    }
+}
+
+/// Parse a custom scalar from a query.
+#[doc(hidden)]
+pub fn parse_custom_scalar(value: ScalarToken<'_>) -> ParseScalarResult<'_, DefaultScalarValue> {
+    <String as juniper::ParseScalarValue>::from_str(value)
+}
+
+/// Convert a custom scalar from a `juniper::LookAheadValue`.
+#[doc(hidden)]
+pub fn custom_scalar_from_look_ahead_value(
+    value: &LookAheadValue<'_, DefaultScalarValue>,
+    graphql_name: &str,
+) -> Result<String, ConversionError> {
+    match value {
+        LookAheadValue::Scalar(DefaultScalarValue::String(s)) => Ok(s.to_owned()),
+        other => Err(ConversionError::unexpected_value(graphql_name, other)),
+    }
+}
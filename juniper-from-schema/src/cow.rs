@@ -0,0 +1,63 @@
+//! Resolving fields with `@juniper(ownership: "cow")`.
+
+use juniper::{
+    meta::MetaType, Arguments, ExecutionResult, Executor, GraphQLType, Registry, ScalarRefValue,
+    ScalarValue, Selection, Value,
+};
+use std::borrow::Cow;
+
+/// Wraps the `Cow` returned by a field method so juniper can resolve it without cloning borrowed
+/// values.
+#[doc(hidden)]
+#[derive(Debug)]
+pub struct CowField<'a, T: Clone>(pub Cow<'a, T>);
+
+impl<'a, S, T> GraphQLType<S> for CowField<'a, T>
+where
+    S: ScalarValue,
+    T: GraphQLType<S> + Clone,
+    for<'b> &'b S: ScalarRefValue<'b>,
+{
+    type Context = T::Context;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(info: &T::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>
+    where
+        S: 'r,
+    {
+        T::meta(info, registry)
+    }
+
+    fn resolve_into_type(
+        &self,
+        info: &T::TypeInfo,
+        name: &str,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<T::Context, S>,
+    ) -> ExecutionResult<S> {
+        (*self.0).resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(
+        &self,
+        info: &T::TypeInfo,
+        field: &str,
+        args: &Arguments<S>,
+        executor: &Executor<T::Context, S>,
+    ) -> ExecutionResult<S> {
+        (*self.0).resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(
+        &self,
+        info: &T::TypeInfo,
+        selection_set: Option<&[Selection<S>]>,
+        executor: &Executor<T::Context, S>,
+    ) -> Value<S> {
+        (*self.0).resolve(info, selection_set, executor)
+    }
+}
@@ -22,15 +22,28 @@
 //!     - [Definition for `@juniper`](#definition-for-juniper)
 //!     - [Customizing ownership](#customizing-ownership)
 //!     - [Infallible fields](#infallible-fields)
+//!     - [Fields without query trails](#fields-without-query-trails)
+//!     - [Context-only field methods](#context-only-field-methods)
+//!     - [Arguments structs](#arguments-structs)
+//!     - [Default field options](#default-field-options)
 //! - [GraphQL to Rust types](#graphql-to-rust-types)
 //! - [Query trails](#query-trails)
 //!     - [Abbreviated example](#abbreviated-example)
 //!     - [Types](#types)
 //!     - [Downcasting for interface and union `QueryTrail`s](#downcasting-for-interface-and-union-querytrails)
 //!     - [`QueryTrail`s for fields that take arguments](#querytrails-for-fields-that-take-arguments)
+//!     - [Listing the selected fields](#listing-the-selected-fields)
+//!     - [Owned `QueryTrail`s](#owned-querytrails)
+//!     - [Query complexity](#query-complexity)
+//!     - [Cache keys](#cache-keys)
+//!     - [SQL projections](#sql-projections)
 //! - [Customizing the error type](#customizing-the-error-type)
 //! - [Customizing the context type](#customizing-the-context-type)
+//! - [Customizing the generated names](#customizing-the-generated-names)
+//! - [Customizing derives and attributes](#customizing-derives-and-attributes)
 //! - [Inspecting the generated code](#inspecting-the-generated-code)
+//! - [Caching the generated code](#caching-the-generated-code)
+//! - [Machine readable errors](#machine-readable-errors)
 //!
 //! # Example
 //!
@@ -260,6 +273,9 @@
 //! - `DateTimeUtc` becomes [`chrono::DateTime<chrono::offset::Utc>`] by default but if defined with
 //! `scalar DateTimeUtc @juniper(with_time_zone: false)` it will become [`chrono::naive::NaiveDateTime`].
 //!
+//! Support for these is enabled by the `url`, `uuid`, and `chrono` features, which are on by
+//! default like they are in Juniper.
+//!
 //! Juniper doesn't support [`chrono::Date`](https://docs.rs/chrono/0.4.9/chrono/struct.Date.html)
 //! so therefore this library cannot support that either. You can read about Juniper's supported
 //! integrations [here](https://docs.rs/juniper/0.13.1/juniper/integrations/index.html).
@@ -708,13 +724,30 @@
 //!
 //! A number of [schema directives][] are supported that lets you customize the generated code:
 //!
-//! - `@juniper(ownership: "owned|borrowed|as_ref")`. For customizing ownership of returned data.
+//! - `@juniper(ownership: "owned|borrowed|borrowed_from_context|as_ref|str|slice|arc|cow|iter")`.
+//! For customizing ownership of returned data, and with `"owned|borrowed"` of arguments.
 //! More info [here](#customizing-ownership).
 //! - `@juniper(infallible: true|false)`. Customize if a field should return `Result<T, _>` or
 //! just `T`. More info
 //! [here](http://localhost:4000/juniper_from_schema/index.html#infallible-fields).
+//! - `@juniper(trail: true|false)`. Customize if a field method should receive a `QueryTrail`.
+//! More info [here](#fields-without-query-trails).
+//! - `@juniper(executor: true|false)`. Customize if a field method should receive the
+//! `Executor` or only the context. More info [here](#context-only-field-methods).
+//! - `@juniper(arguments_struct: true|false)`. Customize if a field method should receive its
+//! arguments as a struct. More info [here](#arguments-structs).
+//! - `@juniper(ownership: ..., infallible: ..., trail: ..., executor: ..., arguments_struct: ...)`
+//! on `schema { }`, types, and interfaces. For changing the defaults of the fields. More info
+//! [here](#default-field-options).
+//! - `@juniper(derive: [String!], attributes: [String!])` on scalars, interfaces, unions, enums,
+//! and input objects. For adding derives and attributes to the generated Rust types. More info
+//! [here](#customizing-derives-and-attributes).
 //! - `@deprecated`. For deprecating types in your schema. Also supports supplying a reason with
 //! `@deprecated(reason: "...")`
+//! - `@cost(value: Int, multipliers: [String])`. For computing the complexity of queries. More
+//! info [here](#query-complexity).
+//! - `@sql(table: String)` on types and `@sql(column: String, join: String)` on fields. For
+//! computing the columns and joins needed by queries. More info [here](#sql-projections).
 //!
 //! [schema directives]: https://www.apollographql.com/docs/apollo-server/schema/directives/
 //!
@@ -727,8 +760,14 @@
 //! directive @juniper(
 //!     ownership: String = "borrowed",
 //!     infallible: Boolean = false,
-//!     with_time_zone: Boolean = true
-//! ) on FIELD_DEFINITION
+//!     with_time_zone: Boolean = true,
+//!     trail: Boolean = true,
+//!     executor: Boolean = true,
+//!     arguments_struct: Boolean = false,
+//!     derive: [String!],
+//!     attributes: [String!]
+//! ) on FIELD_DEFINITION | ARGUMENT_DEFINITION | SCHEMA | OBJECT | SCALAR | INTERFACE | UNION
+//!     | ENUM | INPUT_OBJECT
 //! ```
 //!
 //! This directive definition is allowed in your schema, as well as any other directive definition.
@@ -756,8 +795,23 @@
 //! - `@juniper(ownership: "borrowed")`: The data returned will be borrowed from `self`
 //! (`FieldResult<&T>`).
 //! - `@juniper(ownership: "owned")`: The return type will be owned (`FieldResult<T>`).
+//! - `@juniper(ownership: "borrowed_from_context")`: The data returned will be borrowed for the
+//! lifetime of the executor (`FieldResult<&'a T>`), rather than from `self`. This allows returning
+//! references into `executor.context()`.
 //! - `@juniper(ownership: "as_ref")`: Only applicable for `Option` and `Vec` return types. Changes
-//! the inner type to be borrowed (`FieldResult<Option<&T>>` or `FieldResult<Vec<&T>>`).
+//! the innermost type to be borrowed (`FieldResult<Option<&T>>` or `FieldResult<Vec<&T>>`).
+//! Nested types are borrowed all the way through, so `[[T]]` becomes
+//! `Option<Vec<Option<Vec<Option<&T>>>>>`.
+//! - `@juniper(ownership: "str")`: Only applicable for `String` fields. The return type will be
+//! `FieldResult<&str>`, or `FieldResult<Option<&str>>` if the field is nullable.
+//! - `@juniper(ownership: "slice")`: Only applicable for list fields. The return type will be
+//! `FieldResult<&[T]>`, or `FieldResult<Option<&[T]>>` if the list is nullable.
+//! - `@juniper(ownership: "arc")`: The return type will be `FieldResult<Arc<T>>`.
+//! - `@juniper(ownership: "cow")`: The return type will be `FieldResult<Cow<'_, T>>`. Borrowed
+//! values are resolved without being cloned.
+//! - `@juniper(ownership: "iter")`: Only applicable for list fields. The return type will be
+//! `FieldResult<impl Iterator<Item = T>>`, or `FieldResult<Option<impl Iterator<Item = T>>>` if
+//! the list is nullable. The items are collected into a `Vec` before they're resolved.
 //!
 //! Example:
 //!
@@ -778,6 +832,8 @@
 //!         borrowed: String!
 //!         owned: String! @juniper(ownership: "owned")
 //!         asRef: String @juniper(ownership: "as_ref")
+//!         str: String! @juniper(ownership: "str")
+//!         ids: [Int!]! @juniper(ownership: "iter")
 //!     }
 //! }
 //!
@@ -798,10 +854,127 @@
 //!         // ...
 //!         # unimplemented!()
 //!     }
+//!
+//!     fn field_str(&self, _: &Executor<'_, Context>) -> FieldResult<&str> {
+//!         // ...
+//!         # unimplemented!()
+//!     }
+//!
+//!     fn field_ids(
+//!         &self,
+//!         _: &Executor<'_, Context>,
+//!     ) -> FieldResult<impl Iterator<Item = i32>> {
+//!         // ...
+//!         # Ok(std::iter::empty())
+//!     }
+//! }
+//! ```
+//!
+//! `borrowed_from_context` is useful for data that lives in the context, such as the current user:
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate juniper;
+//! # use juniper_from_schema::*;
+//! # use juniper::*;
+//! # fn main() {}
+//! pub struct Context {
+//!     current_user: User,
+//! }
+//!
+//! impl juniper::Context for Context {}
+//!
+//! graphql_schema! {
+//!     schema {
+//!         query: Query
+//!     }
+//!
+//!     type Query {
+//!         currentUser: User! @juniper(ownership: "borrowed_from_context")
+//!     }
+//!
+//!     type User {
+//!         id: ID!
+//!     }
+//! }
+//!
+//! pub struct Query;
+//!
+//! impl QueryFields for Query {
+//!     fn field_current_user<'a, 'r>(
+//!         &self,
+//!         executor: &Executor<'a, Context>,
+//!         trail: &QueryTrail<'r, User, Walked>,
+//!     ) -> FieldResult<&'a User> {
+//!         Ok(&executor.context().current_user)
+//!     }
+//! }
+//!
+//! pub struct User {
+//!     id: ID,
+//! }
+//!
+//! impl UserFields for User {
+//!     fn field_id(&self, _: &Executor<'_, Context>) -> FieldResult<&ID> {
+//!         Ok(&self.id)
+//!     }
+//! }
+//! ```
+//!
+//! Since the query trail only lives while the field is being resolved it gets its own lifetime
+//! (`'r` above) on fields using `borrowed_from_context`.
+//!
+//! Fields on interfaces should use the same ownership as the fields on the types implementing the
+//! interface.
+//!
+//! Field arguments are owned by default. With `@juniper(ownership: "borrowed")` on an argument
+//! the field method receives `&str` for `String` arguments, `&[T]` for lists, and `&T` for other
+//! types, such as input objects. Nullable arguments become `Option<&str>`, `Option<&[T]>`, and
+//! `Option<&T>`:
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate juniper;
+//! # use juniper_from_schema::*;
+//! # use juniper::*;
+//! # pub struct Context;
+//! # impl juniper::Context for Context {}
+//! # fn main() {}
+//! graphql_schema! {
+//!     schema {
+//!         query: Query
+//!     }
+//!
+//!     type Query {
+//!         search(
+//!             query: Search! @juniper(ownership: "borrowed"),
+//!             tags: [String!] @juniper(ownership: "borrowed"),
+//!         ): Int! @juniper(ownership: "owned")
+//!     }
+//!
+//!     input Search {
+//!         text: String!
+//!     }
+//! }
+//!
+//! pub struct Query;
+//!
+//! impl QueryFields for Query {
+//!     fn field_search(
+//!         &self,
+//!         _: &Executor<'_, Context>,
+//!         query: &Search,
+//!         tags: Option<&[String]>,
+//!     ) -> FieldResult<i32> {
+//!         // ...
+//!         # unimplemented!()
+//!     }
 //! }
 //! ```
 //!
-//! All field arguments will be owned.
+//! The generated code keeps the owned values alive until the field method returns. Only `owned`
+//! and `borrowed` are supported on arguments, and arguments of fields using
+//! [`arguments_struct`](#arguments-structs) are always owned.
 //!
 //! ## Infallible fields
 //!
@@ -850,6 +1023,213 @@
 //! }
 //! ```
 //!
+//! ## Fields without query trails
+//!
+//! Fields that return object types, interfaces, or unions receive a
+//! [`QueryTrail`](#query-trails). Building it requires a look ahead from Juniper, which is done
+//! every time the field is resolved, even if the trail is never used. For fields where you don't
+//! need it you can opt out using `@juniper(trail: false)`, which removes the `trail` argument from
+//! the field method:
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate juniper;
+//! # use juniper_from_schema::*;
+//! # use juniper::*;
+//! # pub struct Context;
+//! # impl juniper::Context for Context {}
+//! # fn main() {}
+//! # pub struct User { id: ID }
+//! # impl UserFields for User {
+//! #     fn field_id(&self, _: &Executor<'_, Context>) -> FieldResult<&ID> {
+//! #         Ok(&self.id)
+//! #     }
+//! # }
+//! graphql_schema! {
+//!     schema {
+//!         query: Query
+//!     }
+//!
+//!     type Query {
+//!         users: [User!]! @juniper(ownership: "owned", trail: false)
+//!     }
+//!
+//!     type User {
+//!         id: ID!
+//!     }
+//! }
+//!
+//! pub struct Query;
+//!
+//! impl QueryFields for Query {
+//!     fn field_users(&self, _: &Executor<'_, Context>) -> FieldResult<Vec<User>> {
+//!         // ...
+//!         # unimplemented!()
+//!     }
+//! }
+//! ```
+//!
+//! The directive has no effect on fields that return scalars, since they never receive a trail.
+//! Fields on interfaces should use the same setting as the fields on the types implementing the
+//! interface. Interfaces don't get arguments structs of their own, since resolving an interface
+//! field calls the field method of the implementing type.
+//!
+//! `benches/query_trails.rs` compares resolving the fields of
+//! `tests/schemas/complex_schema.graphql` with and without trails. Run it with
+//! `cargo bench --bench query_trails`.
+//!
+//! ## Context-only field methods
+//!
+//! Field methods receive the Juniper `Executor`, even though most of them only need
+//! `executor.context()`. With `@juniper(executor: false)` the field method receives the context
+//! instead, which makes it possible to call it from code that doesn't use Juniper:
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate juniper;
+//! # use juniper_from_schema::*;
+//! # use juniper::*;
+//! # fn main() {}
+//! pub struct Context {
+//!     user_count: i32,
+//! }
+//!
+//! impl juniper::Context for Context {}
+//!
+//! graphql_schema! {
+//!     schema {
+//!         query: Query
+//!     }
+//!
+//!     type Query {
+//!         userCount: Int! @juniper(ownership: "owned", executor: false)
+//!     }
+//! }
+//!
+//! pub struct Query;
+//!
+//! impl QueryFields for Query {
+//!     fn field_user_count(&self, ctx: &Context) -> FieldResult<i32> {
+//!         Ok(ctx.user_count)
+//!     }
+//! }
+//! ```
+//!
+//! The context has the lifetime of the executor, so it works together with
+//! `@juniper(ownership: "borrowed_from_context")`. Like the other field options, `executor` can
+//! be set for a whole type or schema, as described in
+//! [default field options](#default-field-options).
+//! Fields on interfaces should use the same setting as the fields on the types implementing the
+//! interface. Interfaces don't get arguments structs of their own, since resolving an interface
+//! field calls the field method of the implementing type.
+//!
+//! ## Arguments structs
+//!
+//! Field methods receive one parameter per argument, so adding an argument to the schema changes
+//! the signature of the method. With `@juniper(arguments_struct: true)` the arguments are instead
+//! passed as a generated `{Type}{Field}Arguments` struct, with a public field for each argument
+//! and default values applied:
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate juniper;
+//! # use juniper_from_schema::*;
+//! # use juniper::*;
+//! # pub struct Context;
+//! # impl juniper::Context for Context {}
+//! # fn main() {}
+//! # pub struct User { id: ID }
+//! # impl UserFields for User {
+//! #     fn field_id(&self, _: &Executor<'_, Context>) -> FieldResult<&ID> {
+//! #         Ok(&self.id)
+//! #     }
+//! # }
+//! graphql_schema! {
+//!     schema {
+//!         query: Query
+//!     }
+//!
+//!     type Query {
+//!         users(first: Int = 10, nameContains: String): [User!]!
+//!             @juniper(ownership: "owned", arguments_struct: true)
+//!     }
+//!
+//!     type User {
+//!         id: ID!
+//!     }
+//! }
+//!
+//! pub struct Query;
+//!
+//! impl QueryFields for Query {
+//!     fn field_users(
+//!         &self,
+//!         _: &Executor<'_, Context>,
+//!         trail: &QueryTrail<'_, User, Walked>,
+//!         args: QueryUsersArguments,
+//!     ) -> FieldResult<Vec<User>> {
+//!         let first: i32 = args.first;
+//!         let name_contains: Option<String> = args.name_contains;
+//!         // ...
+//!         # unimplemented!()
+//!     }
+//! }
+//! ```
+//!
+//! The same struct can be built from the arguments in a [query trail][] with
+//! `to_arguments`, such as `trail.users_args().map(|args| args.to_arguments())`, so the arguments
+//! of a field are only modelled once. Fields without arguments are not affected.
+//!
+//! Fields on interfaces should use the same setting as the fields on the types implementing the
+//! interface. Interfaces don't get arguments structs of their own, since resolving an interface
+//! field calls the field method of the implementing type.
+//!
+//! [query trail]: #querytrails-for-fields-that-take-arguments
+//!
+//! ## Default field options
+//!
+//! `ownership`, `infallible`, `trail`, `executor`, and `arguments_struct` can also be set on
+//! `schema { }`, where they apply to every field in the schema, or on an object or interface
+//! type, where they apply to the fields of that type. Options set on a field take precedence over
+//! the ones set on its type, which take precedence over the ones set on the schema:
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate juniper;
+//! # use juniper_from_schema::*;
+//! # use juniper::*;
+//! # pub struct Context;
+//! # impl juniper::Context for Context {}
+//! # fn main() {}
+//! graphql_schema! {
+//!     schema @juniper(ownership: "owned") {
+//!         query: Query
+//!     }
+//!
+//!     type Query @juniper(infallible: true) {
+//!         count: Int!
+//!         name: String! @juniper(ownership: "borrowed", infallible: false)
+//!     }
+//! }
+//!
+//! pub struct Query {
+//!     name: String,
+//! }
+//!
+//! impl QueryFields for Query {
+//!     fn field_count(&self, _: &Executor<'_, Context>) -> i32 {
+//!         1
+//!     }
+//!
+//!     fn field_name(&self, _: &Executor<'_, Context>) -> FieldResult<&String> {
+//!         Ok(&self.name)
+//!     }
+//! }
+//! ```
+//!
+//! Defaults set on an interface only apply to the fields of the interface, not to the types
+//! implementing it.
+//!
 //! # GraphQL to Rust types
 //!
 //! This is how the standard GraphQL types will be mapped to Rust:
@@ -1157,6 +1537,40 @@
 //! This can be done by calling `.downcast()` which automatically gets implemented for interface and
 //! union query trails. See above for an example.
 //!
+//! ### Only downcasting to types in the query
+//!
+//! `.downcast()` works for every implementor of the interface or member of the union, even if the
+//! query doesn't ask for anything specific to that type. To skip loading data for such types you
+//! can instead use the generated `as_{name of type}` methods (e.g. `as_tweet`), which return
+//! `None` unless the query has an inline fragment on the type or selects a field that only exists
+//! on the type through a named fragment:
+//!
+//! ```ignore
+//! if let Some(tweet_trail) = trail.as_tweet() {
+//!     // the query contains `... on Tweet { ... }`
+//! }
+//! ```
+//!
+//! Juniper doesn't keep track of which type a named fragment is on, so for named fragments only
+//! fields that no other implementor or member has are taken into account. If `Tweet` and `Photo`
+//! both have a `text` field, `...TweetFields` with `fragment TweetFields on Tweet { text }` makes
+//! neither `as_tweet` nor `as_photo` return `Some`. Use inline fragments, or `.downcast()`, if
+//! that matters.
+//!
+//! Fields and fragments excluded with `@include(if: false)` or `@skip(if: true)` are never
+//! considered part of the query, both by `as_*` methods and the other `QueryTrail` methods.
+//!
+//! ### Union members with conflicting field types
+//!
+//! Union query trails get methods for the fields of all the members of the union. If members have
+//! fields with the same name but different types, such as `Photo.size: Int` and
+//! `Video.size: Duration`, no method is generated for the field on the union trail. The field is
+//! still available after downcasting with `as_photo()`, `as_video()` or `.downcast()`.
+//!
+//! GraphQL requires such fields to be aliased if they're selected together. Only the first of them
+//! can be inspected through the union trail, but the resolvers for the fields always receive the
+//! right trail.
+//!
 //! ## `QueryTrail`s for fields that take arguments
 //!
 //! Sometimes you have GraphQL fields that take arguments that impact which things your resolvers
@@ -1221,14 +1635,17 @@
 //!         executor: &'a juniper::Executor<'a, Context>,
 //!         trail: &'a QueryTrail<'a, Country, Walked>
 //!     ) -> juniper::FieldResult<Vec<Country>> {
-//!         // Get struct that has all arguments passed to `Country.users`
-//!         let args: CountryUsersArgs<'a> = trail.users_args();
-//!
-//!         // The struct has methods for each argument, e.g. `active_since`.
-//!         //
-//!         // Notice that it automatically converts the incoming value to
-//!         // a `DateTime<Utc>`.
-//!         let _: DateTime<Utc> = args.active_since();
+//!         // Get struct that has all arguments passed to `Country.users`. It is
+//!         // `None` if `users` wasn't part of the query.
+//!         if let Some(args) = trail.users_args() {
+//!             let args: CountryUsersArgs<'a> = args;
+//!
+//!             // The struct has methods for each argument, e.g. `active_since`.
+//!             //
+//!             // Notice that it automatically converts the incoming value to
+//!             // a `DateTime<Utc>`.
+//!             let _: DateTime<Utc> = args.active_since()?;
+//!         }
 //!
 //!         # unimplemented!()
 //!         // ...
@@ -1289,7 +1706,7 @@
 //!         executor: &juniper::Executor<'_, Context>,
 //!         trail: &QueryTrail<'_, Country, Walked>
 //!     ) -> juniper::FieldResult<Vec<Country>> {
-//!         let args: CountryUsersArgs = trail.users_args();
+//!         let args: Option<CountryUsersArgs> = trail.users_args();
 //!
 //!         # unimplemented!()
 //!         // ...
@@ -1299,6 +1716,17 @@
 //!
 //! The name of the arguments struct will always be `{name of type}{name of field}Args` (e.g.
 //! `CountryUsersArgs`). The method names will always be the name of the arguments in snake case.
+//! For fields using [`@juniper(arguments_struct: true)`](#arguments-structs) there is also a
+//! `to_arguments` method that converts all the arguments into the struct passed to the field
+//! method.
+//!
+//! The argument methods return `Result<T, ConversionError>`. Converting an argument fails if the
+//! value in the query cannot be converted into the Rust type, for example a `Url` argument that
+//! isn't a valid URL. Missing nullable arguments are `Ok(None)` and missing arguments with default
+//! values return the default. The [`ConversionError`][] contains the path to the value that failed
+//! to convert and implements `Display`, so it can be returned from resolvers with `?`.
+//!
+//! [`ConversionError`]: struct.ConversionError.html
 //!
 //! The `*_args` method is only defined on `Walked` query trails so if you get an error like:
 //!
@@ -1319,6 +1747,191 @@
 //! Remember that you can always run `cargo doc` to get a high level overview of the generated
 //! code.
 //!
+//! ## Listing the selected fields
+//!
+//! Every object, interface, and union type gets an enum with a variant for each field, named
+//! `{name of type}Field` (e.g. `UserField`). `QueryTrail::selected_fields` returns an iterator over
+//! the fields selected in the query. If the schema has a type with the same name as one of the
+//! enums it's reported as an error, and the enums can be renamed with the `field_enum` config (see
+//! [Customizing the generated names](#customizing-the-generated-names)).
+//!
+//! ```ignore
+//! let fields: Vec<UserField> = trail.selected_fields().collect();
+//! // [UserField::Id, UserField::Name]
+//! ```
+//!
+//! [`QueryTrail::walk_path`][] finds the selection for a path of field names, such as
+//! `trail.walk_path("posts.author")`, without having to call the generated method for each step.
+//! Note that it returns the raw `juniper::LookAheadSelection` rather than a `QueryTrail`, so the
+//! generated methods aren't available on the result and it has to be inspected with Juniper's
+//! look-ahead API.
+//!
+//! `QueryTrail`s also implement `Debug`, which prints the fields selected in the query as a tree.
+//! `{:#?}` can be used to get one field per line:
+//!
+//! ```ignore
+//! println!("{:?}", trail);
+//! // QueryTrail {id, name, posts {author {name}}}
+//! ```
+//!
+//! [`QueryTrail::walk_path`]: struct.QueryTrail.html#method.walk_path
+//!
+//! ## Owned `QueryTrail`s
+//!
+//! `QueryTrail`s borrow from the executor so they cannot be moved into spawned tasks, sent to
+//! batching loaders, or stored. [`QueryTrail::to_owned`][] copies the selection into an
+//! [`OwnedQueryTrail`][], which is `Send + Sync + 'static` and cheap to clone:
+//!
+//! ```ignore
+//! let owned: OwnedQueryTrail<User, Walked> = trail.to_owned();
+//!
+//! std::thread::spawn(move || {
+//!     if let Some(args) = owned.posts_args() {
+//!         let first: i32 = args.first()?;
+//!     }
+//!     // ...
+//! });
+//! ```
+//!
+//! `OwnedQueryTrail` has the same generated methods as `QueryTrail` through the
+//! `OwnedQueryTrail{name of type}Extensions` and `OwnedQueryTrail{name of type}ArgumentsExtensions`
+//! traits, and interface and union trails can be converted with `.downcast()`.
+//!
+//! [`QueryTrail::to_owned`]: struct.QueryTrail.html#method.to_owned
+//! [`OwnedQueryTrail`]: struct.OwnedQueryTrail.html
+//!
+//! ## Query complexity
+//!
+//! `QueryTrail`s can be used to reject expensive queries before resolving them. The cost of fields
+//! is declared with the `@cost` directive:
+//!
+//! ```graphql
+//! type Query {
+//!   users(first: Int = 10): [User!]! @cost(multipliers: ["first"])
+//! }
+//!
+//! type User {
+//!   id: ID! @cost(value: 0)
+//!   posts(first: Int!): [Post!]! @cost(value: 5, multipliers: ["first"])
+//! }
+//! ```
+//!
+//! The complexity of a selected field is its `value` (`1` if it doesn't have a `@cost` directive)
+//! plus the complexity of the fields selected below it, multiplied by the value of each of the
+//! `multipliers`. Multipliers must be names of `Int` arguments of the field. If an argument isn't
+//! given in the query its default value is used, or `1` if it doesn't have one. Interfaces and
+//! unions cost as much as their most expensive possible type.
+//!
+//! The complexity of the fields selected by a `QueryTrail` is computed with the generated
+//! `QueryTrailComplexity` trait. To include the cost of the field being resolved, the generated
+//! function `complexity` takes a field of the query type, as a variant of the [field
+//! enum](#listing-the-selected-fields) `QueryField`, and the selection of that field from
+//! `executor.look_ahead()`:
+//!
+//! ```ignore
+//! fn field_users<'a>(
+//!     &self,
+//!     executor: &Executor<'a, Context>,
+//!     trail: &QueryTrail<'a, User, Walked>,
+//!     first: i32,
+//! ) -> FieldResult<Vec<User>> {
+//!     if complexity(QueryField::Users, &executor.look_ahead()) > 1000 {
+//!         return Err(FieldError::new("Query is too complex", Value::null()));
+//!     }
+//!     // ...
+//! }
+//! ```
+//!
+//! Juniper doesn't expose the selection of the whole operation, so each root field has to check
+//! its own complexity.
+//!
+//! ## Cache keys
+//!
+//! [`QueryTrail::cache_key`][] returns a canonical string describing the selection, including the
+//! selected fields, aliases, arguments, and the types of inline fragments on interfaces and unions.
+//! Fields and arguments are sorted and variables are replaced by their values, so equivalent
+//! queries get the same key:
+//!
+//! ```ignore
+//! // `user(id: 1) { userName: name posts(first: 10) { title } }`
+//! assert_eq!(
+//!     trail.cache_key(),
+//!     Some("(id:1){posts(first:10){title},userName:name}".to_string()),
+//! );
+//! ```
+//!
+//! [`QueryTrail::cache_hash`][] returns a hash of the key that is stable across compilations, so it
+//! can be used for caches shared between processes.
+//!
+//! Juniper only exposes the arguments and subselection of the first selection of a field. So if a
+//! field is selected more than once under different aliases, such as `recent: posts(first: 5)` and
+//! `all: posts(first: 100)`, or a field with a subselection is selected more than once, both
+//! methods return `None` rather than a key that might be shared with a different query. Don't cache
+//! those queries.
+//!
+//! [`QueryTrail::cache_key`]: struct.QueryTrail.html#method.cache_key
+//! [`QueryTrail::cache_hash`]: struct.QueryTrail.html#method.cache_hash
+//!
+//! ## SQL projections
+//!
+//! If your types are backed by SQL tables you can describe the mapping with `@sql` directives and
+//! compute which columns to select and which tables to join from a `QueryTrail`:
+//!
+//! ```graphql
+//! type User @sql(table: "users") {
+//!   id: ID! @sql(column: "id")
+//!   name: String! @sql(column: "full_name")
+//!   posts: [Post!]! @sql(join: "{alias}.author_id = {parent}.id")
+//!   invitedBy: User @sql(column: "invited_by_id", join: "{alias}.id = {parent}.invited_by_id")
+//! }
+//!
+//! type Post @sql(table: "posts") {
+//!   title: String! @sql(column: "title")
+//! }
+//! ```
+//!
+//! - `@sql(table: "...")` on an object type sets the table the type is stored in.
+//! - `@sql(column: "...")` on a field selects the column if the field is in the query.
+//! - `@sql(join: "...")` on a field joins the table of the field's type and includes the columns
+//! selected below it. The value is the join condition. The type of the field must have a
+//! `@sql(table: "...")` directive.
+//!
+//! Each join gets an alias made from the alias of its parent and the name of the field, such as
+//! `users_posts`, so the same table can be joined more than once. Join conditions refer to the
+//! joined table as `{alias}` and to the parent table as `{parent}`, which are replaced with the
+//! aliases. Columns of joined tables use the alias as their table.
+//!
+//! Fields without `@sql` directives, like computed fields or fields loaded separately, are ignored.
+//!
+//! The generated `QueryTrailSql` trait then returns a [`SqlProjection`][]:
+//!
+//! ```ignore
+//! // `users { name posts { title } }`
+//! let projection = trail.sql_projection();
+//!
+//! assert_eq!(projection.table, "users");
+//! assert_eq!(
+//!     projection.columns,
+//!     vec![
+//!         SqlColumn { table: "users".to_string(), column: "full_name" },
+//!         SqlColumn { table: "users_posts".to_string(), column: "title" },
+//!     ],
+//! );
+//! assert_eq!(
+//!     projection.joins,
+//!     vec![SqlJoin {
+//!         table: "posts",
+//!         alias: "users_posts".to_string(),
+//!         on: "users_posts.author_id = users.id".to_string(),
+//!     }],
+//! );
+//! ```
+//!
+//! `SqlProjection` is plain data so it can be used with any query builder, and tested without a
+//! database.
+//!
+//! [`SqlProjection`]: struct.SqlProjection.html
+//!
 //! # Customizing the error type
 //!
 //! By default the return type of the generated field methods will be [`juniper::FieldResult<T>`].
@@ -1419,6 +2032,138 @@
 //! [`graphql_schema!`]: macro.graphql_schema.html
 //! [`graphql_schema_from_file!`]: macro.graphql_schema_from_file.html
 //!
+//! # Customizing the generated names
+//!
+//! The names of the generated traits, methods, and modules can be changed with these
+//! [`graphql_schema_from_file!`] configs:
+//!
+//! | Config | Default |
+//! |---|---|
+//! | `schema_name` | `"Schema"` |
+//! | `query_trails_module` | `"query_trails"` |
+//! | `fields_trait` | `"{type}Fields"` |
+//! | `field_method` | `"field_{field}"` |
+//! | `query_trail_extensions` | `"QueryTrail{type}Extensions"` |
+//! | `args_type` | `"{type}{field}Args"` |
+//! | `arguments_type` | `"{type}{field}Arguments"` |
+//! | `field_enum` | `"{type}Field"` |
+//!
+//! `{type}` is replaced with the name of the GraphQL type and `{field}` with the name of the field,
+//! snake cased for methods and camel cased for types. `query_trail_extensions` is also used for the
+//! `QueryTrail{type}ArgumentsExtensions` and `QueryTrail{type}DowncastExtensions` traits, and the
+//! `OwnedQueryTrail` extension traits get an `Owned` prefix.
+//!
+//! This makes it possible to generate several schemas in the same module, as long as they don't
+//! share any type names:
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate juniper;
+//! # use juniper::*;
+//! # use juniper_from_schema::graphql_schema_from_file;
+//! # fn main() {}
+//! # pub struct Context;
+//! # impl juniper::Context for Context {}
+//! # pub struct Mutation;
+//! # impl PublicMutationFields for Mutation {
+//! #     fn resolve_noop(&self, executor: &Executor<'_, Context>) -> juniper::FieldResult<&bool> {
+//! #         Ok(&true)
+//! #     }
+//! # }
+//! graphql_schema_from_file!(
+//!     "tests/schemas/doc_schema.graphql",
+//!     schema_name: "PublicSchema",
+//!     query_trails_module: "public_query_trails",
+//!     fields_trait: "Public{type}Fields",
+//!     field_method: "resolve_{field}",
+//! );
+//!
+//! pub struct Query;
+//!
+//! impl PublicQueryFields for Query {
+//!     fn resolve_hello_world(
+//!         &self,
+//!         executor: &Executor<'_, Context>,
+//!         name: String,
+//!     ) -> juniper::FieldResult<String> {
+//!         Ok(format!("Hello, {}!", name))
+//!     }
+//! }
+//! ```
+//!
+//! Glob imports of the `query_trails` modules, such as the one the generated code adds to the
+//! module, are ambiguous for items with the same name in both modules, such as the [`complexity`]
+//! function. Refer to those through the module instead, for example
+//! `public_query_trails::complexity`.
+//!
+//! [`graphql_schema!`] always uses the default names.
+//!
+//! [`complexity`]: #query-complexity
+//!
+//! # Customizing derives and attributes
+//!
+//! Additional derives and attributes can be added to the Rust types generated for enums, input
+//! objects, custom scalars, interfaces, and unions. To add them to a single type use
+//! `@juniper(derive: [...], attributes: [...])`:
+//!
+//! ```
+//! # #[macro_use]
+//! # extern crate juniper;
+//! # use juniper::*;
+//! # use juniper_from_schema::graphql_schema;
+//! # fn main() {}
+//! # pub struct Context;
+//! # impl juniper::Context for Context {}
+//! # pub struct Query;
+//! # impl QueryFields for Query {
+//! #     fn field_priority(&self, executor: &Executor<'_, Context>) -> FieldResult<Priority> {
+//! #         Ok(Priority::High)
+//! #     }
+//! # }
+//! graphql_schema! {
+//!     type Query {
+//!         priority: Priority! @juniper(ownership: "owned")
+//!     }
+//!
+//!     enum Priority @juniper(
+//!         derive: ["PartialOrd", "Ord", "serde::Serialize"],
+//!         attributes: ["#[serde(rename_all = \"lowercase\")]"]
+//!     ) {
+//!         LOW
+//!         HIGH
+//!     }
+//!
+//!     schema { query: Query }
+//! }
+//! ```
+//!
+//! Each string in `attributes` must contain exactly one outer attribute. Attributes are placed after
+//! the derives, so they can be helper attributes such as `#[serde(...)]`.
+//!
+//! To add them to every type of a kind use these [`graphql_schema_from_file!`] configs:
+//!
+//! | Kind | Configs |
+//! |---|---|
+//! | Enums | `enum_derives`, `enum_attributes` |
+//! | Input objects | `input_object_derives`, `input_object_attributes` |
+//! | Custom scalars | `scalar_derives`, `scalar_attributes` |
+//! | Interfaces | `interface_derives`, `interface_attributes` |
+//! | Unions | `union_derives`, `union_attributes` |
+//!
+//! ```ignore
+//! graphql_schema_from_file!(
+//!     "schema.graphql",
+//!     input_object_derives: [serde::Deserialize],
+//!     input_object_attributes: [#[serde(rename_all = "camelCase")]],
+//! );
+//! ```
+//!
+//! Derives from the configs and the directive are combined. Derives that the generated code
+//! already has, such as `Debug` and `Clone`, are skipped so they don't conflict.
+//!
+//! The [special case scalars](#special-case-scalars) don't support derives or attributes since
+//! their Rust types are defined in other crates.
+//!
 //! # Inspecting the generated code
 //!
 //! If you wish to see exactly what code gets generated you can set the env var
@@ -1447,6 +2192,54 @@
 //!
 //! [feature]: https://doc.rust-lang.org/cargo/reference/specifying-dependencies.html#choosing-features
 //! [rustfmt]: https://github.com/rust-lang/rustfmt
+//!
+//! # Caching the generated code
+//!
+//! The code generated by `graphql_schema_from_file!` can be cached, so incremental builds that
+//! don't change the schema don't have to generate it again. Cached code is only reused if the
+//! schema file, the `error_type` and `context_type` configs, and the version of
+//! juniper-from-schema are all unchanged. Each invocation of the macro has a single cache file
+//! which is overwritten when the schema changes.
+//!
+//! The cache is stored in `JUNIPER_FROM_SCHEMA_CACHE_DIR` if it's set, and otherwise in
+//! `$CARGO_TARGET_DIR/juniper-from-schema` if `CARGO_TARGET_DIR` is set. Cargo doesn't tell
+//! procedural macros where the target directory is, so without either variable the cache is
+//! disabled. Both have to be absolute paths, since relative paths would be resolved against the
+//! directory of whichever crate is being compiled:
+//!
+//! ```bash
+//! JUNIPER_FROM_SCHEMA_CACHE_DIR=/tmp/schema-cache cargo build
+//! CARGO_TARGET_DIR="$PWD/target" cargo build
+//! ```
+//!
+//! Failing to write to the cache doesn't fail the build, but prints a warning. The cache can be
+//! disabled by setting `JUNIPER_FROM_SCHEMA_CACHE` to `0` when compiling, and it's always
+//! disabled when `JUNIPER_FROM_SCHEMA_DEBUG` is set.
+//!
+//! Schemas defined with `graphql_schema!` are not cached.
+//!
+//! # Machine readable errors
+//!
+//! Errors in your schema are printed as colored text by default. If you set the env var
+//! `JUNIPER_FROM_SCHEMA_DIAGNOSTICS` to `json` when compiling they're instead printed as a JSON
+//! array, which is easier for editors and CI to consume:
+//!
+//! ```bash
+//! JUNIPER_FROM_SCHEMA_DIAGNOSTICS=json cargo build
+//! ```
+//!
+//! Each error contains `file`, `line`, `column`, `severity`, `code`, `message`, `notes`, and
+//! `suggestions`. The codes are stable so they can be used to look up or filter specific errors.
+//!
+//! The same errors can be checked without compiling using the `juniper-from-schema` binary from
+//! [juniper-from-schema-code-gen](https://crates.io/crates/juniper-from-schema-code-gen):
+//!
+//! ```bash
+//! juniper-from-schema check --json schema.graphql
+//! ```
+//!
+//! If the macro invocation changes the names of generated items, pass the same configs with
+//! `--naming`, such as `--naming field_enum={type}SelectedField`, since some errors depend on them.
 
 #![deny(
     missing_docs,
@@ -1457,15 +2250,34 @@
 )]
 #![doc(html_root_url = "https://docs.rs/juniper-from-schema/0.5.2")]
 
-use juniper::{DefaultScalarValue, LookAheadSelection};
+use juniper::{DefaultScalarValue, LookAheadSelection, LookAheadValue};
 use std::marker::PhantomData;
 
-pub use juniper_from_schema_code_gen::{graphql_schema, graphql_schema_from_file};
+mod cache_key;
+mod conversions;
+mod cow;
+mod owned;
+mod sql;
+
+#[doc(hidden)]
+pub use conversions::{
+    custom_scalar_from_input_value, custom_scalar_from_look_ahead_value, parse_custom_scalar,
+    resolve_custom_scalar, FromDefaultScalarValue, FromLookAheadValue,
+};
+#[doc(hidden)]
+pub use cow::CowField;
+pub use juniper_from_schema_proc_macro::{graphql_schema, graphql_schema_from_file};
+#[doc(hidden)]
+pub use owned::OwnedSelection;
+pub use owned::{DowncastOwnedQueryTrail, OwnedQueryTrail};
+pub use sql::{SqlColumn, SqlJoin, SqlProjection};
 
 /// A type used to parameterize `QueryTrail` to know that `walk` has been called.
+#[derive(Debug, Clone, Copy)]
 pub struct Walked;
 
 /// A type used to parameterize `QueryTrail` to know that `walk` has *not* been called.
+#[derive(Debug, Clone, Copy)]
 pub struct NotWalked;
 
 /// A wrapper around a `juniper::LookAheadSelection` with methods for each possible child.
@@ -1505,11 +2317,366 @@ impl<'a, T, K> QueryTrail<'a, T, K> {
     }
 }
 
+impl<'a, T, K> QueryTrail<'a, T, K> {
+    /// Walk into a descendant of the trail by a path of GraphQL field names separated by `.`,
+    /// such as `"posts.author"`.
+    ///
+    /// This returns the raw `juniper::LookAheadSelection`, not a `QueryTrail`, since the type of
+    /// the descendant isn't known at compile time. The generated `QueryTrail` methods are therefore
+    /// not available on the result. Returns `None` if any field along the path isn't part of the
+    /// query.
+    pub fn walk_path(&self, path: &str) -> Option<&'a LookAheadSelection<'a, DefaultScalarValue>> {
+        use juniper::LookAheadMethods;
+
+        path.split('.')
+            .filter(|segment| !segment.is_empty())
+            .try_fold(self.look_ahead?, |look_ahead, segment| {
+                look_ahead.select_child(segment)
+            })
+    }
+}
+
+impl<'a, T, K> QueryTrail<'a, T, K> {
+    // These have names that are unlikely to clash with the generated field methods, since
+    // inherent methods take precedence over trait methods.
+
+    #[doc(hidden)]
+    pub fn child_selected(&self, name: &str) -> bool {
+        self.child_look_ahead(name).is_some()
+    }
+
+    #[doc(hidden)]
+    pub fn child_trail<U>(&self, name: &str) -> QueryTrail<'a, U, NotWalked> {
+        QueryTrail {
+            look_ahead: self.child_look_ahead(name),
+            node_type: PhantomData,
+            walked: NotWalked,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn child_arguments(&self, name: &str) -> Option<SelectionRef<'a>> {
+        self.child_look_ahead(name).map(SelectionRef::Borrowed)
+    }
+
+    fn child_look_ahead(
+        &self,
+        name: &str,
+    ) -> Option<&'a LookAheadSelection<'a, DefaultScalarValue>> {
+        use juniper::LookAheadMethods;
+
+        self.look_ahead
+            .and_then(|look_ahead| look_ahead.select_child(name))
+    }
+}
+
+impl<'a, T> QueryTrail<'a, T, Walked> {
+    #[doc(hidden)]
+    pub fn unchecked_downcast<U>(&self) -> QueryTrail<'a, U, Walked> {
+        QueryTrail {
+            look_ahead: self.look_ahead,
+            node_type: PhantomData,
+            walked: Walked,
+        }
+    }
+
+    /// Downcast an interface or union trail if the query might select fields specific to
+    /// `type_name`.
+    ///
+    /// That is the case if the query has an inline fragment on `type_name` selecting one of
+    /// `type_fields`, or selects one of `type_specific_fields`, which can only happen through a
+    /// named fragment. These must be fields no other possible type has, since selections from
+    /// named fragments apply to all types. Used by the generated `as_*` methods.
+    #[doc(hidden)]
+    pub fn downcast_if_selected<U>(
+        &self,
+        type_name: &str,
+        type_fields: &[&str],
+        type_specific_fields: &[&str],
+    ) -> Option<QueryTrail<'a, U, Walked>> {
+        use juniper::LookAheadMethods;
+
+        let look_ahead = self.look_ahead?;
+
+        // `juniper` doesn't say which type a child selection applies to, so compare with the
+        // selections that apply to all types. No GraphQL type is named "".
+        //
+        // `juniper` marks the last child added by an inline fragment with the type of the
+        // fragment after each of its selections. If a selection is skipped with `@skip` or
+        // `@include` that is a child of an earlier fragment, possibly on another type, so only
+        // children the type actually has count.
+        let untyped = look_ahead.for_explicit_type("");
+        let typed = look_ahead.for_explicit_type(type_name);
+        let inline_fragment = type_fields.iter().chain(Some(&"__typename")).any(|field| {
+            typed.select_child(field).is_some() && untyped.select_child(field).is_none()
+        });
+
+        // The type `juniper` gives children can be wrong, as described above, so look for type
+        // specific fields in all children.
+        let named_fragment = type_specific_fields
+            .iter()
+            .any(|field| look_ahead.select_child(field).is_some());
+
+        let selected = inline_fragment || named_fragment;
+
+        if selected {
+            Some(QueryTrail {
+                look_ahead: Some(look_ahead),
+                node_type: PhantomData,
+                walked: Walked,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/// Convert from one type of `QueryTrail` to another. Used for converting interface and union
+/// trails into concrete subtypes.
+///
+/// Implemented by the generated code for each interface and union and the types they can be.
+pub trait DowncastQueryTrail<'a, T> {
+    /// Perform the downcast.
+    fn downcast(self) -> QueryTrail<'a, T, Walked>;
+}
+
+/// The selection the generated `{Type}{Field}Args` structs read arguments from.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub enum SelectionRef<'a> {
+    Borrowed(&'a LookAheadSelection<'a, DefaultScalarValue>),
+    Owned(&'a OwnedSelection),
+}
+
+impl<'a> SelectionRef<'a> {
+    #[allow(missing_docs)]
+    pub fn argument(self, name: &str) -> Option<LookAheadValue<'a, DefaultScalarValue>> {
+        use juniper::LookAheadMethods;
+
+        match self {
+            SelectionRef::Borrowed(look_ahead) => look_ahead
+                .arguments()
+                .iter()
+                .find(|arg| arg.name() == name)
+                .map(|arg| arg.value().clone()),
+            SelectionRef::Owned(selection) => selection.argument(name),
+        }
+    }
+}
+
+/// Description of the fields of a GraphQL type, used to print and copy `QueryTrail`s.
+///
+/// Implemented for every object, interface, and union type by the generated code.
+#[doc(hidden)]
+pub trait QueryTrailFields {
+    #[allow(missing_docs)]
+    fn query_trail_fields() -> &'static [QueryTrailField];
+
+    /// The types implementing an interface or the members of a union.
+    fn query_trail_possible_types() -> &'static [&'static str] {
+        &[]
+    }
+}
+
+#[doc(hidden)]
+#[allow(missing_docs)]
+pub struct QueryTrailField {
+    pub name: &'static str,
+    pub children: fn() -> &'static [QueryTrailField],
+    pub possible_types: fn() -> &'static [&'static str],
+}
+
+#[doc(hidden)]
+#[allow(missing_docs)]
+pub fn no_query_trail_fields() -> &'static [QueryTrailField] {
+    &[]
+}
+
+#[doc(hidden)]
+#[allow(missing_docs)]
+pub fn no_query_trail_possible_types() -> &'static [&'static str] {
+    &[]
+}
+
+/// Read an `Int` argument used as a `@cost` multiplier. Negative values count as zero.
+#[doc(hidden)]
+pub fn cost_multiplier(
+    look_ahead: &LookAheadSelection<'_, DefaultScalarValue>,
+    name: &str,
+    default: u64,
+) -> u64 {
+    use juniper::LookAheadMethods;
+
+    match look_ahead.argument(name).map(|arg| arg.value()) {
+        Some(LookAheadValue::Scalar(DefaultScalarValue::Int(value))) => (*value).max(0) as u64,
+        _ => default,
+    }
+}
+
+/// Prints the fields selected in the query as a tree, such as `QueryTrail { id, posts { title } }`.
+///
+/// Fields are printed in the order they're defined in the schema, using their names from the
+/// schema even if they're aliased in the query.
+impl<'a, T, K> std::fmt::Debug for QueryTrail<'a, T, K>
+where
+    T: QueryTrailFields,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.look_ahead {
+            Some(look_ahead) => {
+                write!(f, "QueryTrail ")?;
+                DebugSelection {
+                    look_ahead,
+                    fields: T::query_trail_fields(),
+                }
+                .fmt(f)
+            }
+            None => write!(f, "QueryTrail(None)"),
+        }
+    }
+}
+
+struct DebugSelection<'a> {
+    look_ahead: &'a LookAheadSelection<'a, DefaultScalarValue>,
+    fields: &'static [QueryTrailField],
+}
+
+impl<'a> std::fmt::Debug for DebugSelection<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        use juniper::LookAheadMethods;
+
+        let mut set = f.debug_set();
+        for field in self.fields {
+            if let Some(look_ahead) = self.look_ahead.select_child(field.name) {
+                set.entry(&DebugField {
+                    name: field.name,
+                    selection: DebugSelection {
+                        look_ahead,
+                        fields: (field.children)(),
+                    },
+                });
+            }
+        }
+        set.finish()
+    }
+}
+
+struct DebugField<'a> {
+    name: &'static str,
+    selection: DebugSelection<'a>,
+}
+
+impl<'a> std::fmt::Debug for DebugField<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if self.selection.fields.is_empty() {
+            Ok(())
+        } else {
+            write!(f, " ")?;
+            self.selection.fmt(f)
+        }
+    }
+}
+
+/// Error returned when an argument in an incoming query cannot be converted into the Rust type
+/// generated for it.
+///
+/// Returned by the argument methods on `{Type}{Field}Args` structs. It implements
+/// `std::fmt::Display` so `?` will convert it into a `juniper::FieldError`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ConversionError {
+    path: Vec<String>,
+    expected: String,
+    reason: String,
+}
+
+impl ConversionError {
+    #[doc(hidden)]
+    pub fn new(expected: impl Into<String>, reason: impl Into<String>) -> Self {
+        ConversionError {
+            path: Vec::new(),
+            expected: expected.into(),
+            reason: reason.into(),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn unexpected_value(
+        expected: impl Into<String>,
+        value: &juniper::LookAheadValue<'_, DefaultScalarValue>,
+    ) -> Self {
+        let got = match value {
+            juniper::LookAheadValue::Null => "null",
+            juniper::LookAheadValue::Scalar(DefaultScalarValue::Int(_)) => "Int",
+            juniper::LookAheadValue::Scalar(DefaultScalarValue::Float(_)) => "Float",
+            juniper::LookAheadValue::Scalar(DefaultScalarValue::String(_)) => "String",
+            juniper::LookAheadValue::Scalar(DefaultScalarValue::Boolean(_)) => "Boolean",
+            juniper::LookAheadValue::Enum(_) => "enum",
+            juniper::LookAheadValue::List(_) => "list",
+            juniper::LookAheadValue::Object(_) => "object",
+        };
+        ConversionError::new(expected, format!("got `{}`", got))
+    }
+
+    #[doc(hidden)]
+    pub fn in_path(mut self, segment: impl Into<String>) -> Self {
+        self.path.insert(0, segment.into());
+        self
+    }
+
+    /// The path to the value that failed to convert, starting with the argument name.
+    ///
+    /// Object keys and list indices are separate segments, so for the argument `filter: { ids:
+    /// [1, "two"] }` the path would be `["filter", "ids", "1"]`.
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
+    /// The GraphQL type that was expected, such as `Int` or `[String!]`.
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    /// Why the conversion failed, such as ``got `null` ``.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+impl std::fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed converting argument `{}`. Expected `{}`, {}",
+            self.path.join("."),
+            self.expected,
+            self.reason,
+        )
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
 #[cfg(test)]
 mod test {
     #[allow(unused_imports)]
     use super::*;
 
+    #[test]
+    fn test_conversion_error_display() {
+        let error = ConversionError::new("Int", "got `String`")
+            .in_path("1")
+            .in_path("ids")
+            .in_path("filter");
+
+        assert_eq!(error.path(), &["filter", "ids", "1"]);
+        assert_eq!(
+            error.to_string(),
+            "Failed converting argument `filter.ids.1`. Expected `Int`, got `String`"
+        );
+    }
+
     #[rustversion::nightly]
     #[test]
     fn test_compile_pass() {
@@ -0,0 +1,242 @@
+use crate::{QueryTrail, QueryTrailField, QueryTrailFields, Walked};
+use juniper::{DefaultScalarValue, LookAheadMethods, LookAheadSelection, LookAheadValue};
+use std::{fmt, marker::PhantomData, sync::Arc};
+
+/// An owned version of `QueryTrail`, created with [`QueryTrail::to_owned`].
+///
+/// It doesn't borrow from the executor, is `Send + Sync + 'static`, and cheap to clone. So it can
+/// be moved into spawned tasks, sent to batching loaders, or stored in caches.
+///
+/// Has the same generated methods as `QueryTrail` through the `OwnedQueryTrail{Type}Extensions`
+/// and `OwnedQueryTrail{Type}ArgumentsExtensions` traits.
+///
+/// [`QueryTrail::to_owned`]: struct.QueryTrail.html#method.to_owned
+pub struct OwnedQueryTrail<T, K> {
+    #[doc(hidden)]
+    pub selection: Option<Arc<OwnedSelection>>,
+    #[doc(hidden)]
+    pub node_type: PhantomData<fn() -> T>,
+    #[doc(hidden)]
+    pub walked: K,
+}
+
+impl<'a, T, K> QueryTrail<'a, T, K>
+where
+    T: QueryTrailFields,
+    K: Copy,
+{
+    /// Copy the selection into an [`OwnedQueryTrail`](struct.OwnedQueryTrail.html).
+    ///
+    /// Only the fields known from the schema are copied. If a field is selected more than once
+    /// under different aliases only the first selection is copied, just like `QueryTrail` only
+    /// sees the first selection.
+    pub fn to_owned(&self) -> OwnedQueryTrail<T, K> {
+        OwnedQueryTrail {
+            selection: self.look_ahead.map(|look_ahead| {
+                Arc::new(OwnedSelection::new(look_ahead, T::query_trail_fields()))
+            }),
+            node_type: PhantomData,
+            walked: self.walked,
+        }
+    }
+}
+
+impl<T> OwnedQueryTrail<T, crate::NotWalked> {
+    /// Check if the trail is present in the query being executed
+    pub fn walk(self) -> Option<OwnedQueryTrail<T, Walked>> {
+        match self.selection {
+            Some(selection) => Some(OwnedQueryTrail {
+                selection: Some(selection),
+                node_type: PhantomData,
+                walked: Walked,
+            }),
+            None => None,
+        }
+    }
+}
+
+impl<T, K> OwnedQueryTrail<T, K> {
+    #[doc(hidden)]
+    pub fn child_selected(&self, name: &str) -> bool {
+        self.child_selection(name).is_some()
+    }
+
+    #[doc(hidden)]
+    pub fn child<U>(&self, name: &str) -> OwnedQueryTrail<U, crate::NotWalked> {
+        OwnedQueryTrail {
+            selection: self.child_selection(name).cloned(),
+            node_type: PhantomData,
+            walked: crate::NotWalked,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn child_arguments(&self, name: &str) -> Option<crate::SelectionRef<'_>> {
+        self.child_selection(name)
+            .map(|selection| crate::SelectionRef::Owned(selection))
+    }
+
+    #[doc(hidden)]
+    pub fn cast<U>(&self) -> OwnedQueryTrail<U, Walked> {
+        OwnedQueryTrail {
+            selection: self.selection.clone(),
+            node_type: PhantomData,
+            walked: Walked,
+        }
+    }
+
+    fn child_selection(&self, name: &str) -> Option<&Arc<OwnedSelection>> {
+        self.selection.as_ref().and_then(|selection| {
+            selection
+                .children
+                .iter()
+                .find(|(child_name, _)| child_name == name)
+                .map(|(_, child)| child)
+        })
+    }
+}
+
+/// Convert from one type of `OwnedQueryTrail` to another. Used for converting interface and union
+/// trails into concrete subtypes.
+///
+/// Implemented by the generated code for each interface and union and the types they can be.
+pub trait DowncastOwnedQueryTrail<T> {
+    /// Perform the downcast.
+    fn downcast(self) -> OwnedQueryTrail<T, Walked>;
+}
+
+impl<T, K: Clone> Clone for OwnedQueryTrail<T, K> {
+    fn clone(&self) -> Self {
+        OwnedQueryTrail {
+            selection: self.selection.clone(),
+            node_type: PhantomData,
+            walked: self.walked.clone(),
+        }
+    }
+}
+
+/// Prints the fields selected in the query as a tree, such as
+/// `OwnedQueryTrail { id, posts { title } }`.
+impl<T, K> fmt::Debug for OwnedQueryTrail<T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.selection {
+            Some(selection) => {
+                write!(f, "OwnedQueryTrail ")?;
+                selection.fmt(f)
+            }
+            None => write!(f, "OwnedQueryTrail(None)"),
+        }
+    }
+}
+
+/// An owned copy of a `juniper::LookAheadSelection`.
+#[doc(hidden)]
+#[derive(Clone, PartialEq)]
+pub struct OwnedSelection {
+    arguments: Vec<(String, OwnedValue)>,
+    children: Vec<(String, Arc<OwnedSelection>)>,
+}
+
+impl OwnedSelection {
+    fn new(
+        look_ahead: &LookAheadSelection<'_, DefaultScalarValue>,
+        fields: &'static [QueryTrailField],
+    ) -> Self {
+        OwnedSelection {
+            arguments: look_ahead
+                .arguments()
+                .iter()
+                .map(|arg| (arg.name().to_string(), OwnedValue::new(arg.value())))
+                .collect(),
+            children: fields
+                .iter()
+                .filter_map(|field| {
+                    look_ahead.select_child(field.name).map(|child| {
+                        let child = OwnedSelection::new(child, (field.children)());
+                        (field.name.to_string(), Arc::new(child))
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn argument(&self, name: &str) -> Option<LookAheadValue<'_, DefaultScalarValue>> {
+        self.arguments
+            .iter()
+            .find(|(arg_name, _)| arg_name == name)
+            .map(|(_, value)| value.as_look_ahead_value())
+    }
+}
+
+impl fmt::Debug for OwnedSelection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        struct DebugChild<'a>(&'a str, &'a OwnedSelection);
+
+        impl<'a> fmt::Debug for DebugChild<'a> {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}", self.0)?;
+
+                if self.1.children.is_empty() {
+                    Ok(())
+                } else {
+                    write!(f, " ")?;
+                    self.1.fmt(f)
+                }
+            }
+        }
+
+        f.debug_set()
+            .entries(
+                self.children
+                    .iter()
+                    .map(|(name, child)| DebugChild(name, child)),
+            )
+            .finish()
+    }
+}
+
+/// An owned copy of a `juniper::LookAheadValue`.
+#[derive(Debug, Clone, PartialEq)]
+enum OwnedValue {
+    Null,
+    Scalar(DefaultScalarValue),
+    Enum(String),
+    List(Vec<OwnedValue>),
+    Object(Vec<(String, OwnedValue)>),
+}
+
+impl OwnedValue {
+    fn new(value: &LookAheadValue<'_, DefaultScalarValue>) -> Self {
+        match value {
+            LookAheadValue::Null => OwnedValue::Null,
+            LookAheadValue::Scalar(scalar) => OwnedValue::Scalar((*scalar).clone()),
+            LookAheadValue::Enum(name) => OwnedValue::Enum(name.to_string()),
+            LookAheadValue::List(values) => {
+                OwnedValue::List(values.iter().map(OwnedValue::new).collect())
+            }
+            LookAheadValue::Object(fields) => OwnedValue::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), OwnedValue::new(value)))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn as_look_ahead_value(&self) -> LookAheadValue<'_, DefaultScalarValue> {
+        match self {
+            OwnedValue::Null => LookAheadValue::Null,
+            OwnedValue::Scalar(scalar) => LookAheadValue::Scalar(scalar),
+            OwnedValue::Enum(name) => LookAheadValue::Enum(name),
+            OwnedValue::List(values) => {
+                LookAheadValue::List(values.iter().map(OwnedValue::as_look_ahead_value).collect())
+            }
+            OwnedValue::Object(fields) => LookAheadValue::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_look_ahead_value()))
+                    .collect(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,81 @@
+/// The columns to select and the tables to join to resolve a query, computed from `@sql`
+/// directives in the schema.
+///
+/// Returned by the generated `QueryTrailSql::sql_projection` method. See the
+/// ["SQL projections"](index.html#sql-projections) section for more details.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SqlProjection {
+    /// The table of the type the projection was made from.
+    pub table: &'static str,
+    /// The columns to select, in the order they're defined in the schema. Columns of joined
+    /// tables come after the column of the field that joined them.
+    pub columns: Vec<SqlColumn>,
+    /// The tables to join, parents before children.
+    pub joins: Vec<SqlJoin>,
+}
+
+/// A column to select.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SqlColumn {
+    /// The table the column belongs to. Either the table the projection was made from, or the
+    /// alias of a join.
+    pub table: String,
+    /// The name of the column.
+    pub column: &'static str,
+}
+
+/// A table to join.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SqlJoin {
+    /// The table to join.
+    pub table: &'static str,
+    /// The alias to join the table as. It's the alias of the parent table followed by the name of
+    /// the field that joined it, such as `users_friends`, so a table can be joined more than once.
+    pub alias: String,
+    /// The join condition as written in the schema, with `{alias}` replaced by the alias of the
+    /// joined table and `{parent}` by the alias of the parent table.
+    pub on: String,
+}
+
+impl SqlProjection {
+    #[doc(hidden)]
+    pub fn new(table: &'static str) -> Self {
+        SqlProjection {
+            table,
+            columns: Vec::new(),
+            joins: Vec::new(),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn add_column(&mut self, table: &str, column: &'static str) {
+        let column = SqlColumn {
+            table: table.to_string(),
+            column,
+        };
+        if !self.columns.contains(&column) {
+            self.columns.push(column);
+        }
+    }
+
+    /// Add a join and return its alias.
+    #[doc(hidden)]
+    pub fn add_join(
+        &mut self,
+        parent: &str,
+        field: &'static str,
+        table: &'static str,
+        on: &'static str,
+    ) -> String {
+        let alias = format!("{}_{}", parent, field);
+        let join = SqlJoin {
+            table,
+            on: on.replace("{alias}", &alias).replace("{parent}", parent),
+            alias: alias.clone(),
+        };
+        if !self.joins.contains(&join) {
+            self.joins.push(join);
+        }
+        alias
+    }
+}
@@ -0,0 +1,159 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::graphql_schema;
+use serde_json::json;
+
+pub struct Context;
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      users(first: Int = 2, nameContains: String): [User!]! @juniper(ownership: "owned")
+      user(id: Int!): User @juniper(ownership: "owned", arguments_struct: false)
+      node(id: Int!): Node @juniper(ownership: "owned")
+    }
+
+    type User implements Node {
+      id: Int! @juniper(ownership: "owned")
+      name(uppercase: Boolean = false): String! @juniper(ownership: "owned")
+      greeting: String! @juniper(ownership: "owned")
+    }
+
+    interface Node {
+      id: Int! @juniper(ownership: "owned")
+      name(uppercase: Boolean = false): String! @juniper(ownership: "owned")
+    }
+
+    schema @juniper(arguments_struct: true) {
+      query: Query
+    }
+}
+
+fn all_users() -> Vec<User> {
+    vec![
+        User {
+            id: 1,
+            name: "Alice".to_string(),
+        },
+        User {
+            id: 2,
+            name: "Bob".to_string(),
+        },
+        User {
+            id: 3,
+            name: "Carol".to_string(),
+        },
+    ]
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_users<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+        args: QueryUsersArguments,
+    ) -> FieldResult<Vec<User>> {
+        // The arguments of fields further down the query use the same struct.
+        if let Some(name_args) = trail.name_args() {
+            let name_args: UserNameArguments = name_args.to_arguments()?;
+            assert!(name_args.uppercase);
+        }
+
+        Ok(all_users()
+            .into_iter()
+            .filter(|user| match &args.name_contains {
+                Some(name) => user.name.contains(name.as_str()),
+                None => true,
+            })
+            .take(args.first as usize)
+            .collect())
+    }
+
+    fn field_user<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+        id: i32,
+    ) -> FieldResult<Option<User>> {
+        Ok(all_users().into_iter().find(|user| user.id == id))
+    }
+
+    fn field_node<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Node, Walked>,
+        args: QueryNodeArguments,
+    ) -> FieldResult<Option<Node>> {
+        Ok(all_users()
+            .into_iter()
+            .find(|user| user.id == args.id)
+            .map(Node::from))
+    }
+}
+
+pub struct User {
+    id: i32,
+    name: String,
+}
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(self.id)
+    }
+
+    fn field_name<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        args: UserNameArguments,
+    ) -> FieldResult<String> {
+        if args.uppercase {
+            Ok(self.name.to_uppercase())
+        } else {
+            Ok(self.name.clone())
+        }
+    }
+
+    fn field_greeting<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<String> {
+        Ok(format!("Hello {}", self.name))
+    }
+}
+
+fn run_query(query: &str) -> serde_json::Value {
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, EmptyMutation::new()),
+        &Variables::new(),
+        &Context,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    serde_json::from_str(&serde_json::to_string(&juniper_value).unwrap()).unwrap()
+}
+
+#[test]
+fn test_default_values_are_applied() {
+    let value = run_query("query { users { id } }");
+    assert_eq!(value, json!({ "users": [{ "id": 1 }, { "id": 2 }] }));
+}
+
+#[test]
+fn test_passing_arguments() {
+    let value = run_query(
+        r#"query {
+            users(first: 5, nameContains: "o") { name(uppercase: true) }
+            user(id: 1) { greeting }
+            node(id: 3) { name }
+        }"#,
+    );
+    assert_eq!(
+        value,
+        json!({
+            "users": [{ "name": "BOB" }, { "name": "CAROL" }],
+            "user": { "greeting": "Hello Alice" },
+            "node": { "name": "Carol" },
+        })
+    );
+}
@@ -0,0 +1,138 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::graphql_schema;
+use serde_json::json;
+
+pub struct Context;
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      greet(name: String! @juniper(ownership: "borrowed")): String! @juniper(ownership: "owned")
+
+      greetMaybe(name: String @juniper(ownership: "borrowed")): String!
+        @juniper(ownership: "owned")
+
+      greetDefault(name: String = "World" @juniper(ownership: "borrowed")): String!
+        @juniper(ownership: "owned")
+
+      sum(numbers: [Int!]! @juniper(ownership: "borrowed")): Int! @juniper(ownership: "owned")
+
+      sumMaybe(numbers: [Int!] @juniper(ownership: "borrowed")): Int!
+        @juniper(ownership: "owned")
+
+      search(query: Search! @juniper(ownership: "borrowed")): String!
+        @juniper(ownership: "owned")
+
+      searchMaybe(query: Search @juniper(ownership: "borrowed")): String!
+        @juniper(ownership: "owned")
+
+      owned(name: String! @juniper(ownership: "owned")): String! @juniper(ownership: "owned")
+    }
+
+    input Search {
+      text: String!
+    }
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_greet<'a>(&self, executor: &Executor<'a, Context>, name: &str) -> FieldResult<String> {
+        Ok(format!("Hello {}", name))
+    }
+
+    fn field_greet_maybe<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        name: Option<&str>,
+    ) -> FieldResult<String> {
+        Ok(format!("Hello {}", name.unwrap_or("nobody")))
+    }
+
+    fn field_greet_default<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        name: &str,
+    ) -> FieldResult<String> {
+        Ok(format!("Hello {}", name))
+    }
+
+    fn field_sum<'a>(&self, executor: &Executor<'a, Context>, numbers: &[i32]) -> FieldResult<i32> {
+        Ok(numbers.iter().sum())
+    }
+
+    fn field_sum_maybe<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        numbers: Option<&[i32]>,
+    ) -> FieldResult<i32> {
+        Ok(numbers.map(|numbers| numbers.iter().sum()).unwrap_or(0))
+    }
+
+    fn field_search<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        query: &Search,
+    ) -> FieldResult<String> {
+        Ok(query.text.clone())
+    }
+
+    fn field_search_maybe<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        query: Option<&Search>,
+    ) -> FieldResult<String> {
+        Ok(query.map(|query| query.text.clone()).unwrap_or_default())
+    }
+
+    fn field_owned<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        name: String,
+    ) -> FieldResult<String> {
+        Ok(name)
+    }
+}
+
+#[test]
+fn test_borrowed_arguments() {
+    let (juniper_value, errors) = juniper::execute(
+        r#"query {
+            greet(name: "Alice")
+            greetMaybe
+            greetDefault
+            sum(numbers: [1, 2, 3])
+            sumMaybe(numbers: [4])
+            search(query: { text: "juniper" })
+            searchMaybe
+            owned(name: "Bob")
+        }"#,
+        None,
+        &Schema::new(Query, EmptyMutation::new()),
+        &Variables::new(),
+        &Context,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+
+    let value: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&juniper_value).unwrap()).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "greet": "Hello Alice",
+            "greetMaybe": "Hello nobody",
+            "greetDefault": "Hello World",
+            "sum": 6,
+            "sumMaybe": 4,
+            "search": "juniper",
+            "searchMaybe": "",
+            "owned": "Bob",
+        })
+    );
+}
@@ -0,0 +1,91 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::graphql_schema;
+use serde_json::json;
+
+pub struct Context {
+    current_user: User,
+    tags: Vec<String>,
+}
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      currentUser: User! @juniper(ownership: "borrowed_from_context")
+      tags: [String!]! @juniper(ownership: "borrowed_from_context", infallible: true)
+    }
+
+    type User {
+      id: Int! @juniper(ownership: "owned")
+      name: String! @juniper(ownership: "borrowed_from_context")
+    }
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    // The trail gets its own lifetime since it doesn't live as long as the executor.
+    fn field_current_user<'a, 'r>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'r, User, Walked>,
+    ) -> FieldResult<&'a User> {
+        Ok(&executor.context().current_user)
+    }
+
+    fn field_tags<'a>(&self, executor: &Executor<'a, Context>) -> &'a Vec<String> {
+        &executor.context().tags
+    }
+}
+
+pub struct User {
+    id: i32,
+    name: String,
+}
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(self.id)
+    }
+
+    // The user is stored in the context, so its name can be returned with the lifetime of the
+    // executor.
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&'a String> {
+        Ok(&executor.context().current_user.name)
+    }
+}
+
+#[test]
+fn test_returning_references_into_the_context() {
+    let context = Context {
+        current_user: User {
+            id: 1,
+            name: "Bob".to_string(),
+        },
+        tags: vec!["admin".to_string()],
+    };
+
+    let (juniper_value, errors) = juniper::execute(
+        "query { currentUser { id name } tags }",
+        None,
+        &Schema::new(Query, EmptyMutation::new()),
+        &Variables::new(),
+        &context,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+
+    let value: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&juniper_value).unwrap()).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "currentUser": { "id": 1, "name": "Bob" },
+            "tags": ["admin"],
+        }),
+    );
+}
@@ -0,0 +1,245 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, InputValue, Variables};
+use juniper_from_schema::{graphql_schema, graphql_schema_from_file};
+use std::sync::Mutex;
+
+pub struct Context {
+    seen: Mutex<Vec<(Option<String>, Option<u64>)>>,
+}
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      user(id: Int!, filter: Filter): User! @juniper(ownership: "owned")
+      search: [SearchResult!]! @juniper(ownership: "owned")
+    }
+
+    input Filter {
+      tags: [String!]!
+      published: Boolean!
+    }
+
+    type User {
+      id: Int! @juniper(ownership: "owned")
+      name: String!
+      posts(first: Int!): [Post!]! @juniper(ownership: "owned")
+    }
+
+    type Post {
+      title: String!
+    }
+
+    union SearchResult = User | Post
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_user<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+        id: i32,
+        filter: Option<Filter>,
+    ) -> FieldResult<User> {
+        executor
+            .context()
+            .seen
+            .lock()
+            .unwrap()
+            .push((trail.cache_key(), trail.cache_hash()));
+        Ok(User {
+            name: "Bob".to_string(),
+        })
+    }
+
+    fn field_search<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, SearchResult, Walked>,
+    ) -> FieldResult<Vec<SearchResult>> {
+        executor
+            .context()
+            .seen
+            .lock()
+            .unwrap()
+            .push((trail.cache_key(), trail.cache_hash()));
+        Ok(vec![])
+    }
+}
+
+pub struct User {
+    name: String,
+}
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(1)
+    }
+
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.name)
+    }
+
+    fn field_posts<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Post, Walked>,
+        first: i32,
+    ) -> FieldResult<Vec<Post>> {
+        Ok(vec![])
+    }
+}
+
+pub struct Post;
+
+impl PostFields for Post {
+    fn field_title<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn test_cache_key() {
+    let (key, _) = query(
+        r#"
+        query {
+            user(id: 1, filter: { tags: ["a", "b"], published: true }) {
+                userName: name
+                posts(first: 10) { title }
+                id
+            }
+        }
+        "#,
+        Variables::new(),
+    );
+    assert_eq!(
+        key.unwrap(),
+        r#"(filter:{published:true,tags:["a","b"]},id:1){id,posts(first:10){title},userName:name}"#,
+    );
+}
+
+#[test]
+fn test_equivalent_queries_have_the_same_key() {
+    let (key, hash) = query(
+        "query { user(id: 1) { id posts(first: 10) { title } } }",
+        Variables::new(),
+    );
+
+    let (other_key, other_hash) = query(
+        r#"
+        query($first: Int!) {
+            user(id: 1) {
+                ...UserFields
+                id
+            }
+        }
+
+        fragment UserFields on User {
+            posts(first: $first) {
+                title
+            }
+        }
+        "#,
+        vec![("first".to_string(), InputValue::scalar(10))]
+            .into_iter()
+            .collect(),
+    );
+
+    assert!(key.is_some());
+    assert_eq!(key, other_key);
+    assert_eq!(hash, other_hash);
+}
+
+#[test]
+fn test_different_queries_have_different_keys() {
+    let (key, hash) = query("query { user(id: 1) { id } }", Variables::new());
+    let (other_key, other_hash) = query("query { user(id: 2) { id } }", Variables::new());
+    assert_ne!(key, other_key);
+    assert_ne!(hash, other_hash);
+
+    let (other_key, other_hash) = query("query { user(id: 1) { userId: id } }", Variables::new());
+    assert_ne!(key, other_key);
+    assert_ne!(hash, other_hash);
+}
+
+#[test]
+fn test_cache_key_of_union() {
+    let (key, _) = query(
+        "query { search { __typename ... on Post { title } } }",
+        Variables::new(),
+    );
+    assert_eq!(key.unwrap(), "{...on Post,__typename,title}");
+}
+
+#[test]
+fn test_cache_hash_is_stable() {
+    let (_, hash) = query("query { user(id: 1) { id } }", Variables::new());
+    assert_eq!(hash, Some(0xcde9_90cf_dab9_993d));
+}
+
+#[test]
+fn test_fields_selected_under_several_aliases_have_no_key() {
+    let (key, hash) = query(
+        "query { user(id: 1) { a: posts(first: 5) { title } b: posts(first: 100) { title } } }",
+        Variables::new(),
+    );
+    assert_eq!(key, None);
+    assert_eq!(hash, None);
+
+    // Only differs from the query above in the arguments of the second alias.
+    let (key, _) = query(
+        "query { user(id: 1) { a: posts(first: 5) { title } b: posts(first: 7) { title } } }",
+        Variables::new(),
+    );
+    assert_eq!(key, None);
+
+    let (key, _) = query(
+        "query { user(id: 1) { first: name second: name } }",
+        Variables::new(),
+    );
+    assert_eq!(key, None);
+}
+
+#[test]
+fn test_fields_with_subselections_selected_several_times_have_no_key() {
+    let (key, _) = query(
+        r#"
+        query {
+            user(id: 1) {
+                posts(first: 10) { title }
+                ...UserFields
+            }
+        }
+
+        fragment UserFields on User {
+            posts(first: 10) { title }
+        }
+        "#,
+        Variables::new(),
+    );
+    assert_eq!(key, None);
+
+    let (key, _) = query("query { user(id: 1) { id id } }", Variables::new());
+    assert_eq!(key.unwrap(), "(id:1){id}");
+}
+
+fn query(query: &str, vars: Variables) -> (Option<String>, Option<u64>) {
+    let ctx = Context {
+        seen: Mutex::new(Vec::new()),
+    };
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, juniper::EmptyMutation::new()),
+        &vars,
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    ctx.seen.into_inner().unwrap().pop().unwrap()
+}
@@ -0,0 +1,19 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+include!("../compile_pass/setup.rs");
+
+juniper_from_schema::graphql_schema! {
+    type Query {
+      slice(names: [String!]! @juniper(ownership: "slice")): Int!
+      search(query: Search!): Int!
+      count(name: String! @juniper(ownership: "borrowed")): Int!
+        @juniper(arguments_struct: true)
+    }
+
+    input Search {
+      text: String! @juniper(ownership: "borrowed")
+    }
+
+    schema {
+      query: Query
+    }
+}
@@ -0,0 +1,22 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+include!("../compile_pass/setup.rs");
+
+juniper_from_schema::graphql_schema! {
+    type Query {
+        strings(first: String): [String!]! @cost(multipliers: ["first"])
+    }
+
+    schema { query: Query }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_strings<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        first: Option<String>,
+    ) -> FieldResult<&Vec<String>> {
+        unimplemented!()
+    }
+}
@@ -0,0 +1,12 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+include!("../compile_pass/setup.rs");
+
+juniper_from_schema::graphql_schema! {
+    type Query @juniper(ownership: "shared") {
+        string: String!
+    }
+
+    schema @juniper(derive: ["Debug"]) {
+        query: Query
+    }
+}
@@ -0,0 +1,7 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+include!("../compile_pass/setup.rs");
+
+juniper_from_schema::graphql_schema_from_file!(
+    "../../../juniper-from-schema/tests/schemas/customizing_context_name.graphql",
+    fields_trait: "AdminFields"
+);
@@ -0,0 +1,13 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+include!("../compile_pass/setup.rs");
+
+juniper_from_schema::graphql_schema! {
+    type Query {
+      count: Int! @juniper(ownership: "str")
+      name: String! @juniper(ownership: "iter")
+    }
+
+    schema {
+      query: Query
+    }
+}
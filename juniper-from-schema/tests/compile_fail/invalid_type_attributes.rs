@@ -0,0 +1,16 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+include!("../compile_pass/setup.rs");
+
+juniper_from_schema::graphql_schema! {
+    type Query {
+        color: Color! @juniper(ownership: "owned")
+    }
+
+    enum Color @juniper(derive: ["not a path"]) {
+        RED
+    }
+
+    scalar Url @juniper(attributes: ["#[serde(transparent)]"])
+
+    schema { query: Query }
+}
@@ -11,8 +11,14 @@ juniper_from_schema::graphql_schema! {
     directive @juniper(
         ownership: String = "borrowed",
         infallible: Boolean = false,
-        with_time_zone: Boolean = true
-    ) on FIELD_DEFINITION
+        with_time_zone: Boolean = true,
+        trail: Boolean = true,
+        executor: Boolean = true,
+        arguments_struct: Boolean = false,
+        derive: [String!],
+        attributes: [String!]
+    ) on FIELD_DEFINITION | ARGUMENT_DEFINITION | SCHEMA | OBJECT | SCALAR | INTERFACE | UNION
+      | ENUM | INPUT_OBJECT
 }
 
 pub struct Query;
@@ -0,0 +1,181 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, InputValue, Variables};
+use juniper_from_schema::{graphql_schema, graphql_schema_from_file};
+use std::sync::Mutex;
+
+pub struct Context {
+    seen: Mutex<Vec<u64>>,
+}
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      user: User! @juniper(ownership: "owned")
+      search(first: Int = 10): [SearchResult!]! @juniper(ownership: "owned") @cost(multipliers: ["first"])
+      characters: [Character!]! @juniper(ownership: "owned")
+    }
+
+    type User {
+      id: Int! @juniper(ownership: "owned") @cost(value: 0)
+      posts(first: Int!): [Post!]! @juniper(ownership: "owned") @cost(value: 2, multipliers: ["first"])
+    }
+
+    type Post {
+      title: String!
+      author: User! @juniper(ownership: "owned") @cost(value: 5)
+    }
+
+    union SearchResult = User | Post
+
+    interface Character {
+      id: Int! @juniper(ownership: "owned")
+    }
+
+    type Human implements Character {
+      id: Int! @juniper(ownership: "owned")
+      friends: [Character!]! @juniper(ownership: "owned") @cost(value: 10)
+    }
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_user<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+    ) -> FieldResult<User> {
+        let mut seen = executor.context().seen.lock().unwrap();
+        seen.push(complexity(QueryField::User, &executor.look_ahead()));
+        seen.push(trail.complexity());
+        Ok(User)
+    }
+
+    fn field_search<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, SearchResult, Walked>,
+        first: i32,
+    ) -> FieldResult<Vec<SearchResult>> {
+        executor
+            .context()
+            .seen
+            .lock()
+            .unwrap()
+            .push(complexity(QueryField::Search, &executor.look_ahead()));
+        Ok(vec![])
+    }
+
+    fn field_characters<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Character, Walked>,
+    ) -> FieldResult<Vec<Character>> {
+        executor
+            .context()
+            .seen
+            .lock()
+            .unwrap()
+            .push(complexity(QueryField::Characters, &executor.look_ahead()));
+        Ok(vec![])
+    }
+}
+
+pub struct User;
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(1)
+    }
+
+    fn field_posts<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Post, Walked>,
+        first: i32,
+    ) -> FieldResult<Vec<Post>> {
+        Ok(vec![])
+    }
+}
+
+pub struct Post;
+
+impl PostFields for Post {
+    fn field_title<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+
+    fn field_author<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+    ) -> FieldResult<User> {
+        unimplemented!()
+    }
+}
+
+pub struct Human;
+
+impl HumanFields for Human {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(1)
+    }
+
+    fn field_friends<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Character, Walked>,
+    ) -> FieldResult<Vec<Character>> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn test_complexity_of_nested_lists() {
+    // user: 1 + posts (2 + title 1 + author (5 + id 0)) * 3
+    let seen = query("{ user { id posts(first: 3) { title author { id } } } }");
+    assert_eq!(seen, vec![25, 24]);
+}
+
+#[test]
+fn test_complexity_uses_default_argument_values() {
+    // search: (1 + max(User, Post)) * 10, where Post costs 1 for `title`
+    let seen = query("{ search { ... on Post { title } } }");
+    assert_eq!(seen, vec![20]);
+
+    let seen = query("{ search(first: 2) { ... on Post { title } } }");
+    assert_eq!(seen, vec![4]);
+}
+
+#[test]
+fn test_complexity_of_interfaces() {
+    // characters: 1 + Human (id 1 + friends (10 + id 1))
+    let seen = query("{ characters { id ... on Human { friends { id } } } }");
+    assert_eq!(seen, vec![13]);
+}
+
+#[test]
+fn test_negative_multipliers_count_as_zero() {
+    let seen = query("{ user { posts(first: -1) { title } } }");
+    assert_eq!(seen, vec![1, 0]);
+}
+
+fn query(query: &str) -> Vec<u64> {
+    let ctx = Context {
+        seen: Mutex::new(Vec::new()),
+    };
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, juniper::EmptyMutation::new()),
+        &Variables::new(),
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    ctx.seen.into_inner().unwrap()
+}
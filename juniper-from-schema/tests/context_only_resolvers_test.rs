@@ -0,0 +1,123 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::graphql_schema;
+use serde_json::json;
+
+pub struct Context {
+    users: Vec<User>,
+}
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      users: [User!]! @juniper(ownership: "borrowed_from_context")
+      userCount: Int! @juniper(ownership: "owned")
+      node(id: Int!): Node @juniper(ownership: "owned", executor: true)
+    }
+
+    type User implements Node @juniper(executor: true) {
+      id: Int! @juniper(ownership: "owned")
+      name: String!
+    }
+
+    interface Node @juniper(executor: true) {
+      id: Int! @juniper(ownership: "owned")
+    }
+
+    schema @juniper(executor: false) {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_users<'a, 'r>(
+        &self,
+        ctx: &'a Context,
+        trail: &QueryTrail<'r, User, Walked>,
+    ) -> FieldResult<&'a Vec<User>> {
+        Ok(&ctx.users)
+    }
+
+    fn field_user_count(&self, ctx: &Context) -> FieldResult<i32> {
+        Ok(ctx.users.len() as i32)
+    }
+
+    fn field_node<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Node, Walked>,
+        id: i32,
+    ) -> FieldResult<Option<Node>> {
+        let user = executor
+            .context()
+            .users
+            .iter()
+            .find(|user| user.id == id)
+            .cloned();
+        Ok(user.map(Node::from))
+    }
+}
+
+#[derive(Clone)]
+pub struct User {
+    id: i32,
+    name: String,
+}
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(self.id)
+    }
+
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.name)
+    }
+}
+
+fn context() -> Context {
+    Context {
+        users: vec![
+            User {
+                id: 1,
+                name: "Alice".to_string(),
+            },
+            User {
+                id: 2,
+                name: "Bob".to_string(),
+            },
+        ],
+    }
+}
+
+#[test]
+fn test_context_only_resolvers() {
+    let context = context();
+    let (juniper_value, errors) = juniper::execute(
+        "query { users { name } userCount node(id: 2) { id } }",
+        None,
+        &Schema::new(Query, EmptyMutation::new()),
+        &Variables::new(),
+        &context,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+
+    let value: serde_json::Value =
+        serde_json::from_str(&serde_json::to_string(&juniper_value).unwrap()).unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "users": [{ "name": "Alice" }, { "name": "Bob" }],
+            "userCount": 2,
+            "node": { "id": 2 },
+        }),
+    );
+}
+
+#[test]
+fn test_resolvers_can_be_called_without_juniper() {
+    let context = context();
+    assert_eq!(Query.field_user_count(&context).unwrap(), 2);
+}
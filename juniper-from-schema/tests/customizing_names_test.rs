@@ -0,0 +1,140 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::graphql_schema_from_file;
+use serde_json::json;
+
+pub struct Context;
+impl juniper::Context for Context {}
+
+graphql_schema_from_file!("tests/schemas/public_schema.graphql");
+
+graphql_schema_from_file!(
+    "tests/schemas/admin_schema.graphql",
+    schema_name: "AdminSchema",
+    query_trails_module: "admin_query_trails",
+    fields_trait: "Admin{type}Resolvers",
+    field_method: "resolve_{field}",
+    query_trail_extensions: "AdminQueryTrail{type}Extensions",
+    args_type: "Admin{type}{field}Arguments",
+    field_enum: "Admin{type}SelectedField"
+);
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_user<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+        id: i32,
+    ) -> FieldResult<User> {
+        Ok(User {
+            id,
+            name: format!("User {}", id),
+        })
+    }
+}
+
+pub struct User {
+    id: i32,
+    name: String,
+}
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(self.id)
+    }
+
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.name)
+    }
+}
+
+pub struct AdminQuery;
+
+impl AdminAdminQueryResolvers for AdminQuery {
+    fn resolve_users<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, AdminUser, Walked>,
+        first: i32,
+    ) -> FieldResult<Vec<AdminUser>> {
+        // The extension traits are renamed too.
+        assert!(AdminQueryTrailAdminUserExtensions::email(trail));
+
+        Ok((0..first)
+            .map(|id| AdminUser {
+                id,
+                email: format!("user{}@example.com", id),
+            })
+            .collect())
+    }
+}
+
+pub struct AdminUser {
+    id: i32,
+    email: String,
+}
+
+impl AdminAdminUserResolvers for AdminUser {
+    fn resolve_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(self.id)
+    }
+
+    fn resolve_email<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.email)
+    }
+}
+
+#[test]
+fn test_two_schemas_in_one_module() {
+    let value = execute(
+        "query { user(id: 1) { id name } }",
+        &Schema::new(Query, EmptyMutation::new()),
+    );
+    assert_eq!(value, json!({ "user": { "id": 1, "name": "User 1" } }));
+
+    let value = execute(
+        "query { users(first: 2) { id email } }",
+        &AdminSchema::new(AdminQuery, EmptyMutation::new()),
+    );
+    assert_eq!(
+        value,
+        json!({
+            "users": [
+                { "id": 0, "email": "user0@example.com" },
+                { "id": 1, "email": "user1@example.com" },
+            ],
+        }),
+    );
+}
+
+#[test]
+fn test_renamed_query_trail_items() {
+    fn admin_args<'a>(
+        trail: &'a QueryTrail<'a, AdminQuery, Walked>,
+    ) -> Option<AdminAdminQueryUsersArguments<'a>> {
+        admin_query_trails::AdminQueryTrailAdminQueryArgumentsExtensions::users_args(trail)
+    }
+
+    fn public_args<'a>(trail: &'a QueryTrail<'a, Query, Walked>) -> Option<QueryUserArgs<'a>> {
+        query_trails::QueryTrailQueryArgumentsExtensions::user_args(trail)
+    }
+
+    fn admin_fields(trail: &QueryTrail<AdminUser, Walked>) -> Vec<AdminAdminUserSelectedField> {
+        trail.selected_fields().collect()
+    }
+}
+
+fn execute<Q>(
+    query: &str,
+    schema: &juniper::RootNode<'static, Q, EmptyMutation<Context>>,
+) -> serde_json::Value
+where
+    Q: juniper::GraphQLType<Context = Context, TypeInfo = ()>,
+{
+    let (juniper_value, errors) =
+        juniper::execute(query, None, schema, &Variables::new(), &Context).unwrap();
+    assert_eq!(errors, vec![]);
+    serde_json::from_str(&serde_json::to_string(&juniper_value).unwrap()).unwrap()
+}
@@ -0,0 +1,90 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::graphql_schema_from_file;
+use serde_json::json;
+
+pub struct Context;
+impl juniper::Context for Context {}
+
+graphql_schema_from_file!(
+    "tests/schemas/derives_schema.graphql",
+    input_object_derives: [serde::Serialize, serde::Deserialize],
+    input_object_attributes: [#[serde(rename_all = "camelCase")]],
+    enum_derives: [serde::Serialize, serde::Deserialize],
+    scalar_derives: [serde::Serialize, serde::Deserialize, Debug],
+);
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_search<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, SearchResult, Walked>,
+        filter: SearchFilter,
+        after: Option<Cursor>,
+    ) -> FieldResult<Vec<SearchResult>> {
+        Ok(vec![SearchResult::from(User {
+            name: filter.text_query,
+        })])
+    }
+}
+
+#[derive(Debug)]
+pub struct User {
+    name: String,
+}
+
+impl UserFields for User {
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.name)
+    }
+}
+
+#[test]
+fn test_derives_and_attributes_from_configs() {
+    let filter = SearchFilter {
+        text_query: "rust".to_string(),
+        sort_order: Some(SortOrder::Descending),
+    };
+
+    let value = serde_json::to_value(&filter).unwrap();
+    assert_eq!(
+        value,
+        json!({ "textQuery": "rust", "sortOrder": "Descending" })
+    );
+
+    let filter: SearchFilter = serde_json::from_value(value).unwrap();
+    assert_eq!(filter.text_query, "rust");
+    assert_eq!(filter.sort_order, Some(SortOrder::Descending));
+
+    let cursor = Cursor::new("abc");
+    assert_eq!(serde_json::to_value(&cursor).unwrap(), json!("abc"));
+}
+
+#[test]
+fn test_derives_from_directives() {
+    assert!(SortOrder::Ascending < SortOrder::Descending);
+
+    let result = SearchResult::from(User {
+        name: "Bob".to_string(),
+    });
+    assert_eq!(format!("{:?}", result), r#"User(User { name: "Bob" })"#);
+}
+
+#[test]
+fn test_schema_still_works() {
+    let (value, errors) = juniper::execute(
+        r#"query { search(filter: { textQuery: "rust" }) { ... on User { name } } }"#,
+        None,
+        &Schema::new(Query, EmptyMutation::new()),
+        &Variables::new(),
+        &Context,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    assert_eq!(
+        serde_json::to_value(&value).unwrap(),
+        json!({ "search": [{ "name": "rust" }] })
+    );
+}
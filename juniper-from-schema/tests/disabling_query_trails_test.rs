@@ -0,0 +1,108 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::{graphql_schema, graphql_schema_from_file};
+use serde_json::json;
+
+pub struct Context;
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      users(first: Int!): [User!]! @juniper(ownership: "owned", trail: false)
+      user: User! @juniper(ownership: "owned")
+      search: [SearchResult!]! @juniper(ownership: "owned", trail: false)
+    }
+
+    type User {
+      id: Int! @juniper(ownership: "owned", trail: false)
+      name: String!
+    }
+
+    union SearchResult = User
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_users<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        first: i32,
+    ) -> FieldResult<Vec<User>> {
+        Ok((0..first).map(User::new).collect())
+    }
+
+    fn field_user<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+    ) -> FieldResult<User> {
+        Ok(User::new(1))
+    }
+
+    fn field_search<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<Vec<SearchResult>> {
+        Ok(vec![SearchResult::from(User::new(2))])
+    }
+}
+
+pub struct User {
+    id: i32,
+    name: String,
+}
+
+impl User {
+    fn new(id: i32) -> Self {
+        User {
+            id,
+            name: format!("User {}", id),
+        }
+    }
+}
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(self.id)
+    }
+
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.name)
+    }
+}
+
+#[test]
+fn test_fields_without_trails() {
+    let value = query("query { users(first: 2) { id name } search { ... on User { id } } }");
+    assert_eq!(
+        value,
+        json!({
+            "users": [
+                { "id": 0, "name": "User 0" },
+                { "id": 1, "name": "User 1" },
+            ],
+            "search": [{ "id": 2 }],
+        }),
+    );
+}
+
+#[test]
+fn test_other_fields_still_get_trails() {
+    let value = query("query { user { id } }");
+    assert_eq!(value, json!({ "user": { "id": 1 } }));
+}
+
+fn query(query: &str) -> serde_json::Value {
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, EmptyMutation::new()),
+        &Variables::new(),
+        &Context,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    serde_json::from_str(&serde_json::to_string(&juniper_value).unwrap()).unwrap()
+}
@@ -0,0 +1,112 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::{graphql_schema, graphql_schema_from_file};
+use serde_json::json;
+
+pub struct Context;
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      user(id: Int!): User! @juniper(trail: false)
+      node: Node!
+      version: String! @juniper(ownership: "borrowed")
+    }
+
+    type User implements Node @juniper(infallible: true) {
+      id: Int!
+      name: String!
+      email: String @juniper(ownership: "as_ref", infallible: false)
+    }
+
+    interface Node @juniper(infallible: true, trail: false) {
+      id: Int!
+    }
+
+    schema @juniper(ownership: "owned") {
+      query: Query
+    }
+}
+
+pub struct Query {
+    version: String,
+}
+
+impl QueryFields for Query {
+    fn field_user<'a>(&self, executor: &Executor<'a, Context>, id: i32) -> FieldResult<User> {
+        Ok(User::new(id))
+    }
+
+    fn field_node<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Node, Walked>,
+    ) -> FieldResult<Node> {
+        Ok(Node::from(User::new(2)))
+    }
+
+    fn field_version<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.version)
+    }
+}
+
+pub struct User {
+    id: i32,
+    name: String,
+    email: Option<String>,
+}
+
+impl User {
+    fn new(id: i32) -> Self {
+        User {
+            id,
+            name: format!("User {}", id),
+            email: None,
+        }
+    }
+}
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> i32 {
+        self.id
+    }
+
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> String {
+        self.name.clone()
+    }
+
+    fn field_email<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<Option<&String>> {
+        Ok(self.email.as_ref())
+    }
+}
+
+#[test]
+fn test_fields_use_the_defaults() {
+    let value = query("query { user(id: 1) { id name email } node { id } version }");
+    assert_eq!(
+        value,
+        json!({
+            "user": { "id": 1, "name": "User 1", "email": null },
+            "node": { "id": 2 },
+            "version": "1.0",
+        }),
+    );
+}
+
+fn query(query: &str) -> serde_json::Value {
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(
+            Query {
+                version: "1.0".to_string(),
+            },
+            EmptyMutation::new(),
+        ),
+        &Variables::new(),
+        &Context,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    serde_json::from_str(&serde_json::to_string(&juniper_value).unwrap()).unwrap()
+}
@@ -0,0 +1,167 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, LookAheadMethods, Variables};
+use juniper_from_schema::{graphql_schema, graphql_schema_from_file};
+
+pub struct Context;
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      user: User! @juniper(ownership: "owned")
+      search: [SearchResult!]! @juniper(ownership: "owned")
+    }
+
+    type User {
+      id: Int! @juniper(ownership: "owned")
+      name: String!
+      posts: [Post!]! @juniper(ownership: "owned")
+    }
+
+    type Post {
+      title: String!
+      author: User! @juniper(ownership: "owned")
+    }
+
+    union SearchResult = User | Post
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_user<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+    ) -> FieldResult<User> {
+        assert_eq!(
+            trail.selected_fields().collect::<Vec<_>>(),
+            vec![UserField::Id, UserField::Name, UserField::Posts],
+        );
+        assert_eq!(UserField::Posts.graphql_name(), "posts");
+
+        assert!(trail.posts().selected_fields().eq(vec![PostField::Author]));
+        assert_eq!(trail.posts().author().selected_fields().count(), 1);
+
+        let author = trail.walk_path("posts.author").unwrap();
+        assert!(author.has_child("name"));
+        assert!(trail.walk_path("posts.title").is_none());
+        assert!(trail.walk_path("").is_some());
+
+        assert_eq!(
+            format!("{:?}", trail),
+            "QueryTrail {id, name, posts {author {name}}}",
+        );
+        assert_eq!(format!("{:?}", trail.posts().title()), "false");
+        assert_eq!(
+            format!("{:?}", trail.posts().author().posts()),
+            "QueryTrail(None)"
+        );
+
+        Ok(User {
+            name: "Bob".to_string(),
+        })
+    }
+
+    fn field_search<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, SearchResult, Walked>,
+    ) -> FieldResult<Vec<SearchResult>> {
+        assert_eq!(
+            trail.selected_fields().collect::<Vec<_>>(),
+            vec![SearchResultField::Title],
+        );
+        assert_eq!(format!("{:?}", trail), "QueryTrail {title}");
+
+        Ok(vec![])
+    }
+}
+
+pub struct User {
+    name: String,
+}
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(1)
+    }
+
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.name)
+    }
+
+    fn field_posts<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Post, Walked>,
+    ) -> FieldResult<Vec<Post>> {
+        Ok(vec![])
+    }
+}
+
+pub struct Post;
+
+impl PostFields for Post {
+    fn field_title<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+
+    fn field_author<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+    ) -> FieldResult<User> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn test_inspecting_query_trails() {
+    query(
+        r#"
+        query {
+            user {
+                userName: name
+                posts {
+                    author {
+                        name
+                    }
+                }
+                id
+            }
+        }
+        "#,
+    );
+}
+
+#[test]
+fn test_inspecting_union_query_trails() {
+    query(
+        r#"
+        query {
+            search {
+                ... on Post {
+                    title
+                }
+            }
+        }
+        "#,
+    );
+}
+
+fn query(query: &str) {
+    let ctx = Context;
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, juniper::EmptyMutation::new()),
+        &Variables::new(),
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+}
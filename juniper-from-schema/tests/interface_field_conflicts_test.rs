@@ -0,0 +1,151 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::{graphql_schema, graphql_schema_from_file};
+use std::sync::Mutex;
+
+pub struct Context {
+    seen: Mutex<Vec<String>>,
+}
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      media: [Media!]! @juniper(ownership: "owned")
+    }
+
+    interface Media {
+      url: String!
+    }
+
+    type Photo implements Media {
+      url: String!
+      size: Dimensions! @juniper(ownership: "owned")
+    }
+
+    type Video implements Media {
+      url: String!
+      size: Duration! @juniper(ownership: "owned")
+    }
+
+    type Dimensions {
+      width: Int! @juniper(ownership: "owned")
+    }
+
+    type Duration {
+      seconds: Int! @juniper(ownership: "owned")
+    }
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_media<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Media, Walked>,
+    ) -> FieldResult<Vec<Media>> {
+        let owned = trail.to_owned();
+        let photo: OwnedQueryTrail<Photo, Walked> = owned.clone().downcast();
+        let video: OwnedQueryTrail<Video, Walked> = owned.downcast();
+
+        executor.context().seen.lock().unwrap().push(format!(
+            "owned photo: {:?}, owned video: {:?}, cache key: {:?}",
+            photo,
+            video,
+            trail.cache_key(),
+        ));
+
+        Ok(vec![])
+    }
+}
+
+pub struct Photo;
+
+impl PhotoFields for Photo {
+    fn field_url<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+
+    fn field_size<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Dimensions, Walked>,
+    ) -> FieldResult<Dimensions> {
+        unimplemented!()
+    }
+}
+
+pub struct Video;
+
+impl VideoFields for Video {
+    fn field_url<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+
+    fn field_size<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Duration, Walked>,
+    ) -> FieldResult<Duration> {
+        unimplemented!()
+    }
+}
+
+pub struct Dimensions;
+
+impl DimensionsFields for Dimensions {
+    fn field_width<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        unimplemented!()
+    }
+}
+
+pub struct Duration;
+
+impl DurationFields for Duration {
+    fn field_seconds<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn test_conflicting_implementor_fields_keep_their_selections() {
+    let seen = query("query { media { ... on Photo { size { width } } } }");
+    assert_eq!(
+        seen,
+        vec![
+            "owned photo: OwnedQueryTrail {size {width}}, \
+             owned video: OwnedQueryTrail {size {width}}, \
+             cache key: Some(\"{...on Photo,size{width}}\")"
+        ],
+    );
+
+    let seen = query("query { media { ... on Video { size { seconds } } } }");
+    assert_eq!(
+        seen,
+        vec![
+            "owned photo: OwnedQueryTrail {size {seconds}}, \
+             owned video: OwnedQueryTrail {size {seconds}}, \
+             cache key: Some(\"{...on Video,size{seconds}}\")"
+        ],
+    );
+}
+
+fn query(query: &str) -> Vec<String> {
+    let ctx = Context {
+        seen: Mutex::new(Vec::new()),
+    };
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, juniper::EmptyMutation::new()),
+        &Variables::new(),
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    ctx.seen.into_inner().unwrap()
+}
@@ -0,0 +1,161 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::{graphql_schema, graphql_schema_from_file};
+use std::sync::Mutex;
+
+pub struct Context {
+    seen: Mutex<Vec<String>>,
+}
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      user: User! @juniper(ownership: "owned")
+      characters: [Character!]! @juniper(ownership: "owned")
+    }
+
+    type User {
+      id: Int! @juniper(ownership: "owned")
+      posts(first: Int!, tags: [String!] = ["rust"]): [Post!]! @juniper(ownership: "owned")
+    }
+
+    type Post {
+      title: String!
+    }
+
+    interface Character {
+      id: Int! @juniper(ownership: "owned")
+    }
+
+    type Human implements Character {
+      id: Int! @juniper(ownership: "owned")
+      homePlanet: String!
+    }
+
+    schema {
+      query: Query
+    }
+}
+
+fn assert_send_sync_static<T: Send + Sync + 'static>(t: T) -> T {
+    t
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_user<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+    ) -> FieldResult<User> {
+        let owned = assert_send_sync_static(trail.to_owned());
+
+        let seen = std::thread::spawn(move || {
+            let args = owned.posts_args().unwrap();
+            format!(
+                "{:?} {:?} first: {}, tags: {:?}, title: {}",
+                owned,
+                owned.selected_fields().collect::<Vec<_>>(),
+                args.first().unwrap(),
+                args.tags().unwrap(),
+                owned.posts().walk().unwrap().title(),
+            )
+        })
+        .join()
+        .unwrap();
+
+        executor.context().seen.lock().unwrap().push(seen);
+
+        Ok(User)
+    }
+
+    fn field_characters<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Character, Walked>,
+    ) -> FieldResult<Vec<Character>> {
+        let owned = trail.to_owned();
+        let human: OwnedQueryTrail<Human, Walked> = owned.downcast();
+
+        executor.context().seen.lock().unwrap().push(format!(
+            "{:?} home planet: {}",
+            owned,
+            human.home_planet()
+        ));
+
+        Ok(vec![])
+    }
+}
+
+pub struct User;
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(1)
+    }
+
+    fn field_posts<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Post, Walked>,
+        first: i32,
+        tags: Vec<String>,
+    ) -> FieldResult<Vec<Post>> {
+        Ok(vec![])
+    }
+}
+
+pub struct Post;
+
+impl PostFields for Post {
+    fn field_title<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+}
+
+pub struct Human;
+
+impl HumanFields for Human {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(1)
+    }
+
+    fn field_home_planet<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn test_owned_query_trail() {
+    let seen = query("query { user { id posts(first: 5) { title } } }");
+    assert_eq!(
+        seen,
+        vec!["OwnedQueryTrail {id, posts {title}} [Id, Posts] first: 5, tags: [\"rust\"], title: true"],
+    );
+}
+
+#[test]
+fn test_downcasting_owned_query_trail() {
+    let seen = query("query { characters { id ... on Human { homePlanet } } }");
+    assert_eq!(
+        seen,
+        vec!["OwnedQueryTrail {id, homePlanet} home planet: true"]
+    );
+}
+
+fn query(query: &str) -> Vec<String> {
+    let ctx = Context {
+        seen: Mutex::new(Vec::new()),
+    };
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, juniper::EmptyMutation::new()),
+        &Variables::new(),
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    ctx.seen.into_inner().unwrap()
+}
@@ -0,0 +1,166 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::graphql_schema;
+use serde_json::json;
+use std::{borrow::Cow, sync::Arc};
+
+pub struct Context;
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      name: String! @juniper(ownership: "str")
+      nickname: String @juniper(ownership: "str")
+      tags: [String!]! @juniper(ownership: "slice")
+      aliases: [String!] @juniper(ownership: "slice")
+      user: User! @juniper(ownership: "arc", trail: false)
+      title: String! @juniper(ownership: "cow")
+      users: [User!]! @juniper(ownership: "cow", trail: false)
+      numbers: [Int!]! @juniper(ownership: "iter")
+      maybeNumbers: [Int] @juniper(ownership: "iter", infallible: true)
+      nested: [[String]] @juniper(ownership: "as_ref")
+    }
+
+    type User {
+      id: Int! @juniper(ownership: "owned")
+    }
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query {
+    name: String,
+    tags: Vec<String>,
+    user: Arc<User>,
+    users: Vec<User>,
+    nested: Option<Vec<Option<Vec<Option<String>>>>>,
+}
+
+impl QueryFields for Query {
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&str> {
+        Ok(&self.name)
+    }
+
+    fn field_nickname<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<Option<&str>> {
+        Ok(None)
+    }
+
+    fn field_tags<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&[String]> {
+        Ok(&self.tags)
+    }
+
+    fn field_aliases<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+    ) -> FieldResult<Option<&[String]>> {
+        Ok(Some(&self.tags[1..]))
+    }
+
+    fn field_user<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<Arc<User>> {
+        Ok(Arc::clone(&self.user))
+    }
+
+    fn field_title<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<Cow<'_, String>> {
+        Ok(Cow::Owned(format!("Hello, {}", self.name)))
+    }
+
+    fn field_users<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<Cow<'_, Vec<User>>> {
+        Ok(Cow::Borrowed(&self.users))
+    }
+
+    fn field_numbers<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+    ) -> FieldResult<impl Iterator<Item = i32>> {
+        Ok(self.users.iter().map(|user| user.id * 2))
+    }
+
+    fn field_maybe_numbers<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+    ) -> Option<impl Iterator<Item = Option<i32>>> {
+        Some(vec![Some(1), None].into_iter())
+    }
+
+    fn field_nested<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+    ) -> FieldResult<Option<Vec<Option<Vec<Option<&String>>>>>> {
+        Ok(self.nested.as_ref().map(|lists| {
+            lists
+                .iter()
+                .map(|list| {
+                    list.as_ref()
+                        .map(|list| list.iter().map(Option::as_ref).collect())
+                })
+                .collect()
+        }))
+    }
+}
+
+#[derive(Clone)]
+pub struct User {
+    id: i32,
+}
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(self.id)
+    }
+}
+
+#[test]
+fn test_ownership_modes() {
+    let query_root = Query {
+        name: "Bob".to_string(),
+        tags: vec!["a".to_string(), "b".to_string()],
+        user: Arc::new(User { id: 1 }),
+        users: vec![User { id: 2 }, User { id: 3 }],
+        nested: Some(vec![Some(vec![Some("x".to_string()), None]), None]),
+    };
+
+    let value = query(
+        query_root,
+        r#"
+        query {
+            name nickname tags aliases
+            user { id }
+            title
+            users { id }
+            numbers maybeNumbers
+            nested
+        }
+        "#,
+    );
+
+    assert_eq!(
+        value,
+        json!({
+            "name": "Bob",
+            "nickname": null,
+            "tags": ["a", "b"],
+            "aliases": ["b"],
+            "user": { "id": 1 },
+            "title": "Hello, Bob",
+            "users": [{ "id": 2 }, { "id": 3 }],
+            "numbers": [4, 6],
+            "maybeNumbers": [1, null],
+            "nested": [["x", null], null],
+        }),
+    );
+}
+
+fn query(query_root: Query, query: &str) -> serde_json::Value {
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(query_root, EmptyMutation::new()),
+        &Variables::new(),
+        &Context,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    serde_json::from_str(&serde_json::to_string(&juniper_value).unwrap()).unwrap()
+}
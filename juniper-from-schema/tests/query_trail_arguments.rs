@@ -85,53 +85,51 @@ impl QueryFields for Query {
         trail: &QueryTrail<'_, A, Walked>,
     ) -> FieldResult<A> {
         if let Some(c) = trail.b().c().walk() {
-            assert_eq!("foo".to_string(), c.field_with_arg_args().string_arg());
-            assert_eq!(None, c.field_with_arg_args().nullable_arg());
-            assert_eq!(
-                Some("bar".to_string()),
-                c.field_with_arg_args().nullable_arg2()
-            );
-            assert_eq!(1, c.field_with_arg_args().int_arg());
-            assert_eq!("2.5", c.field_with_arg_args().float_arg().to_string());
-            assert_eq!(false, c.field_with_arg_args().bool_arg());
-            assert_eq!(vec![1, 2, 3], c.field_with_arg_args().list_arg());
-            assert_eq!(Color::Red, c.field_with_arg_args().enum_arg());
-            assert_eq!(
-                "baz".to_string(),
-                c.field_with_arg_args().object_arg().value
-            );
+            let args = c.field_with_arg_args().unwrap();
+            assert_eq!("foo".to_string(), args.string_arg().unwrap());
+            assert_eq!(None, args.nullable_arg().unwrap());
+            assert_eq!(Some("bar".to_string()), args.nullable_arg2().unwrap());
+            assert_eq!(1, args.int_arg().unwrap());
+            assert_eq!("2.5", args.float_arg().unwrap().to_string());
+            assert!(!args.bool_arg().unwrap());
+            assert_eq!(vec![1, 2, 3], args.list_arg().unwrap());
+            assert_eq!(Color::Red, args.enum_arg().unwrap());
+            assert_eq!("baz".to_string(), args.object_arg().unwrap().value);
             assert_eq!(
                 Cursor("cursor-value".to_string()),
-                c.field_with_arg_args().cursor_arg()
+                args.cursor_arg().unwrap()
             );
-            assert_eq!(ID::new("id-value"), c.field_with_arg_args().id_arg());
+            assert_eq!(ID::new("id-value"), args.id_arg().unwrap());
             assert_eq!(
                 Url::parse("https://example.net").unwrap(),
-                c.field_with_arg_args().url_arg()
+                args.url_arg().unwrap()
             );
             assert_eq!(
                 Uuid::parse_str("46ebd0ee-0e6d-43c9-b90d-ccc35a913f3e").unwrap(),
-                c.field_with_arg_args().uuid_arg()
+                args.uuid_arg().unwrap()
             );
             assert_eq!(
                 NaiveDate::parse_from_str("2019-01-01", "%Y-%m-%d").unwrap(),
-                c.field_with_arg_args().date_arg()
+                args.date_arg().unwrap()
             );
             assert_eq!(
                 DateTime::parse_from_rfc3339("1996-12-19T16:39:57-08:00").unwrap(),
-                c.field_with_arg_args().date_time_arg()
+                args.date_time_arg().unwrap()
             );
             assert_eq!(
                 "value set in schema".to_string(),
-                c.field_with_arg_args().default_arg()
+                args.default_arg().unwrap()
             );
             assert_eq!(
                 "value set in query".to_string(),
-                c.field_with_arg_args().default_arg2()
+                args.default_arg2().unwrap()
             );
             assert_eq!(
                 "qux".to_string(),
-                c.field_with_arg_returning_type_args().string_arg()
+                c.field_with_arg_returning_type_args()
+                    .unwrap()
+                    .string_arg()
+                    .unwrap()
             );
         }
 
@@ -251,6 +249,43 @@ fn scalar_values() {
     );
 }
 
+#[test]
+fn conversion_errors() {
+    use juniper::{DefaultScalarValue, LookAheadValue};
+    use juniper_from_schema::FromLookAheadValue;
+
+    let int = DefaultScalarValue::Int(1);
+    let err = FromLookAheadValue::<String>::from(&LookAheadValue::Scalar(&int)).unwrap_err();
+    assert_eq!(err.expected(), "String");
+    assert_eq!(err.reason(), "got `Int`");
+
+    let err = FromLookAheadValue::<Color>::from(&LookAheadValue::Enum("GREEN")).unwrap_err();
+    assert_eq!(err.expected(), "Color");
+    assert_eq!(err.reason(), "got unknown value `GREEN`");
+
+    let url = DefaultScalarValue::String("not a url".to_string());
+    let err = FromLookAheadValue::<Url>::from(&LookAheadValue::Scalar(&url)).unwrap_err();
+    assert_eq!(err.expected(), "Url");
+
+    let list = LookAheadValue::List(vec![
+        LookAheadValue::Scalar(&int),
+        LookAheadValue::Enum("RED"),
+    ]);
+    let err = FromLookAheadValue::<Vec<i32>>::from(&list).unwrap_err();
+    assert_eq!(err.path(), &["1".to_string()]);
+
+    let object = LookAheadValue::Object(vec![("value", LookAheadValue::Scalar(&int))]);
+    let err = FromLookAheadValue::<InputObject>::from(&object).unwrap_err();
+    assert_eq!(err.path(), &["value".to_string()]);
+    assert_eq!(
+        err.to_string(),
+        "Failed converting argument `value`. Expected `String`, got `Int`"
+    );
+
+    let err = FromLookAheadValue::<InputObject>::from(&LookAheadValue::Object(vec![])).unwrap_err();
+    assert_eq!(err.reason(), "missing field `value`");
+}
+
 type Context = ();
 
 fn run_query(query: &str) -> Value {
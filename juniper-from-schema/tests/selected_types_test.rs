@@ -0,0 +1,262 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, InputValue, Variables};
+use juniper_from_schema::{graphql_schema, graphql_schema_from_file};
+use std::sync::Mutex;
+
+pub struct Context {
+    seen: Mutex<Vec<String>>,
+}
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      characters: [Character!]! @juniper(ownership: "owned")
+      search: [SearchResult!]! @juniper(ownership: "owned")
+      human: Human! @juniper(ownership: "owned")
+    }
+
+    interface Character {
+      id: Int! @juniper(ownership: "owned")
+    }
+
+    type Human implements Character {
+      id: Int! @juniper(ownership: "owned")
+      name: String!
+      homePlanet: String!
+    }
+
+    type Droid implements Character {
+      id: Int! @juniper(ownership: "owned")
+      name: String!
+      primaryFunction: String!
+    }
+
+    union SearchResult = Human | Droid
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_characters<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Character, Walked>,
+    ) -> FieldResult<Vec<Character>> {
+        executor.context().seen.lock().unwrap().push(format!(
+            "human: {}, droid: {}",
+            trail.as_human().is_some(),
+            trail.as_droid().is_some(),
+        ));
+        Ok(vec![])
+    }
+
+    fn field_search<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, SearchResult, Walked>,
+    ) -> FieldResult<Vec<SearchResult>> {
+        executor.context().seen.lock().unwrap().push(format!(
+            "human: {}, droid: {}",
+            trail.as_human().map(|human| human.home_planet()).is_some(),
+            trail.as_droid().is_some(),
+        ));
+        Ok(vec![])
+    }
+
+    fn field_human<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Human, Walked>,
+    ) -> FieldResult<Human> {
+        executor
+            .context()
+            .seen
+            .lock()
+            .unwrap()
+            .push(format!("home planet: {}", trail.home_planet()));
+        Ok(Human {
+            id: 1,
+            name: "Luke".to_string(),
+            home_planet: "Tatooine".to_string(),
+        })
+    }
+}
+
+pub struct Human {
+    id: i32,
+    name: String,
+    home_planet: String,
+}
+
+impl HumanFields for Human {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(self.id)
+    }
+
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.name)
+    }
+
+    fn field_home_planet<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.home_planet)
+    }
+}
+
+pub struct Droid {
+    id: i32,
+    name: String,
+    primary_function: String,
+}
+
+impl DroidFields for Droid {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        Ok(self.id)
+    }
+
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.name)
+    }
+
+    fn field_primary_function<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        Ok(&self.primary_function)
+    }
+}
+
+#[test]
+fn test_downcasting_without_fragments() {
+    let seen = query("query { characters { id } }", Variables::new());
+    assert_eq!(seen, vec!["human: false, droid: false"]);
+}
+
+#[test]
+fn test_downcasting_with_inline_fragment() {
+    let seen = query(
+        "query { characters { id ... on Droid { primaryFunction } } }",
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["human: false, droid: true"]);
+
+    let seen = query(
+        "query { search { ... on Human { homePlanet } } }",
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["human: true, droid: false"]);
+}
+
+#[test]
+fn test_downcasting_with_named_fragment() {
+    let seen = query(
+        r#"
+        query { characters { ...HumanFields } }
+        fragment HumanFields on Human { homePlanet }
+        "#,
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["human: true, droid: false"]);
+}
+
+#[test]
+fn test_downcasting_with_named_fragment_selecting_shared_fields() {
+    // `name` exists on both `Human` and `Droid`, so it doesn't say which type the fragment is on.
+    let seen = query(
+        r#"
+        query { characters { ...DroidFields } }
+        fragment DroidFields on Droid { name }
+        "#,
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["human: false, droid: false"]);
+
+    let seen = query(
+        r#"
+        query { search { ...DroidFields } }
+        fragment DroidFields on Droid { name }
+        "#,
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["human: false, droid: false"]);
+
+    let seen = query(
+        r#"
+        query { characters { ...DroidFields } }
+        fragment DroidFields on Droid { name primaryFunction }
+        "#,
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["human: false, droid: true"]);
+}
+
+#[test]
+fn test_downcasting_with_inline_fragment_selecting_shared_fields() {
+    let seen = query(
+        "query { characters { ... on Droid { name } } }",
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["human: false, droid: true"]);
+}
+
+#[test]
+fn test_downcasting_with_skipped_fragment() {
+    let seen = query(
+        "query { characters { id ... on Human @skip(if: true) { homePlanet } } }",
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["human: false, droid: false"]);
+}
+
+#[test]
+fn test_downcasting_with_skipped_field_in_fragment() {
+    // `juniper` gives `primaryFunction` the type of the `Human` fragment because the last
+    // selection of that fragment is skipped.
+    let seen = query(
+        "query { characters { ... on Droid { primaryFunction } ... on Human { homePlanet @skip(if: true) } } }",
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["human: false, droid: true"]);
+}
+
+#[test]
+fn test_include_and_skip() {
+    let query_str = r#"
+        query($withPlanet: Boolean!) {
+            human {
+                id
+                homePlanet @include(if: $withPlanet)
+            }
+        }
+    "#;
+
+    let mut vars = Variables::new();
+    vars.insert("withPlanet".to_string(), InputValue::scalar(false));
+    assert_eq!(query(query_str, vars), vec!["home planet: false"]);
+
+    let mut vars = Variables::new();
+    vars.insert("withPlanet".to_string(), InputValue::scalar(true));
+    assert_eq!(query(query_str, vars), vec!["home planet: true"]);
+
+    let seen = query(
+        "query { human { id homePlanet @skip(if: true) } }",
+        Variables::new(),
+    );
+    assert_eq!(seen, vec!["home planet: false"]);
+}
+
+fn query(query: &str, vars: Variables) -> Vec<String> {
+    let ctx = Context {
+        seen: Mutex::new(Vec::new()),
+    };
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, juniper::EmptyMutation::new()),
+        &vars,
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    ctx.seen.into_inner().unwrap()
+}
@@ -0,0 +1,257 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::{
+    graphql_schema, graphql_schema_from_file, SqlColumn, SqlJoin, SqlProjection,
+};
+use std::sync::Mutex;
+
+pub struct Context {
+    seen: Mutex<Vec<SqlProjection>>,
+}
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      users: [User!]! @juniper(ownership: "owned")
+    }
+
+    type User @sql(table: "users") {
+      id: Int! @juniper(ownership: "owned") @sql(column: "id")
+      name: String! @sql(column: "full_name")
+      postCount: Int! @juniper(ownership: "owned")
+      posts: [Post!]! @juniper(ownership: "owned") @sql(join: "{alias}.author_id = {parent}.id")
+      country: Country! @juniper(ownership: "owned") @sql(column: "country_id")
+      invitedBy: User @juniper(ownership: "owned")
+        @sql(column: "invited_by_id", join: "{alias}.id = {parent}.invited_by_id")
+    }
+
+    type Post @sql(table: "posts") {
+      title: String! @sql(column: "title")
+      comments: [Comment!]! @juniper(ownership: "owned")
+        @sql(join: "{alias}.post_id = {parent}.id")
+    }
+
+    type Comment @sql(table: "comments") {
+      body: String! @sql(column: "body")
+    }
+
+    type Country @sql(table: "countries") {
+      name: String! @sql(column: "name")
+    }
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_users<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+    ) -> FieldResult<Vec<User>> {
+        executor
+            .context()
+            .seen
+            .lock()
+            .unwrap()
+            .push(trail.sql_projection());
+        Ok(vec![])
+    }
+}
+
+pub struct User;
+
+impl UserFields for User {
+    fn field_id<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        unimplemented!()
+    }
+
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+
+    fn field_post_count<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        unimplemented!()
+    }
+
+    fn field_posts<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Post, Walked>,
+    ) -> FieldResult<Vec<Post>> {
+        unimplemented!()
+    }
+
+    fn field_country<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Country, Walked>,
+    ) -> FieldResult<Country> {
+        unimplemented!()
+    }
+
+    fn field_invited_by<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, User, Walked>,
+    ) -> FieldResult<Option<User>> {
+        unimplemented!()
+    }
+}
+
+pub struct Post;
+
+impl PostFields for Post {
+    fn field_title<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+
+    fn field_comments<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Comment, Walked>,
+    ) -> FieldResult<Vec<Comment>> {
+        unimplemented!()
+    }
+}
+
+pub struct Comment;
+
+impl CommentFields for Comment {
+    fn field_body<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+}
+
+pub struct Country;
+
+impl CountryFields for Country {
+    fn field_name<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+}
+
+fn column(table: &str, column: &'static str) -> SqlColumn {
+    SqlColumn {
+        table: table.to_string(),
+        column,
+    }
+}
+
+fn join(table: &'static str, alias: &str, on: &str) -> SqlJoin {
+    SqlJoin {
+        table,
+        alias: alias.to_string(),
+        on: on.to_string(),
+    }
+}
+
+#[test]
+fn test_columns_of_selected_fields() {
+    let projection = query("query { users { postCount name id } }");
+    assert_eq!(
+        projection,
+        SqlProjection {
+            table: "users",
+            columns: vec![column("users", "id"), column("users", "full_name")],
+            joins: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_nested_joins() {
+    let projection = query(
+        r#"
+        query {
+            users {
+                posts {
+                    comments { body }
+                    title
+                }
+                country { name }
+            }
+        }
+        "#,
+    );
+    assert_eq!(
+        projection,
+        SqlProjection {
+            table: "users",
+            columns: vec![
+                column("users_posts", "title"),
+                column("users_posts_comments", "body"),
+                column("users", "country_id"),
+            ],
+            joins: vec![
+                join("posts", "users_posts", "users_posts.author_id = users.id"),
+                join(
+                    "comments",
+                    "users_posts_comments",
+                    "users_posts_comments.post_id = users_posts.id",
+                ),
+            ],
+        }
+    );
+}
+
+#[test]
+fn test_self_joins() {
+    let projection = query(
+        r#"
+        query {
+            users {
+                name
+                invitedBy {
+                    name
+                    invitedBy { name }
+                }
+            }
+        }
+        "#,
+    );
+    assert_eq!(
+        projection,
+        SqlProjection {
+            table: "users",
+            columns: vec![
+                column("users", "full_name"),
+                column("users", "invited_by_id"),
+                column("users_invited_by", "full_name"),
+                column("users_invited_by", "invited_by_id"),
+                column("users_invited_by_invited_by", "full_name"),
+            ],
+            joins: vec![
+                join(
+                    "users",
+                    "users_invited_by",
+                    "users_invited_by.id = users.invited_by_id",
+                ),
+                join(
+                    "users",
+                    "users_invited_by_invited_by",
+                    "users_invited_by_invited_by.id = users_invited_by.invited_by_id",
+                ),
+            ],
+        }
+    );
+}
+
+fn query(query: &str) -> SqlProjection {
+    let ctx = Context {
+        seen: Mutex::new(Vec::new()),
+    };
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, juniper::EmptyMutation::new()),
+        &Variables::new(),
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    ctx.seen.into_inner().unwrap().pop().unwrap()
+}
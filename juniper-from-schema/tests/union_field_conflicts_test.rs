@@ -0,0 +1,132 @@
+#![allow(dead_code, unused_variables, unused_must_use, unused_imports)]
+use juniper::{EmptyMutation, Executor, FieldResult, Variables};
+use juniper_from_schema::{graphql_schema, graphql_schema_from_file};
+use std::sync::Mutex;
+
+pub struct Context {
+    seen: Mutex<Vec<String>>,
+}
+impl juniper::Context for Context {}
+
+graphql_schema! {
+    type Query {
+      media: [Media!]! @juniper(ownership: "owned")
+    }
+
+    union Media = Photo | Video
+
+    type Photo {
+      url: String!
+      size: Int! @juniper(ownership: "owned")
+    }
+
+    type Video {
+      url: String!
+      size: Duration! @juniper(ownership: "owned")
+    }
+
+    type Duration {
+      seconds: Int! @juniper(ownership: "owned")
+    }
+
+    schema {
+      query: Query
+    }
+}
+
+pub struct Query;
+
+impl QueryFields for Query {
+    fn field_media<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Media, Walked>,
+    ) -> FieldResult<Vec<Media>> {
+        let photo = trail.as_photo().map(|photo| photo.size());
+        let video = trail.as_video().map(|video| video.size().seconds());
+        let owned: OwnedQueryTrail<Video, Walked> = trail.to_owned().downcast();
+
+        executor.context().seen.lock().unwrap().push(format!(
+            "url: {}, photo size: {:?}, video size: {:?}, owned video: {:?}",
+            trail.url(),
+            photo,
+            video,
+            owned,
+        ));
+
+        Ok(vec![])
+    }
+}
+
+pub struct Photo;
+
+impl PhotoFields for Photo {
+    fn field_url<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+
+    fn field_size<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        unimplemented!()
+    }
+}
+
+pub struct Video;
+
+impl VideoFields for Video {
+    fn field_url<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<&String> {
+        unimplemented!()
+    }
+
+    fn field_size<'a>(
+        &self,
+        executor: &Executor<'a, Context>,
+        trail: &QueryTrail<'a, Duration, Walked>,
+    ) -> FieldResult<Duration> {
+        unimplemented!()
+    }
+}
+
+pub struct Duration;
+
+impl DurationFields for Duration {
+    fn field_seconds<'a>(&self, executor: &Executor<'a, Context>) -> FieldResult<i32> {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn test_conflicting_fields_are_reachable_through_downcasts() {
+    let seen = query("query { media { ... on Photo { url size } } }");
+    assert_eq!(
+        seen,
+        vec![
+            "url: true, photo size: Some(true), video size: None, \
+             owned video: OwnedQueryTrail {url, size}"
+        ],
+    );
+
+    let seen = query("query { media { ... on Video { size { seconds } } } }");
+    assert_eq!(
+        seen,
+        vec![
+            "url: false, photo size: None, video size: Some(true), \
+             owned video: OwnedQueryTrail {size {seconds}}"
+        ],
+    );
+}
+
+fn query(query: &str) -> Vec<String> {
+    let ctx = Context {
+        seen: Mutex::new(Vec::new()),
+    };
+    let (juniper_value, errors) = juniper::execute(
+        query,
+        None,
+        &Schema::new(Query, juniper::EmptyMutation::new()),
+        &Variables::new(),
+        &ctx,
+    )
+    .unwrap();
+    assert_eq!(errors, vec![]);
+    ctx.seen.into_inner().unwrap()
+}